@@ -0,0 +1,900 @@
+use crate::error::AppError;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample};
+use serde::Serialize;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+// 麦克风电平推送间隔（每秒 20 次）
+const MIC_LEVEL_INTERVAL_MS: u64 = 50;
+
+// 静音时 dBFS 为负无穷，统一截到该下限，避免序列化为 null
+const MIN_DBFS: f32 = -100.0;
+
+// 音频设备列表的轮询间隔
+const AUDIO_WATCH_INTERVAL_MS: u64 = 2000;
+
+// 单次录音测试的时长范围（毫秒）
+const MIN_MIC_TEST_MS: u64 = 200;
+const MAX_MIC_TEST_MS: u64 = 10_000;
+// 返回给前端绘制的波形点数
+const WAVEFORM_POINTS: usize = 300;
+// 样本绝对值达到该值即视为削波
+const CLIP_THRESHOLD: f32 = 0.999;
+// 估计底噪时的分段长度（毫秒）
+const NOISE_WINDOW_MS: u32 = 50;
+// 估计主频时分析的样本数，以及信号低于该电平时不估计
+const SPECTRUM_SIZE: usize = 4096;
+const MIN_SPECTRUM_DBFS: f32 = -60.0;
+
+#[derive(Serialize)]
+pub struct AudioDeviceDetail {
+    name: String,
+    is_default: bool,
+    // 系统默认的流配置，取不到时为 None
+    default_sample_rate: Option<u32>,
+    default_channels: Option<u16>,
+    default_sample_format: Option<String>,
+    // 所有支持的配置中出现过的声道数与采样格式（如 "i16"、"f32"），去重排序
+    channel_counts: Vec<u16>,
+    sample_formats: Vec<String>,
+    // cpal 报告的全部配置范围，与驱动返回的顺序一致
+    supported_configs: Vec<AudioConfigRange>,
+    // 默认配置或配置列表查询失败的原因（蓝牙耳机断开、设备被独占等），设备仍保留在列表中
+    config_error: Option<String>,
+}
+
+// 一组声道数与采样格式下支持的采样率区间
+#[derive(Serialize)]
+pub struct AudioConfigRange {
+    channels: u16,
+    min_sample_rate: u32,
+    max_sample_rate: u32,
+    sample_format: String,
+    // 缓冲区大小（帧），驱动未报告时为 None
+    min_buffer_size: Option<u32>,
+    max_buffer_size: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct AudioDevicesDetailed {
+    inputs: Vec<AudioDeviceDetail>,
+    outputs: Vec<AudioDeviceDetail>,
+}
+
+#[tauri::command]
+pub fn list_audio_devices_detailed() -> Result<AudioDevicesDetailed, AppError> {
+    let host = cpal::default_host();
+    let default_input = host.default_input_device().and_then(|d| d.name().ok());
+    let default_output = host.default_output_device().and_then(|d| d.name().ok());
+
+    let inputs = host
+        .input_devices()
+        .map_err(|e| AppError::Audio(format!("input devices: {}", e)))?
+        .map(|d| describe_device(&d, true, default_input.as_deref()))
+        .collect();
+    let outputs = host
+        .output_devices()
+        .map_err(|e| AppError::Audio(format!("output devices: {}", e)))?
+        .map(|d| describe_device(&d, false, default_output.as_deref()))
+        .collect();
+    Ok(AudioDevicesDetailed {
+        inputs: dedup_by_name(inputs, |d| &d.name),
+        outputs: dedup_by_name(outputs, |d| &d.name),
+    })
+}
+
+// 部分主机上 cpal 会把同一物理设备枚举多次，按名称去重并保留首次出现的顺序
+pub fn dedup_by_name<T>(items: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut seen = std::collections::HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(name(item).to_string()))
+        .collect()
+}
+
+// 去重后的设备名列表，以及默认设备在其中的下标；同名设备只保留第一个，默认设备指向它
+pub fn dedup_names_with_default(
+    names: Vec<String>,
+    default_name: Option<&str>,
+) -> (Vec<String>, Option<usize>) {
+    let names = dedup_by_name(names, |n| n.as_str());
+    let default_index = default_name.and_then(|name| names.iter().position(|n| n == name));
+    (names, default_index)
+}
+
+// 配置枚举失败（设备被占用、驱动异常）时仍保留该设备，配置列表为空并记录 config_error
+fn describe_device(
+    device: &cpal::Device,
+    input: bool,
+    default_name: Option<&str>,
+) -> AudioDeviceDetail {
+    let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+    let mut errors: Vec<String> = Vec::new();
+    let default_config = if input {
+        device.default_input_config()
+    } else {
+        device.default_output_config()
+    }
+    .map_err(|e| errors.push(format!("default config: {}", e)))
+    .ok();
+
+    let ranges: Vec<cpal::SupportedStreamConfigRange> = if input {
+        device.supported_input_configs().map(|c| c.collect())
+    } else {
+        device.supported_output_configs().map(|c| c.collect())
+    }
+    .map_err(|e| errors.push(format!("supported configs: {}", e)))
+    .unwrap_or_default();
+    let mut channel_counts: Vec<u16> = ranges.iter().map(|r| r.channels()).collect();
+    channel_counts.sort_unstable();
+    channel_counts.dedup();
+    let mut sample_formats: Vec<String> = ranges
+        .iter()
+        .map(|r| r.sample_format().to_string())
+        .collect();
+    sample_formats.sort();
+    sample_formats.dedup();
+    let supported_configs = ranges
+        .iter()
+        .map(|r| {
+            let buffer_size = match r.buffer_size() {
+                cpal::SupportedBufferSize::Range { min, max } => (Some(*min), Some(*max)),
+                cpal::SupportedBufferSize::Unknown => (None, None),
+            };
+            AudioConfigRange {
+                channels: r.channels(),
+                min_sample_rate: r.min_sample_rate().0,
+                max_sample_rate: r.max_sample_rate().0,
+                sample_format: r.sample_format().to_string(),
+                min_buffer_size: buffer_size.0,
+                max_buffer_size: buffer_size.1,
+            }
+        })
+        .collect();
+
+    AudioDeviceDetail {
+        is_default: default_name == Some(name.as_str()),
+        name,
+        default_sample_rate: default_config.as_ref().map(|c| c.sample_rate().0),
+        default_channels: default_config.as_ref().map(|c| c.channels()),
+        default_sample_format: default_config
+            .as_ref()
+            .map(|c| c.sample_format().to_string()),
+        channel_counts,
+        sample_formats,
+        supported_configs,
+        config_error: (!errors.is_empty()).then(|| errors.join("; ")),
+    }
+}
+
+// 运行中的麦克风监听；cpal 的 Stream 不能跨线程，放在独立线程中持有，这里只保留停止信号
+#[derive(Default)]
+pub struct MicMonitor {
+    stop: Mutex<Option<mpsc::Sender<()>>>,
+}
+
+impl MicMonitor {
+    pub fn stop(&self) {
+        if let Some(stop) = self.stop.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            let _ = stop.send(());
+        }
+    }
+}
+
+// 相对满幅的分贝值（dBFS），范围 MIN_DBFS ~ 0.0
+#[derive(Serialize, Clone, Debug)]
+struct MicLevel {
+    rms_dbfs: f32,
+    peak_dbfs: f32,
+}
+
+// 线性幅度（0.0 ~ 1.0）换算为 dBFS
+fn to_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * amplitude.log10()).clamp(MIN_DBFS, 0.0)
+}
+
+#[derive(Default)]
+struct LevelAccumulator {
+    sum_squares: f64,
+    peak: f32,
+    count: u64,
+}
+
+impl LevelAccumulator {
+    // 累加一段样本，整数格式先换算到 -1.0 ~ 1.0
+    fn add<T: Sample>(&mut self, samples: &[T])
+    where
+        f32: FromSample<T>,
+    {
+        for &sample in samples {
+            let value = sample.to_sample::<f32>();
+            self.sum_squares += (value * value) as f64;
+            self.peak = self.peak.max(value.abs());
+            self.count += 1;
+        }
+    }
+
+    // 目前累加的样本的 RMS 与峰值电平，没有样本时均为 MIN_DBFS
+    fn level(&self) -> MicLevel {
+        let rms = if self.count > 0 {
+            (self.sum_squares / self.count as f64).sqrt() as f32
+        } else {
+            0.0
+        };
+        MicLevel {
+            rms_dbfs: to_dbfs(rms),
+            peak_dbfs: to_dbfs(self.peak),
+        }
+    }
+}
+
+// device_name 为 None 时使用默认输入设备；已有监听时先停止旧的
+#[tauri::command]
+pub fn start_mic_monitor(
+    app: AppHandle,
+    monitor: State<'_, MicMonitor>,
+    device_name: Option<String>,
+) -> Result<(), AppError> {
+    monitor.stop();
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let error_stop = stop_tx.clone();
+    std::thread::spawn(move || {
+        let level = Arc::new(Mutex::new(LevelAccumulator::default()));
+        let stream =
+            match open_level_stream(&app, device_name.as_deref(), level.clone(), error_stop) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+        let _ = ready_tx.send(Ok(()));
+
+        // 收到停止信号（包括流出错）或 stop_mic_monitor 的 Sender 被丢弃时退出，Stream 随之释放
+        while let Err(mpsc::RecvTimeoutError::Timeout) =
+            stop_rx.recv_timeout(Duration::from_millis(MIC_LEVEL_INTERVAL_MS))
+        {
+            let acc = std::mem::take(&mut *level.lock().unwrap_or_else(|e| e.into_inner()));
+            let _ = app.emit("mic-level", acc.level());
+        }
+        drop(stream);
+    });
+
+    match ready_rx.recv() {
+        Ok(Ok(())) => {
+            *monitor.stop.lock().unwrap_or_else(|e| e.into_inner()) = Some(stop_tx);
+            Ok(())
+        }
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(AppError::Audio("mic monitor thread exited".to_string())),
+    }
+}
+
+#[tauri::command]
+pub fn stop_mic_monitor(monitor: State<'_, MicMonitor>) {
+    monitor.stop();
+}
+
+// 运行中的设备变化监听，与 MicMonitor 相同只保留停止信号
+#[derive(Default)]
+pub struct AudioWatch {
+    stop: Mutex<Option<mpsc::Sender<()>>>,
+}
+
+impl AudioWatch {
+    pub fn stop(&self) {
+        if let Some(stop) = self.stop.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            let _ = stop.send(());
+        }
+    }
+}
+
+// 与上一次轮询相比新增 / 移除的设备名，以及当前的默认设备
+#[derive(Serialize, Clone)]
+struct AudioDevicesChanged {
+    added_inputs: Vec<String>,
+    removed_inputs: Vec<String>,
+    added_outputs: Vec<String>,
+    removed_outputs: Vec<String>,
+    default_input: Option<String>,
+    default_output: Option<String>,
+}
+
+#[derive(PartialEq)]
+struct DeviceSnapshot {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    default_input: Option<String>,
+    default_output: Option<String>,
+}
+
+// 每 2 秒检查一次设备名，列表或默认设备变化时推送 "audio-devices-changed"；已有监听时先停止旧的
+#[tauri::command]
+pub fn start_audio_watch(app: AppHandle, watch: State<'_, AudioWatch>) {
+    watch.stop();
+
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        let mut previous = device_snapshot(None);
+        while let Err(mpsc::RecvTimeoutError::Timeout) =
+            stop_rx.recv_timeout(Duration::from_millis(AUDIO_WATCH_INTERVAL_MS))
+        {
+            // 枚举失败（音频服务重启等）时跳过本轮，避免把所有设备报告为已移除
+            let Some(current) = device_snapshot(previous.as_ref()) else {
+                continue;
+            };
+            if let Some(prev) = previous.as_ref().filter(|prev| **prev != current) {
+                let diff = |new: &[String], old: &[String]| -> Vec<String> {
+                    new.iter().filter(|n| !old.contains(n)).cloned().collect()
+                };
+                let _ = app.emit(
+                    "audio-devices-changed",
+                    AudioDevicesChanged {
+                        added_inputs: diff(&current.inputs, &prev.inputs),
+                        removed_inputs: diff(&prev.inputs, &current.inputs),
+                        added_outputs: diff(&current.outputs, &prev.outputs),
+                        removed_outputs: diff(&prev.outputs, &current.outputs),
+                        default_input: current.default_input.clone(),
+                        default_output: current.default_output.clone(),
+                    },
+                );
+            }
+            previous = Some(current);
+        }
+    });
+    *watch.stop.lock().unwrap_or_else(|e| e.into_inner()) = Some(stop_tx);
+}
+
+#[tauri::command]
+pub fn stop_audio_watch(watch: State<'_, AudioWatch>) {
+    watch.stop();
+}
+
+// Linux（ALSA）上 cpal 枚举设备时会逐个打开 PCM 句柄，每次轮询都会短暂占用 hw: 设备，
+// 被其他应用占用的设备还会打开失败。这里只读取 ALSA 的设备名提示（与 cpal 使用的名称相同），
+// 不打开任何设备；提示中没有方向的设备同时计入输入与输出
+#[cfg(target_os = "linux")]
+fn device_snapshot(_previous: Option<&DeviceSnapshot>) -> Option<DeviceSnapshot> {
+    let hints: Vec<alsa::device_name::Hint> = alsa::device_name::HintIter::new_str(None, "pcm")
+        .ok()?
+        .collect();
+    let names = |direction: alsa::Direction| -> Vec<String> {
+        let names: Vec<String> = hints
+            .iter()
+            .filter(|hint| hint.direction.is_none() || hint.direction == Some(direction))
+            .filter_map(|hint| hint.name.clone())
+            .filter(|name| name != "null")
+            .collect();
+        dedup_by_name(names, |n| n.as_str())
+    };
+    // ALSA 的默认设备固定为 "default"，获取时不会打开设备
+    let host = cpal::default_host();
+    Some(DeviceSnapshot {
+        inputs: names(alsa::Direction::Capture),
+        outputs: names(alsa::Direction::Playback),
+        default_input: host.default_input_device().and_then(|d| d.name().ok()),
+        default_output: host.default_output_device().and_then(|d| d.name().ok()),
+    })
+}
+
+// CoreAudio / WASAPI 区分输入输出时只查询设备支持的配置，不建立音频流。
+// 查询失败（被其他应用独占、蓝牙正在重连）的设备仍在系统设备列表中，沿用上一次的归类，不报告为移除
+#[cfg(not(target_os = "linux"))]
+fn device_snapshot(previous: Option<&DeviceSnapshot>) -> Option<DeviceSnapshot> {
+    let host = cpal::default_host();
+    let present = device_names(host.devices())?;
+    let mut inputs = device_names(host.input_devices())?;
+    let mut outputs = device_names(host.output_devices())?;
+    if let Some(previous) = previous {
+        let keep = |current: &mut Vec<String>, previous: &[String]| {
+            for name in previous {
+                if present.contains(name) && !current.contains(name) {
+                    current.push(name.clone());
+                }
+            }
+        };
+        keep(&mut inputs, &previous.inputs);
+        keep(&mut outputs, &previous.outputs);
+    }
+    Some(DeviceSnapshot {
+        inputs,
+        outputs,
+        default_input: host.default_input_device().and_then(|d| d.name().ok()),
+        default_output: host.default_output_device().and_then(|d| d.name().ok()),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn device_names(
+    devices: Result<impl Iterator<Item = cpal::Device>, cpal::DevicesError>,
+) -> Option<Vec<String>> {
+    let names: Vec<String> = devices.ok()?.filter_map(|d| d.name().ok()).collect();
+    Some(dedup_by_name(names, |n| n.as_str()))
+}
+
+#[derive(Serialize)]
+pub struct MicTestReport {
+    device: String,
+    sample_rate: u32,
+    channels: u16,
+    // 实际录到的时长
+    duration_ms: u64,
+    peak_dbfs: f32,
+    rms_dbfs: f32,
+    // 以 50 毫秒分段后最安静的 10% 分段的 RMS，近似没有说话时的底噪
+    noise_floor_dbfs: f32,
+    // 所有声道中达到满幅的样本数
+    clipped_samples: u64,
+    // 最响的一段中能量最大的频率，信号过弱时为 None
+    dominant_frequency_hz: Option<f32>,
+    // 声道混合后按段取绝对值最大的样本（保留正负号），共 300 点左右
+    waveform: Vec<f32>,
+}
+
+// 录制 duration_ms 毫秒后分析电平、底噪、削波与主频；device_name 为 None 时使用默认输入设备
+#[tauri::command]
+pub async fn test_microphone(
+    device_name: Option<String>,
+    duration_ms: u64,
+) -> Result<MicTestReport, AppError> {
+    let duration = Duration::from_millis(duration_ms.clamp(MIN_MIC_TEST_MS, MAX_MIC_TEST_MS));
+    tauri::async_runtime::spawn_blocking(move || {
+        record_and_analyze(device_name.as_deref(), duration)
+    })
+    .await
+    .map_err(|e| AppError::Audio(format!("mic test task: {}", e)))?
+}
+
+// cpal 的 Stream 不能跨线程，录音与分析都在同一个阻塞线程中完成
+fn record_and_analyze(
+    device_name: Option<&str>,
+    duration: Duration,
+) -> Result<MicTestReport, AppError> {
+    let device = find_input_device(device_name)?;
+    let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+    let config = device
+        .default_input_config()
+        .map_err(|e| AppError::Audio(format!("input config: {}", e)))?;
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+
+    let recorded: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    let stream_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+    let sink = recorded.clone();
+    let error_sink = stream_error.clone();
+    let stream = build_input_stream(
+        &device,
+        &config,
+        move |data| {
+            sink.lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend_from_slice(data)
+        },
+        move |e| *error_sink.lock().unwrap_or_else(|e| e.into_inner()) = Some(e.to_string()),
+    )?;
+    stream
+        .play()
+        .map_err(|e| AppError::Audio(format!("start input stream: {}", e)))?;
+    std::thread::sleep(duration);
+    drop(stream);
+
+    if let Some(e) = stream_error
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        return Err(AppError::Audio(format!("input stream: {}", e)));
+    }
+    let samples = std::mem::take(&mut *recorded.lock().unwrap_or_else(|e| e.into_inner()));
+    // 没有麦克风权限时系统仍会回调，但数据全部为 0
+    if samples.iter().all(|&s| s == 0.0) {
+        return Err(mic_blocked_error());
+    }
+
+    Ok(MicTestReport {
+        device: name,
+        ..analyze_recording(&samples, channels, sample_rate)
+    })
+}
+
+// 分析交错排列的多声道样本；device 留空，由调用方填入
+fn analyze_recording(samples: &[f32], channels: u16, sample_rate: u32) -> MicTestReport {
+    let channels = channels.max(1);
+    let sample_rate = sample_rate.max(1);
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let mono: Vec<f32> = samples
+        .chunks(channels as usize)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    let window = (sample_rate * NOISE_WINDOW_MS / 1000).max(1) as usize;
+    let mut window_rms: Vec<f32> = mono.chunks(window).map(rms).collect();
+    window_rms.sort_by(f32::total_cmp);
+
+    MicTestReport {
+        device: String::new(),
+        sample_rate,
+        channels,
+        duration_ms: mono.len() as u64 * 1000 / sample_rate as u64,
+        peak_dbfs: to_dbfs(peak),
+        rms_dbfs: to_dbfs(rms(samples)),
+        noise_floor_dbfs: to_dbfs(
+            window_rms
+                .get(window_rms.len() / 10)
+                .copied()
+                .unwrap_or(0.0),
+        ),
+        clipped_samples: samples.iter().filter(|s| s.abs() >= CLIP_THRESHOLD).count() as u64,
+        dominant_frequency_hz: dominant_frequency(&mono, sample_rate),
+        waveform: mono
+            .chunks(mono.len().div_ceil(WAVEFORM_POINTS).max(1))
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .copied()
+                    .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+                    .unwrap_or(0.0)
+            })
+            .collect(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn mic_blocked_error() -> AppError {
+    AppError::PermissionDenied(
+        "microphone returned only silence; allow this app in System Settings > \
+         Privacy & Security > Microphone"
+            .to_string(),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn mic_blocked_error() -> AppError {
+    AppError::PermissionDenied(
+        "microphone returned only silence; check Settings > Privacy & security > Microphone \
+         and that the device is not muted"
+            .to_string(),
+    )
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn mic_blocked_error() -> AppError {
+    AppError::PermissionDenied(
+        "microphone returned only silence; the device may be muted or blocked".to_string(),
+    )
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / samples.len() as f64).sqrt() as f32
+}
+
+// 取最响的一段加汉宁窗后做 DFT，返回幅度最大的频率（忽略 20 Hz 以下）。
+// 每个频点用旋转因子递推，避免逐点计算三角函数
+fn dominant_frequency(mono: &[f32], sample_rate: u32) -> Option<f32> {
+    let size = SPECTRUM_SIZE.min(mono.len());
+    if size < 64 {
+        return None;
+    }
+    let segment = (0..=mono.len() - size)
+        .step_by(size / 2)
+        .map(|start| &mono[start..start + size])
+        .max_by(|a, b| rms(a).total_cmp(&rms(b)))?;
+    if to_dbfs(rms(segment)) < MIN_SPECTRUM_DBFS {
+        return None;
+    }
+
+    let n = size as f64;
+    let windowed: Vec<f64> = segment
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| {
+            let hann = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / n).cos();
+            s as f64 * hann
+        })
+        .collect();
+    let min_bin = ((20.0 * n / sample_rate as f64).ceil() as usize).max(1);
+    let (bin, _) = (min_bin..size / 2)
+        .map(|k| {
+            let theta = 2.0 * std::f64::consts::PI * k as f64 / n;
+            let (step_re, step_im) = (theta.cos(), -theta.sin());
+            let (mut w_re, mut w_im) = (1.0f64, 0.0f64);
+            let (mut re, mut im) = (0.0f64, 0.0f64);
+            for &x in &windowed {
+                re += x * w_re;
+                im += x * w_im;
+                (w_re, w_im) = (
+                    w_re * step_re - w_im * step_im,
+                    w_re * step_im + w_im * step_re,
+                );
+            }
+            (k, re * re + im * im)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    Some((bin as f64 * sample_rate as f64 / n) as f32)
+}
+
+fn open_level_stream(
+    app: &AppHandle,
+    device_name: Option<&str>,
+    level: Arc<Mutex<LevelAccumulator>>,
+    stop: mpsc::Sender<()>,
+) -> Result<cpal::Stream, AppError> {
+    let device = find_input_device(device_name)?;
+    let config = device
+        .default_input_config()
+        .map_err(|e| AppError::Audio(format!("input config: {}", e)))?;
+
+    // 流出错（设备被拔出等）时通知前端并结束监听线程
+    let app = app.clone();
+    let on_error = move |e: cpal::StreamError| {
+        let _ = app.emit("mic-error", e.to_string());
+        let _ = stop.send(());
+    };
+    let on_data = move |data: &[f32]| {
+        level.lock().unwrap_or_else(|e| e.into_inner()).add(data);
+    };
+    let stream = build_input_stream(&device, &config, on_data, on_error)?;
+    stream
+        .play()
+        .map_err(|e| AppError::Audio(format!("start input stream: {}", e)))?;
+    Ok(stream)
+}
+
+// name 为 None 时返回默认输入设备
+fn find_input_device(name: Option<&str>) -> Result<cpal::Device, AppError> {
+    let host = cpal::default_host();
+    match name {
+        Some(name) => {
+            let devices = host
+                .input_devices()
+                .map_err(|e| AppError::Audio(format!("input devices: {}", e)))?;
+            find_by_name(devices, name, |d| d.name().ok())
+        }
+        None => host
+            .default_input_device()
+            .ok_or_else(|| AppError::Audio("no default input device".to_string())),
+    }
+}
+
+// 主机没有设备或设备已拔出时返回 Audio 错误
+fn find_by_name<D>(
+    devices: impl IntoIterator<Item = D>,
+    name: &str,
+    device_name: impl Fn(&D) -> Option<String>,
+) -> Result<D, AppError> {
+    devices
+        .into_iter()
+        .find(|d| device_name(d).is_some_and(|n| n == name))
+        .ok_or_else(|| AppError::Audio(format!("input device not found: {}", name)))
+}
+
+// 按设备的采样格式建立输入流，样本统一换算为 -1.0 ~ 1.0 的 f32（多声道交错排列）
+fn build_input_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    on_data: impl FnMut(&[f32]) + Send + 'static,
+    on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+) -> Result<cpal::Stream, AppError> {
+    let stream_config = config.config();
+    match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_typed_stream::<f32>(device, &stream_config, on_data, on_error)
+        }
+        cpal::SampleFormat::I16 => {
+            build_typed_stream::<i16>(device, &stream_config, on_data, on_error)
+        }
+        cpal::SampleFormat::U16 => {
+            build_typed_stream::<u16>(device, &stream_config, on_data, on_error)
+        }
+        cpal::SampleFormat::I32 => {
+            build_typed_stream::<i32>(device, &stream_config, on_data, on_error)
+        }
+        cpal::SampleFormat::U8 => {
+            build_typed_stream::<u8>(device, &stream_config, on_data, on_error)
+        }
+        other => {
+            return Err(AppError::Audio(format!(
+                "unsupported sample format: {}",
+                other
+            )))
+        }
+    }
+    .map_err(|e| AppError::Audio(format!("build input stream: {}", e)))
+}
+
+// 换算用的缓冲区在回调之间复用，避免在音频线程上反复分配
+fn build_typed_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut on_data: impl FnMut(&[f32]) + Send + 'static,
+    on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let mut buffer: Vec<f32> = Vec::new();
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            buffer.clear();
+            buffer.extend(data.iter().map(|&sample| sample.to_sample::<f32>()));
+            on_data(&buffer);
+        },
+        on_error,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_of<T: Sample>(samples: &[T]) -> MicLevel
+    where
+        f32: FromSample<T>,
+    {
+        let mut acc = LevelAccumulator::default();
+        acc.add(samples);
+        acc.level()
+    }
+
+    #[test]
+    fn duplicate_device_names_collapse_to_one_entry() {
+        let names = [
+            "Built-in Microphone",
+            "USB Audio",
+            "Built-in Microphone",
+            "HDMI",
+        ]
+        .map(String::from)
+        .to_vec();
+        let (names, default_index) = dedup_names_with_default(names, Some("HDMI"));
+        assert_eq!(names, ["Built-in Microphone", "USB Audio", "HDMI"]);
+        assert_eq!(default_index, Some(2));
+
+        // 两个同名设备中任一个为默认设备时，下标都指向保留下来的第一个
+        let names = ["USB Audio", "USB Audio"].map(String::from).to_vec();
+        let (names, default_index) = dedup_names_with_default(names, Some("USB Audio"));
+        assert_eq!(names, ["USB Audio"]);
+        assert_eq!(default_index, Some(0));
+
+        let (_, default_index) = dedup_names_with_default(vec!["HDMI".to_string()], None);
+        assert_eq!(default_index, None);
+    }
+
+    #[test]
+    fn missing_input_device_is_an_audio_error() {
+        let no_devices: Vec<String> = Vec::new();
+        match find_by_name(no_devices, "USB Audio", |d| Some(d.clone())) {
+            Err(AppError::Audio(msg)) => assert!(msg.contains("USB Audio")),
+            other => panic!("expected Audio error, got {:?}", other),
+        }
+
+        // 名称读取失败的设备不参与匹配
+        let devices = vec![None, Some("Built-in Microphone".to_string())];
+        assert!(matches!(
+            find_by_name(devices.clone(), "USB Audio", |d| d.clone()),
+            Err(AppError::Audio(_))
+        ));
+        assert_eq!(
+            find_by_name(devices, "Built-in Microphone", |d| d.clone()).unwrap(),
+            Some("Built-in Microphone".to_string())
+        );
+    }
+
+    #[test]
+    fn silence_is_min_dbfs() {
+        let level = level_of(&[0.0f32; 480]);
+        assert_eq!(level.rms_dbfs, MIN_DBFS);
+        assert_eq!(level.peak_dbfs, MIN_DBFS);
+
+        let empty = LevelAccumulator::default().level();
+        assert_eq!(empty.rms_dbfs, MIN_DBFS);
+        assert_eq!(empty.peak_dbfs, MIN_DBFS);
+    }
+
+    #[test]
+    fn full_scale_sine_is_minus_3_dbfs_rms() {
+        // 480 个样本正好是 48kHz 下 1kHz 正弦的 10 个周期
+        let sine: Vec<f32> = (0..480)
+            .map(|i| (2.0 * std::f32::consts::PI * i as f32 / 48.0).sin())
+            .collect();
+        let level = level_of(&sine);
+        assert!((level.rms_dbfs + 3.01).abs() < 0.05, "{:?}", level);
+        assert!(level.peak_dbfs > -0.01, "{:?}", level);
+
+        let sine_i16: Vec<i16> = sine.iter().map(|&v| (v * i16::MAX as f32) as i16).collect();
+        let level_i16 = level_of(&sine_i16);
+        assert!(
+            (level_i16.rms_dbfs - level.rms_dbfs).abs() < 0.05,
+            "{:?}",
+            level_i16
+        );
+    }
+
+    #[test]
+    fn clipped_buffer_is_capped_at_zero_dbfs() {
+        let clipped = [i16::MIN, i16::MAX, i16::MIN, i16::MAX];
+        let level = level_of(&clipped);
+        assert!(
+            level.rms_dbfs <= 0.0 && level.rms_dbfs > -0.01,
+            "{:?}",
+            level
+        );
+        assert_eq!(level.peak_dbfs, 0.0);
+
+        // 超出满幅的浮点样本同样截到 0 dBFS
+        let level = level_of(&[1.5f32, -2.0, 1.5, -2.0]);
+        assert_eq!(level.rms_dbfs, 0.0);
+        assert_eq!(level.peak_dbfs, 0.0);
+    }
+
+    // 48kHz 下的立体声正弦，两个声道相同
+    fn stereo_sine(freq: f32, amplitude: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let v = amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / 48_000.0).sin();
+                [v, v]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recording_of_1khz_sine() {
+        let report = analyze_recording(&stereo_sine(1000.0, 0.5, 48_000), 2, 48_000);
+        assert_eq!(report.duration_ms, 1000);
+        assert_eq!(report.clipped_samples, 0);
+        assert!(
+            (report.peak_dbfs + 6.02).abs() < 0.05,
+            "{}",
+            report.peak_dbfs
+        );
+        assert!((report.rms_dbfs + 9.03).abs() < 0.05, "{}", report.rms_dbfs);
+        assert!((report.noise_floor_dbfs - report.rms_dbfs).abs() < 0.1);
+        // 频点间隔为 48000 / 4096 ≈ 11.7 Hz
+        let freq = report.dominant_frequency_hz.expect("dominant frequency");
+        assert!((freq - 1000.0).abs() < 12.0, "{}", freq);
+        assert!(!report.waveform.is_empty() && report.waveform.len() <= WAVEFORM_POINTS);
+        assert!(report.waveform.iter().all(|v| v.abs() <= 0.5));
+    }
+
+    #[test]
+    fn recording_of_silence() {
+        let report = analyze_recording(&[0.0; 9600], 2, 48_000);
+        assert_eq!(report.duration_ms, 100);
+        assert_eq!(report.peak_dbfs, MIN_DBFS);
+        assert_eq!(report.rms_dbfs, MIN_DBFS);
+        assert_eq!(report.noise_floor_dbfs, MIN_DBFS);
+        assert_eq!(report.clipped_samples, 0);
+        assert_eq!(report.dominant_frequency_hz, None);
+        assert!(report.waveform.iter().all(|&v| v == 0.0));
+
+        // 电平低于 MIN_SPECTRUM_DBFS 时不估计主频
+        let quiet = analyze_recording(&stereo_sine(440.0, 0.0005, 4800), 2, 48_000);
+        assert_eq!(quiet.dominant_frequency_hz, None);
+    }
+
+    #[test]
+    fn recording_counts_clipped_samples_on_every_channel() {
+        let mut samples = stereo_sine(440.0, 0.5, 4800);
+        samples[10] = 1.0;
+        samples[11] = -1.0;
+        samples[500] = 1.2;
+        samples[501] = 0.9985;
+        let report = analyze_recording(&samples, 2, 48_000);
+        assert_eq!(report.clipped_samples, 3);
+        assert_eq!(report.peak_dbfs, 0.0);
+    }
+}
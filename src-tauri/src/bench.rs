@@ -0,0 +1,390 @@
+use crate::error::AppError;
+use crate::state::SysState;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// 稳定性测试时长上限（秒）
+const MAX_STABILITY_SECS: u64 = 600;
+// 频率比峰值低出该比例即认为发生降频
+const THROTTLE_FREQUENCY_DROP: f64 = 0.15;
+// CPU 跑分时长范围（毫秒）
+const MIN_CPU_BENCH_MS: u64 = 100;
+const MAX_CPU_BENCH_MS: u64 = 60_000;
+// 跑分线程每完成一批运算检查一次截止时间，一批耗时远小于 1 毫秒
+const CPU_BENCH_BATCH: u64 = 10_000;
+// 跑分进度推送间隔
+const CPU_BENCH_PROGRESS_MS: u64 = 250;
+
+// 稳定性测试的运行状态，同一时间只允许一个测试
+#[derive(Default)]
+pub struct StabilityTest {
+    running: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+#[derive(Serialize)]
+pub struct StabilityResult {
+    // 实际运行的秒数（被取消时小于请求的时长）
+    duration_secs: u64,
+    max_temp: Option<f32>,
+    // 各次采样中所有核心平均频率（MHz）的最小值与平均值
+    min_frequency: Option<u64>,
+    avg_frequency: Option<u64>,
+    throttled: bool,
+    cancelled: bool,
+}
+
+#[derive(Serialize, Clone)]
+struct StabilityProgress {
+    elapsed_secs: u64,
+    duration_secs: u64,
+    temperature: Option<f32>,
+    frequency: Option<u64>,
+}
+
+// 所有核心满载运行指定时长，每秒采样温度与频率
+#[tauri::command]
+pub async fn run_stability_test(
+    app: AppHandle,
+    test: State<'_, StabilityTest>,
+    duration_secs: u64,
+) -> Result<StabilityResult, AppError> {
+    // 使用电池时拒绝运行，避免耗尽电量或过热
+    if crate::power::is_on_battery() {
+        return Err(AppError::PermissionDenied(
+            "refusing to run a stability test on battery power".to_string(),
+        ));
+    }
+    let _running = RunningGuard::acquire(&test.running, "a stability test is already running")?;
+    test.cancelled.store(false, Ordering::SeqCst);
+
+    let duration_secs = duration_secs.clamp(1, MAX_STABILITY_SECS);
+    Ok(stress_and_sample(&app, &test, duration_secs).await)
+}
+
+#[tauri::command]
+pub fn cancel_stability_test(test: State<'_, StabilityTest>) {
+    test.cancelled.store(true, Ordering::SeqCst);
+}
+
+// 负载线程在 guard 释放时（包括提前返回）停止
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// 持有期间 running 为 true；命令被取消（future 被丢弃）时同样会清除
+struct RunningGuard<'a>(&'a AtomicBool);
+
+impl<'a> RunningGuard<'a> {
+    fn acquire(running: &'a AtomicBool, busy: &str) -> Result<Self, AppError> {
+        if running.swap(true, Ordering::SeqCst) {
+            return Err(AppError::Busy(busy.to_string()));
+        }
+        Ok(RunningGuard(running))
+    }
+}
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+async fn stress_and_sample(
+    app: &AppHandle,
+    test: &StabilityTest,
+    duration_secs: u64,
+) -> StabilityResult {
+    let stop = Arc::new(AtomicBool::new(false));
+    let _guard = StopOnDrop(stop.clone());
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    for seed in 0..workers as u64 {
+        let stop = stop.clone();
+        std::thread::spawn(move || burn_cpu(seed, &stop));
+    }
+
+    let state = app.state::<SysState>();
+    let start = Instant::now();
+    let mut max_temp: Option<f32> = None;
+    let mut frequencies: Vec<u64> = Vec::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    // 第一次 tick 立即返回，跳过以便采样时已处于满载
+    interval.tick().await;
+
+    let mut cancelled = false;
+    while start.elapsed().as_secs() < duration_secs {
+        interval.tick().await;
+        if test.cancelled.load(Ordering::SeqCst) {
+            cancelled = true;
+            break;
+        }
+
+        let temperature = crate::sensors::max_temperature(&state);
+        let frequency = {
+            let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+            sys.system.refresh_cpu_frequency();
+            let cpus = sys.system.cpus();
+            (!cpus.is_empty())
+                .then(|| cpus.iter().map(|c| c.frequency()).sum::<u64>() / cpus.len() as u64)
+                .filter(|&f| f > 0)
+        };
+        if let Some(t) = temperature {
+            max_temp = Some(max_temp.map_or(t, |m| m.max(t)));
+        }
+        frequencies.extend(frequency);
+
+        let _ = app.emit(
+            "stability-progress",
+            StabilityProgress {
+                elapsed_secs: start.elapsed().as_secs(),
+                duration_secs,
+                temperature,
+                frequency,
+            },
+        );
+    }
+
+    let min_frequency = frequencies.iter().copied().min();
+    let peak_frequency = frequencies.iter().copied().max();
+    let avg_frequency = (!frequencies.is_empty())
+        .then(|| frequencies.iter().sum::<u64>() / frequencies.len() as u64);
+    let throttled = match (min_frequency, peak_frequency) {
+        (Some(min), Some(peak)) => (min as f64) < peak as f64 * (1.0 - THROTTLE_FREQUENCY_DROP),
+        _ => false,
+    };
+
+    StabilityResult {
+        duration_secs: start.elapsed().as_secs(),
+        max_temp,
+        min_frequency,
+        avg_frequency,
+        throttled,
+        cancelled,
+    }
+}
+
+#[derive(Serialize)]
+pub struct CpuBenchResult {
+    // 实际运行的毫秒数，从所有线程启动到最后一个线程退出
+    duration_ms: u64,
+    threads: usize,
+    // 一次操作为一步整数乘加运算
+    total_ops: u64,
+    ops_per_sec: f64,
+    // 各线程的 ops/sec；线程不绑定核心，由系统调度到各逻辑核心上
+    per_core_ops_per_sec: Vec<f64>,
+}
+
+#[derive(Serialize, Clone)]
+struct CpuBenchProgress {
+    elapsed_ms: u64,
+    duration_ms: u64,
+    // 到目前为止的平均 ops/sec
+    ops_per_sec: f64,
+}
+
+// 每个逻辑核心一个线程，运行固定的整数运算 duration_ms 毫秒，每 250 毫秒推送一次进度
+#[tauri::command]
+pub async fn run_cpu_benchmark(
+    app: AppHandle,
+    test: State<'_, StabilityTest>,
+    duration_ms: u64,
+) -> Result<CpuBenchResult, AppError> {
+    // 与稳定性测试同时运行时两者的结果都没有意义
+    let _running = RunningGuard::acquire(&test.running, "another CPU test is already running")?;
+    cpu_benchmark(&app, duration_ms.clamp(MIN_CPU_BENCH_MS, MAX_CPU_BENCH_MS)).await
+}
+
+async fn cpu_benchmark(app: &AppHandle, duration_ms: u64) -> Result<CpuBenchResult, AppError> {
+    let threads = {
+        let state = app.state::<SysState>();
+        let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+        if sys.system.cpus().is_empty() {
+            sys.system.refresh_cpu_list(sysinfo::CpuRefreshKind::new());
+        }
+        sys.system.cpus().len().max(1)
+    };
+
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(duration_ms);
+    // 命令被取消（future 被丢弃）时也让线程立即退出
+    let stop = Arc::new(AtomicBool::new(false));
+    let _guard = StopOnDrop(stop.clone());
+    let counters: Vec<Arc<AtomicU64>> = (0..threads).map(|_| Arc::new(AtomicU64::new(0))).collect();
+    let handles: Vec<_> = counters
+        .iter()
+        .enumerate()
+        .map(|(seed, ops)| {
+            let (ops, stop) = (ops.clone(), stop.clone());
+            std::thread::spawn(move || bench_worker(seed as u64, deadline, &stop, &ops))
+        })
+        .collect();
+
+    let total = |counters: &[Arc<AtomicU64>]| -> u64 {
+        counters.iter().map(|c| c.load(Ordering::Relaxed)).sum()
+    };
+    let mut interval = tokio::time::interval(Duration::from_millis(CPU_BENCH_PROGRESS_MS));
+    interval.tick().await;
+    while Instant::now() < deadline {
+        interval.tick().await;
+        let elapsed = start.elapsed();
+        let _ = app.emit(
+            "benchmark-progress",
+            CpuBenchProgress {
+                elapsed_ms: (elapsed.as_millis() as u64).min(duration_ms),
+                duration_ms,
+                ops_per_sec: total(&counters) as f64 / elapsed.as_secs_f64(),
+            },
+        );
+    }
+
+    // 线程在截止时间后的下一批运算结束时退出，等待全部退出后再统计
+    tauri::async_runtime::spawn_blocking(move || {
+        handles.into_iter().for_each(|h| {
+            let _ = h.join();
+        })
+    })
+    .await
+    .map_err(|e| AppError::CommandFailed(format!("benchmark threads: {}", e)))?;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let total_ops = total(&counters);
+    Ok(CpuBenchResult {
+        duration_ms: (elapsed * 1000.0) as u64,
+        threads,
+        total_ops,
+        ops_per_sec: total_ops as f64 / elapsed,
+        per_core_ops_per_sec: counters
+            .iter()
+            .map(|c| c.load(Ordering::Relaxed) as f64 / elapsed)
+            .collect(),
+    })
+}
+
+// 与 burn_cpu 相同的运算，按批计数并在截止时间或停止信号后退出
+fn bench_worker(seed: u64, deadline: Instant, stop: &AtomicBool, ops: &AtomicU64) {
+    let mut x = seed.wrapping_add(1);
+    while Instant::now() < deadline && !stop.load(Ordering::Relaxed) {
+        for _ in 0..CPU_BENCH_BATCH {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+        }
+        std::hint::black_box(x);
+        ops.fetch_add(CPU_BENCH_BATCH, Ordering::Relaxed);
+    }
+}
+
+// 整数运算的忙循环，black_box 防止被编译器优化掉
+fn burn_cpu(seed: u64, stop: &AtomicBool) {
+    let mut x = seed.wrapping_add(1);
+    while !stop.load(Ordering::Relaxed) {
+        for _ in 0..100_000 {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+        }
+        std::hint::black_box(x);
+    }
+}
+
+// 内存延迟测试结果（纳秒/次访问）。为粗略估计：受预取、TLB 未命中、
+// 频率调节以及缓存大小检测的影响，与专业工具的结果可能有偏差
+#[derive(Serialize)]
+pub struct MemoryLatency {
+    l1_ns: f64,
+    l2_ns: f64,
+    l3_ns: f64,
+    ram_ns: f64,
+}
+
+// 缓存大小无法检测时使用的默认值（字节）
+const DEFAULT_L1_SIZE: usize = 32 << 10;
+const DEFAULT_L2_SIZE: usize = 512 << 10;
+const DEFAULT_L3_SIZE: usize = 8 << 20;
+// 测试内存延迟的缓冲区下限，需要远大于末级缓存
+const MIN_RAM_BUFFER: usize = 128 << 20;
+const CACHE_LINE: usize = 64;
+const LATENCY_STEPS: usize = 2_000_000;
+
+#[tauri::command]
+pub async fn benchmark_memory_latency() -> Result<MemoryLatency, AppError> {
+    tauri::async_runtime::spawn_blocking(measure_memory_latency)
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("memory latency task: {}", e)))
+}
+
+fn measure_memory_latency() -> MemoryLatency {
+    let cache = crate::cpu::cache_sizes();
+    let l1 = cache.l1d.map_or(DEFAULT_L1_SIZE, |v| v as usize);
+    let l2 = cache.l2.map_or(DEFAULT_L2_SIZE, |v| v as usize);
+    let l3 = cache.l3.map_or(DEFAULT_L3_SIZE, |v| v as usize);
+    // 缓冲区取缓存大小的一半，确保能完整放入该级缓存而不被挤出
+    MemoryLatency {
+        l1_ns: chase_latency(l1 / 2),
+        l2_ns: chase_latency(l2 / 2),
+        l3_ns: chase_latency(l3 / 2),
+        ram_ns: chase_latency((l3 * 8).max(MIN_RAM_BUFFER)),
+    }
+}
+
+// 指针追逐：每个缓存行存放下一个缓存行的下标，按随机顺序串成一个环，
+// 每次访问都依赖上一次的结果，无法并行或被预取器猜中
+fn chase_latency(buffer_bytes: usize) -> f64 {
+    const SLOT: usize = CACHE_LINE / std::mem::size_of::<usize>();
+    let lines = (buffer_bytes / CACHE_LINE).max(2);
+
+    // Sattolo 算法生成单环排列
+    let mut order: Vec<usize> = (0..lines).collect();
+    let mut rng: u64 = 0x9e37_79b9_7f4a_7c15;
+    for i in (1..lines).rev() {
+        rng = rng
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let j = ((rng >> 33) as usize) % i;
+        order.swap(i, j);
+    }
+    let mut buffer = vec![0usize; lines * SLOT];
+    for i in 0..lines {
+        buffer[order[i] * SLOT] = order[(i + 1) % lines] * SLOT;
+    }
+
+    // 先走一圈预热缓存
+    let mut index = 0;
+    for _ in 0..lines {
+        index = buffer[index];
+    }
+    let start = Instant::now();
+    for _ in 0..LATENCY_STEPS {
+        index = buffer[index];
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(index);
+    elapsed.as_nanos() as f64 / LATENCY_STEPS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_guard_rejects_second_test_and_clears_on_drop() {
+        let running = AtomicBool::new(false);
+        let guard = RunningGuard::acquire(&running, "busy").unwrap();
+        assert!(running.load(Ordering::SeqCst));
+        assert!(matches!(
+            RunningGuard::acquire(&running, "busy"),
+            Err(AppError::Busy(_))
+        ));
+        drop(guard);
+        assert!(!running.load(Ordering::SeqCst));
+        assert!(RunningGuard::acquire(&running, "busy").is_ok());
+    }
+}
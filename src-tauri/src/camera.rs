@@ -0,0 +1,219 @@
+use crate::error::AppError;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CameraInfo {
+    // 枚举顺序，与 list_cameras 的顺序一致
+    index: usize,
+    // 打开设备时使用的标识：macOS 为 unique id，Windows 为 PNPDeviceID，Linux 为 /dev/videoN
+    id: String,
+    name: String,
+    // 支持的分辨率，平台不提供时为空
+    resolutions: Vec<CameraMode>,
+    // 所有分辨率中的最高帧率
+    max_fps: Option<f32>,
+}
+
+#[derive(Serialize)]
+pub struct CameraMode {
+    width: u32,
+    height: u32,
+    max_fps: Option<f32>,
+}
+
+#[tauri::command]
+pub fn list_cameras_detailed() -> Result<Vec<CameraInfo>, AppError> {
+    #[cfg(target_os = "macos")]
+    {
+        list_cameras_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(list_cameras_windows())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Ok(list_cameras_linux())
+    }
+}
+
+// system_profiler 只提供名称、型号与唯一 ID，不包含分辨率
+#[cfg(target_os = "macos")]
+fn list_cameras_macos() -> Result<Vec<CameraInfo>, AppError> {
+    let output = std::process::Command::new("system_profiler")
+        .args(["SPCameraDataType", "-json"])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("system_profiler: {}", e)))?;
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .map_err(|e| AppError::Parse(format!("SPCameraDataType JSON: {}", e)))?;
+    let cameras = json
+        .get("SPCameraDataType")
+        .and_then(|v| v.as_array())
+        .map(|cameras| {
+            cameras
+                .iter()
+                .filter_map(|c| {
+                    let name = c.get("_name")?.as_str()?.to_string();
+                    let id = c
+                        .get("spcamera_unique-id")
+                        .or_else(|| c.get("spcamera_model-id"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(&name)
+                        .to_string();
+                    Some((id, name))
+                })
+                .enumerate()
+                .map(|(index, (id, name))| CameraInfo {
+                    index,
+                    id,
+                    name,
+                    resolutions: Vec::new(),
+                    max_fps: None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(cameras)
+}
+
+// Windows 10 起摄像头属于 Camera 类，旧驱动注册在 Image 类（与扫描仪共用）
+#[cfg(target_os = "windows")]
+const WINDOWS_CAMERA_QUERY: &str = "Get-CimInstance Win32_PnPEntity | \
+Where-Object { ($_.PNPClass -eq 'Camera' -or $_.PNPClass -eq 'Image') -and $_.Status -eq 'OK' } | \
+Select-Object Name, PNPDeviceID | ConvertTo-Json";
+
+// list_cameras 在 Windows 上使用的名称列表，查询失败时为空
+#[cfg(target_os = "windows")]
+pub fn windows_camera_names() -> Vec<String> {
+    list_cameras_windows().into_iter().map(|c| c.name).collect()
+}
+
+// PnP 设备信息中没有分辨率，需要 Media Foundation 才能查询
+#[cfg(target_os = "windows")]
+fn list_cameras_windows() -> Vec<CameraInfo> {
+    let Ok(out) = crate::util::run_checked(
+        "powershell",
+        &["-NoProfile", "-Command", WINDOWS_CAMERA_QUERY],
+    ) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&out) else {
+        return Vec::new();
+    };
+    let items = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let name = item.get("Name")?.as_str()?.to_string();
+            let id = item.get("PNPDeviceID")?.as_str()?.to_string();
+            Some((id, name))
+        })
+        .enumerate()
+        .map(|(index, (id, name))| CameraInfo {
+            index,
+            id,
+            name,
+            resolutions: Vec::new(),
+            max_fps: None,
+        })
+        .collect()
+}
+
+// /sys/class/video4linux/videoN：同一摄像头会注册多个节点（采集、元数据），
+// index 为 0 的节点才是采集节点
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn list_cameras_linux() -> Vec<CameraInfo> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/video4linux") else {
+        return Vec::new();
+    };
+    let mut nodes: Vec<(u32, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(dir.join(file))
+                    .ok()
+                    .map(|v| v.trim().to_string())
+            };
+            if read("index").is_some_and(|i| i != "0") {
+                return None;
+            }
+            let node = entry.file_name().to_string_lossy().into_owned();
+            let number = node.strip_prefix("video")?.parse::<u32>().ok()?;
+            Some((number, read("name").unwrap_or_else(|| node.clone())))
+        })
+        .collect();
+    nodes.sort();
+
+    nodes
+        .into_iter()
+        .enumerate()
+        .map(|(index, (number, name))| {
+            let id = format!("/dev/video{}", number);
+            let resolutions = v4l2_modes(&id);
+            let max_fps = resolutions
+                .iter()
+                .filter_map(|m| m.max_fps)
+                .reduce(f32::max);
+            CameraInfo {
+                index,
+                id,
+                name,
+                resolutions,
+                max_fps,
+            }
+        })
+        .collect()
+}
+
+// v4l2-ctl --list-formats-ext 输出形如：
+// 	[0]: 'YUYV' (YUYV 4:2:2)
+// 		Size: Discrete 640x480
+// 			Interval: Discrete 0.033s (30.000 fps)
+// 不同像素格式的相同分辨率合并，保留最高帧率；未安装 v4l-utils 时返回空列表
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn v4l2_modes(device: &str) -> Vec<CameraMode> {
+    let Ok(text) = crate::util::run_checked("v4l2-ctl", &["-d", device, "--list-formats-ext"])
+    else {
+        return Vec::new();
+    };
+    let mut modes: Vec<CameraMode> = Vec::new();
+    let mut current: Option<usize> = None;
+    for line in text.lines().map(str::trim) {
+        if let Some(size) = line.strip_prefix("Size: Discrete ") {
+            let Some((width, height)) = size
+                .split_once('x')
+                .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)))
+            else {
+                current = None;
+                continue;
+            };
+            let existing = modes
+                .iter()
+                .position(|m| m.width == width && m.height == height);
+            current = Some(existing.unwrap_or_else(|| {
+                modes.push(CameraMode {
+                    width,
+                    height,
+                    max_fps: None,
+                });
+                modes.len() - 1
+            }));
+        } else if line.starts_with("Interval:") {
+            let fps = line
+                .rsplit_once('(')
+                .and_then(|(_, v)| v.split_whitespace().next())
+                .and_then(|v| v.parse::<f32>().ok());
+            if let (Some(i), Some(fps)) = (current, fps) {
+                let mode = &mut modes[i];
+                mode.max_fps = Some(mode.max_fps.map_or(fps, |m| m.max(fps)));
+            }
+        }
+    }
+    // 按像素数从大到小排列
+    modes.sort_by_key(|m| std::cmp::Reverse(m.width as u64 * m.height as u64));
+    modes
+}
@@ -0,0 +1,580 @@
+use serde::Serialize;
+
+// C-state 采样间隔
+#[cfg(target_os = "linux")]
+const CSTATE_SAMPLE_MS: u64 = 500;
+
+#[derive(Serialize)]
+pub struct CStateInfo {
+    cpu: String,
+    state: String,
+    name: String,
+    residency_pct: f32,
+    usage: u64,
+}
+
+// 1 / 5 / 15 分钟平均负载，附带逻辑核心数以便前端按核心数归一化
+#[derive(Serialize)]
+pub struct LoadAvg {
+    one: f64,
+    five: f64,
+    fifteen: f64,
+    logical_cores: usize,
+}
+
+// Windows 没有平均负载的概念，返回 None
+pub fn load_average(logical_cores: usize) -> Option<LoadAvg> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+    let load = sysinfo::System::load_average();
+    Some(LoadAvg {
+        one: load.one,
+        five: load.five,
+        fifteen: load.fifteen,
+        logical_cores,
+    })
+}
+
+// 供仪表盘单独轮询；Windows 上返回 None，前端据此隐藏该组件
+#[tauri::command]
+pub fn get_load_average() -> Option<LoadAvg> {
+    let logical_cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    load_average(logical_cores)
+}
+
+// CPU 硬件安全特性，None 表示无法判断（如非 x86 架构或平台不提供）
+#[derive(Serialize, Default)]
+pub struct CpuSecurityFeatures {
+    smap: Option<bool>,
+    smep: Option<bool>,
+    nx_bit: Option<bool>,
+    // 控制流强制技术（影子栈 / 间接跳转跟踪）
+    cet: Option<bool>,
+    sgx: Option<bool>,
+}
+
+#[tauri::command]
+pub fn get_cpu_security_features() -> CpuSecurityFeatures {
+    #[cfg(target_os = "linux")]
+    {
+        cpu_security_features_linux().unwrap_or_default()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        cpu_security_features_macos().unwrap_or_default()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        cpu_security_features_windows()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        CpuSecurityFeatures::default()
+    }
+}
+
+// /proc/cpuinfo 的 flags 只列出内核实际启用的特性（如 nosmap 启动后不再有 smap），
+// ARM 上没有 flags 行
+#[cfg(target_os = "linux")]
+fn cpu_security_features_linux() -> Option<CpuSecurityFeatures> {
+    let content = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    let flags: std::collections::HashSet<&str> = content
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "flags").then_some(value)
+        })?
+        .split_whitespace()
+        .collect();
+    let has = |flag: &str| Some(flags.contains(flag));
+    Some(CpuSecurityFeatures {
+        smap: has("smap"),
+        smep: has("smep"),
+        nx_bit: has("nx"),
+        cet: Some(flags.contains("user_shstk") || flags.contains("ibt")),
+        sgx: has("sgx"),
+    })
+}
+
+// Intel Mac 的 sysctl 特性列表（XD 即 NX），Apple Silicon 上这些键不存在
+#[cfg(target_os = "macos")]
+fn cpu_security_features_macos() -> Option<CpuSecurityFeatures> {
+    let out = crate::util::run_checked(
+        "sysctl",
+        &[
+            "-n",
+            "machdep.cpu.features",
+            "machdep.cpu.leaf7_features",
+            "machdep.cpu.extfeatures",
+        ],
+    )
+    .ok()?;
+    let flags: std::collections::HashSet<&str> = out.split_whitespace().collect();
+    let has = |flag: &str| Some(flags.contains(flag));
+    Some(CpuSecurityFeatures {
+        smap: has("SMAP"),
+        smep: has("SMEP"),
+        nx_bit: has("XD"),
+        cet: Some(flags.contains("CET_SS") || flags.contains("CET_IBT")),
+        sgx: has("SGX"),
+    })
+}
+
+// Windows 只能方便地查询 DEP（依赖 NX 位）是否可用
+#[cfg(target_os = "windows")]
+fn cpu_security_features_windows() -> CpuSecurityFeatures {
+    let nx_bit = crate::util::run_checked(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "(Get-CimInstance Win32_OperatingSystem).DataExecutionPrevention_Available",
+        ],
+    )
+    .ok()
+    .and_then(|v| match v.trim() {
+        "True" => Some(true),
+        "False" => Some(false),
+        _ => None,
+    });
+    CpuSecurityFeatures {
+        nx_bit,
+        ..Default::default()
+    }
+}
+
+#[tauri::command]
+pub async fn get_cstate_residency() -> Vec<CStateInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        let before = read_cstates_linux();
+        let start = std::time::Instant::now();
+        tokio::time::sleep(std::time::Duration::from_millis(CSTATE_SAMPLE_MS)).await;
+        let after = read_cstates_linux();
+        let elapsed_us = start.elapsed().as_micros().max(1) as f64;
+
+        // 按 (cpu, state) 对两次采样求差，time 单位为微秒
+        after
+            .into_iter()
+            .filter_map(|sample| {
+                let prev = before
+                    .iter()
+                    .find(|b| b.cpu == sample.cpu && b.state == sample.state)?;
+                let time_delta = sample.time_us.saturating_sub(prev.time_us) as f64;
+                Some(CStateInfo {
+                    residency_pct: (time_delta / elapsed_us * 100.0).clamp(0.0, 100.0) as f32,
+                    usage: sample.usage.saturating_sub(prev.usage),
+                    cpu: sample.cpu,
+                    state: sample.state,
+                    name: sample.name,
+                })
+            })
+            .collect()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+struct CStateSample {
+    cpu: String,
+    state: String,
+    name: String,
+    time_us: u64,
+    usage: u64,
+}
+
+// 读取 /sys/devices/system/cpu/cpu*/cpuidle/state*/{name,time,usage}
+#[cfg(target_os = "linux")]
+fn read_cstates_linux() -> Vec<CStateSample> {
+    use std::fs;
+
+    let read = |path: &std::path::Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let mut samples = Vec::new();
+
+    let Ok(cpus) = fs::read_dir("/sys/devices/system/cpu") else {
+        return samples;
+    };
+    for cpu in cpus.flatten() {
+        let cpu_name = cpu.file_name().to_string_lossy().into_owned();
+        let is_cpu_dir = cpu_name
+            .strip_prefix("cpu")
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if !is_cpu_dir {
+            continue;
+        }
+        let Ok(states) = fs::read_dir(cpu.path().join("cpuidle")) else {
+            continue;
+        };
+        for state in states.flatten() {
+            let state_name = state.file_name().to_string_lossy().into_owned();
+            if !state_name.starts_with("state") {
+                continue;
+            }
+            let dir = state.path();
+            let (Some(name), Some(time), Some(usage)) = (
+                read(&dir.join("name")),
+                read(&dir.join("time")).and_then(|s| s.parse().ok()),
+                read(&dir.join("usage")).and_then(|s| s.parse().ok()),
+            ) else {
+                continue;
+            };
+            samples.push(CStateSample {
+                cpu: cpu_name.clone(),
+                state: state_name,
+                name,
+                time_us: time,
+                usage,
+            });
+        }
+    }
+
+    // 按编号数值排序，避免 cpu10 排在 cpu2 前面
+    let index = |s: &str| -> u32 {
+        s.trim_start_matches(|c: char| !c.is_ascii_digit())
+            .parse()
+            .unwrap_or(0)
+    };
+    samples.sort_by_key(|s| (index(&s.cpu), index(&s.state)));
+    samples
+}
+
+// 各级缓存大小（字节），无法检测的级别为 None
+#[derive(Serialize, Default, Clone)]
+pub struct CacheSizes {
+    pub l1d: Option<u64>,
+    pub l1i: Option<u64>,
+    pub l2: Option<u64>,
+    pub l3: Option<u64>,
+}
+
+// 单个逻辑核心的频率范围（MHz），平台不提供的项为 None
+#[derive(Serialize, Clone)]
+pub struct CoreDetails {
+    index: usize,
+    min_mhz: Option<u64>,
+    max_mhz: Option<u64>,
+    base_mhz: Option<u64>,
+    // 混合架构 CPU 上为 "performance" / "efficiency"，无法判断时为 None
+    core_kind: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CpuDetails {
+    cache: CacheSizes,
+    // 运行时检测到的指令集扩展，如 "avx2"、"sse4.2"、"neon"
+    features: Vec<String>,
+    cores: Vec<CoreDetails>,
+}
+
+pub fn cpu_details(logical_cores: usize) -> CpuDetails {
+    // Windows 上缓存与频率来自同一次 WMI 查询
+    #[cfg(target_os = "windows")]
+    let (cache, cores) = {
+        let processor = windows_processor();
+        (
+            windows_cache_sizes(processor.as_ref()),
+            windows_core_details(processor.as_ref(), logical_cores),
+        )
+    };
+    #[cfg(not(target_os = "windows"))]
+    let (cache, cores) = (cache_sizes(), core_details(logical_cores));
+    CpuDetails {
+        cache,
+        features: instruction_features(),
+        cores,
+    }
+}
+
+// 通过 std::arch 在运行时检测，macOS 上标准库内部同样读取 sysctl hw.optional.*
+fn instruction_features() -> Vec<String> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let features = {
+        let mut features: Vec<&str> = Vec::new();
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_x86_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!(
+            "sse2",
+            "sse3",
+            "ssse3",
+            "sse4.1",
+            "sse4.2",
+            "popcnt",
+            "aes",
+            "pclmulqdq",
+            "sha",
+            "avx",
+            "avx2",
+            "fma",
+            "f16c",
+            "bmi1",
+            "bmi2",
+            "avx512f",
+            "avx512bw",
+            "avx512vl",
+            "avx512vnni"
+        );
+        features
+    };
+    #[cfg(target_arch = "aarch64")]
+    let features = {
+        let mut features: Vec<&str> = Vec::new();
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_aarch64_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!(
+            "neon", "aes", "sha2", "sha3", "crc", "lse", "fp16", "dotprod", "i8mm", "bf16", "sve",
+            "sve2"
+        );
+        features
+    };
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    let features: Vec<&str> = Vec::new();
+    features.into_iter().map(String::from).collect()
+}
+
+#[cfg(target_os = "linux")]
+pub fn cache_sizes() -> CacheSizes {
+    let mut sizes = CacheSizes::default();
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu/cpu0/cache") else {
+        return sizes;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+        let (Some(level), Some(kind), Some(size)) = (read("level"), read("type"), read("size"))
+        else {
+            continue;
+        };
+        // size 形如 "32K" / "8192K" / "16M"
+        let size = size.trim();
+        let (number, multiplier) = match size.chars().last() {
+            Some('K') => (&size[..size.len() - 1], 1 << 10),
+            Some('M') => (&size[..size.len() - 1], 1 << 20),
+            _ => (size, 1),
+        };
+        let bytes = number.parse::<u64>().ok().map(|n| n * multiplier);
+        let slot = match (level.trim(), kind.trim()) {
+            ("1", "Instruction") => &mut sizes.l1i,
+            ("1", _) => &mut sizes.l1d,
+            ("2", _) => &mut sizes.l2,
+            ("3", _) => &mut sizes.l3,
+            _ => continue,
+        };
+        *slot = bytes;
+    }
+    sizes
+}
+
+// cpufreq 以 kHz 为单位；base_frequency 仅 intel_pstate 驱动提供。
+// Intel 混合架构在 /sys/devices/cpu_core 与 cpu_atom 下分别列出 P 核与 E 核
+#[cfg(target_os = "linux")]
+fn core_details(logical_cores: usize) -> Vec<CoreDetails> {
+    let cpu_list = |path: &str| {
+        std::fs::read_to_string(path)
+            .map(|v| parse_cpu_list(&v))
+            .unwrap_or_default()
+    };
+    let performance = cpu_list("/sys/devices/cpu_core/cpus");
+    let efficiency = cpu_list("/sys/devices/cpu_atom/cpus");
+
+    (0..logical_cores)
+        .map(|index| {
+            let mhz = |name: &str| {
+                std::fs::read_to_string(format!(
+                    "/sys/devices/system/cpu/cpu{}/cpufreq/{}",
+                    index, name
+                ))
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(|khz| khz / 1000)
+            };
+            let core_kind = if performance.contains(&index) {
+                Some("performance".to_string())
+            } else if efficiency.contains(&index) {
+                Some("efficiency".to_string())
+            } else {
+                None
+            };
+            CoreDetails {
+                index,
+                min_mhz: mhz("cpuinfo_min_freq"),
+                max_mhz: mhz("cpuinfo_max_freq"),
+                base_mhz: mhz("base_frequency"),
+                core_kind,
+            }
+        })
+        .collect()
+}
+
+// 解析 "0-3,8,10-11" 形式的 CPU 列表
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|part| match part.split_once('-') {
+            Some((start, end)) => Some(start.parse().ok()?..=end.parse().ok()?),
+            None => part.parse().ok().map(|n| n..=n),
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl_u64(key: &str) -> Option<u64> {
+    crate::util::run_checked("sysctl", &["-n", key])
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&v| v > 0)
+}
+
+#[cfg(target_os = "macos")]
+pub fn cache_sizes() -> CacheSizes {
+    CacheSizes {
+        l1d: sysctl_u64("hw.l1dcachesize"),
+        l1i: sysctl_u64("hw.l1icachesize"),
+        l2: sysctl_u64("hw.l2cachesize"),
+        l3: sysctl_u64("hw.l3cachesize"),
+    }
+}
+
+// 频率键只在 Intel Mac 上存在（Hz）。Apple Silicon 上 perflevel0 为性能核、
+// perflevel1 为能效核，系统按先能效核后性能核的顺序编号
+#[cfg(target_os = "macos")]
+fn core_details(logical_cores: usize) -> Vec<CoreDetails> {
+    let mhz = |key: &str| sysctl_u64(key).map(|hz| hz / 1_000_000);
+    let (min_mhz, max_mhz, base_mhz) = (
+        mhz("hw.cpufrequency_min"),
+        mhz("hw.cpufrequency_max"),
+        mhz("hw.cpufrequency"),
+    );
+    let efficiency_cores = if sysctl_u64("hw.nperflevels").unwrap_or(1) >= 2 {
+        sysctl_u64("hw.perflevel1.logicalcpu").map(|n| n as usize)
+    } else {
+        None
+    };
+
+    (0..logical_cores)
+        .map(|index| CoreDetails {
+            index,
+            min_mhz,
+            max_mhz,
+            base_mhz,
+            core_kind: efficiency_cores.map(|e| {
+                if index < e {
+                    "efficiency".to_string()
+                } else {
+                    "performance".to_string()
+                }
+            }),
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn cache_sizes() -> CacheSizes {
+    CacheSizes::default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn core_details(_logical_cores: usize) -> Vec<CoreDetails> {
+    Vec::new()
+}
+
+#[cfg(target_os = "windows")]
+struct WindowsProcessor {
+    max_clock_mhz: Option<u64>,
+    l2_kb: Option<u64>,
+    l3_kb: Option<u64>,
+}
+
+// 多路 CPU 时只取第一颗
+#[cfg(target_os = "windows")]
+fn windows_processor() -> Option<WindowsProcessor> {
+    let out = crate::util::run_checked(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Processor | Select-Object -First 1 MaxClockSpeed, L2CacheSize, L3CacheSize | ConvertTo-Json",
+        ],
+    )
+    .ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&out).ok()?;
+    let field = |key: &str| json.get(key).and_then(|v| v.as_u64()).filter(|&v| v > 0);
+    Some(WindowsProcessor {
+        max_clock_mhz: field("MaxClockSpeed"),
+        l2_kb: field("L2CacheSize"),
+        l3_kb: field("L3CacheSize"),
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub fn cache_sizes() -> CacheSizes {
+    windows_cache_sizes(windows_processor().as_ref())
+}
+
+// Win32_Processor 不提供 L1 大小
+#[cfg(target_os = "windows")]
+fn windows_cache_sizes(processor: Option<&WindowsProcessor>) -> CacheSizes {
+    CacheSizes {
+        l2: processor.and_then(|p| p.l2_kb).map(|kb| kb << 10),
+        l3: processor.and_then(|p| p.l3_kb).map(|kb| kb << 10),
+        ..Default::default()
+    }
+}
+
+// WMI 的 MaxClockSpeed 实际是额定（基础）频率，没有每个核心的范围
+#[cfg(target_os = "windows")]
+fn windows_core_details(
+    processor: Option<&WindowsProcessor>,
+    logical_cores: usize,
+) -> Vec<CoreDetails> {
+    let base_mhz = processor.and_then(|p| p.max_clock_mhz);
+    (0..logical_cores)
+        .map(|index| CoreDetails {
+            index,
+            min_mhz: None,
+            max_mhz: None,
+            base_mhz,
+            core_kind: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 前端按这些字段名读取平均负载
+    #[test]
+    fn load_avg_field_names() {
+        let load = LoadAvg {
+            one: 1.5,
+            five: 0.75,
+            fifteen: 0.25,
+            logical_cores: 8,
+        };
+        assert_eq!(
+            serde_json::to_value(&load).unwrap(),
+            serde_json::json!({
+                "one": 1.5,
+                "five": 0.75,
+                "fifteen": 0.25,
+                "logical_cores": 8
+            })
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use crate::error::AppError;
+use crate::util::run_checked;
+use serde::Serialize;
+
+// 勿扰模式 / 专注状态，平台限制：
+// - macOS 12+：读取 ~/Library/DoNotDisturb/DB/Assertions.json（手动开启的专注模式），
+//   按计划自动开启的专注模式不会出现在其中；系统没有公开接口，无法以编程方式切换
+// - macOS 11 及更早：读写 com.apple.notificationcenterui 的 doNotDisturb
+// - Windows：对应“通知”总开关（NOC_GLOBAL_SETTING_TOASTS_ENABLED），不涉及专注助手的优先级规则。
+//   通知平台只在启动时读取该值，没有公开的接口通知它重新加载，写入后要到重新登录才生效，
+//   在此之前读取返回 None
+// - Linux：仅支持 GNOME（org.gnome.desktop.notifications show-banners）
+#[tauri::command]
+pub fn get_do_not_disturb() -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        get_do_not_disturb_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_do_not_disturb_windows()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        get_do_not_disturb_linux()
+    }
+}
+
+#[derive(Serialize)]
+pub struct DoNotDisturbChange {
+    enabled: bool,
+    // 设置已保存但要到重新登录后才生效（Windows），前端应提示用户而不是当作已切换
+    deferred: bool,
+}
+
+#[tauri::command]
+pub fn set_do_not_disturb(enabled: bool) -> Result<DoNotDisturbChange, AppError> {
+    #[cfg(target_os = "macos")]
+    set_do_not_disturb_macos(enabled)?;
+    #[cfg(target_os = "windows")]
+    set_do_not_disturb_windows(enabled)?;
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    set_do_not_disturb_linux(enabled)?;
+    Ok(DoNotDisturbChange {
+        enabled,
+        deferred: cfg!(target_os = "windows"),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn get_do_not_disturb_macos() -> Option<bool> {
+    // macOS 12+ 的专注模式：存在断言记录即表示已开启
+    if let Some(home) = std::env::var_os("HOME") {
+        let path = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+        if let Ok(content) = std::fs::read_to_string(path) {
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let active = json
+                .get("data")
+                .and_then(|v| v.as_array())
+                .map(|data| {
+                    data.iter().any(|entry| {
+                        entry
+                            .get("storeAssertionRecords")
+                            .and_then(|v| v.as_array())
+                            .is_some_and(|records| !records.is_empty())
+                    })
+                })
+                .unwrap_or(false);
+            return Some(active);
+        }
+    }
+
+    // 旧系统的勿扰模式
+    run_checked(
+        "defaults",
+        &["-currentHost", "read", "com.apple.notificationcenterui", "doNotDisturb"],
+    )
+    .ok()
+    .map(|v| v.trim() == "1")
+}
+
+#[cfg(target_os = "macos")]
+fn set_do_not_disturb_macos(enabled: bool) -> Result<(), AppError> {
+    let major = run_checked("sw_vers", &["-productVersion"])?
+        .trim()
+        .split('.')
+        .next()
+        .and_then(|v| v.parse::<u32>().ok())
+        .ok_or_else(|| AppError::Parse("sw_vers product version".to_string()))?;
+    if major >= 12 {
+        return Err(AppError::Unsupported(
+            "Focus can't be changed programmatically on macOS 12 or later".to_string(),
+        ));
+    }
+
+    run_checked(
+        "defaults",
+        &[
+            "-currentHost",
+            "write",
+            "com.apple.notificationcenterui",
+            "doNotDisturb",
+            "-boolean",
+            if enabled { "true" } else { "false" },
+        ],
+    )?;
+    // 通知中心重启后才会读取新设置
+    run_checked("killall", &["NotificationCenter"])?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+const WINDOWS_TOASTS_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Notifications\Settings";
+
+// 本进程第一次写入前读到的值，视为当前登录会话中实际生效的设置
+#[cfg(target_os = "windows")]
+static WINDOWS_DND_IN_EFFECT: std::sync::OnceLock<Option<bool>> = std::sync::OnceLock::new();
+
+#[cfg(target_os = "windows")]
+fn get_do_not_disturb_windows() -> Option<bool> {
+    let saved = read_windows_toasts_disabled();
+    match WINDOWS_DND_IN_EFFECT.get() {
+        // 写入的值与生效的值不同，要到重新登录后才生效，当前状态按未知处理
+        Some(&in_effect) if in_effect != saved => None,
+        _ => saved,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_windows_toasts_disabled() -> Option<bool> {
+    // 输出形如 "NOC_GLOBAL_SETTING_TOASTS_ENABLED    REG_DWORD    0x0"，值不存在表示通知开启
+    match run_checked(
+        "reg",
+        &["query", WINDOWS_TOASTS_KEY, "/v", "NOC_GLOBAL_SETTING_TOASTS_ENABLED"],
+    ) {
+        Ok(text) => Some(
+            text.lines()
+                .find(|l| l.contains("NOC_GLOBAL_SETTING_TOASTS_ENABLED"))
+                .and_then(|l| l.split_whitespace().last())
+                .is_some_and(|v| v == "0x0"),
+        ),
+        Err(AppError::CommandFailed(_)) => Some(false),
+        Err(_) => None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_do_not_disturb_windows(enabled: bool) -> Result<(), AppError> {
+    WINDOWS_DND_IN_EFFECT.get_or_init(read_windows_toasts_disabled);
+    run_checked(
+        "reg",
+        &[
+            "add",
+            WINDOWS_TOASTS_KEY,
+            "/v",
+            "NOC_GLOBAL_SETTING_TOASTS_ENABLED",
+            "/t",
+            "REG_DWORD",
+            "/d",
+            if enabled { "0" } else { "1" },
+            "/f",
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_do_not_disturb_linux() -> Option<bool> {
+    // show-banners 为 false 即 GNOME 的勿扰模式
+    let value = run_checked(
+        "gsettings",
+        &["get", "org.gnome.desktop.notifications", "show-banners"],
+    )
+    .ok()?;
+    match value.trim() {
+        "true" => Some(false),
+        "false" => Some(true),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn set_do_not_disturb_linux(enabled: bool) -> Result<(), AppError> {
+    run_checked(
+        "gsettings",
+        &[
+            "set",
+            "org.gnome.desktop.notifications",
+            "show-banners",
+            if enabled { "false" } else { "true" },
+        ],
+    )
+    .map_err(|e| match e {
+        AppError::CommandFailed(msg) => AppError::Unsupported(format!(
+            "notification settings are only supported on GNOME ({})",
+            msg
+        )),
+        e => e,
+    })?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct ForegroundApp {
+    name: String,
+    pid: Option<u32>,
+    // 前台窗口标题，取不到时为 None
+    title: Option<String>,
+}
+
+// 当前获得焦点的应用；没有前台窗口时返回 None
+// macOS 需要“辅助功能”权限才能读取窗口标题，未授权时返回 PermissionDenied
+// Linux 仅支持 X11（依赖 xprop），Wayland 下没有获取活动窗口的通用接口
+#[tauri::command]
+pub fn get_foreground_app() -> Result<Option<ForegroundApp>, AppError> {
+    #[cfg(target_os = "macos")]
+    {
+        get_foreground_app_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_foreground_app_windows()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        get_foreground_app_linux()
+    }
+}
+
+// 输出三行：进程名、PID、前台窗口标题（没有窗口时为空行）
+#[cfg(target_os = "macos")]
+const MACOS_FOREGROUND_SCRIPT: &str = r#"tell application "System Events"
+    set frontApp to first application process whose frontmost is true
+    set appName to name of frontApp
+    set appPid to unix id of frontApp
+    set winTitle to ""
+    try
+        set winTitle to name of front window of frontApp
+    end try
+end tell
+return appName & linefeed & appPid & linefeed & winTitle"#;
+
+#[cfg(target_os = "macos")]
+fn get_foreground_app_macos() -> Result<Option<ForegroundApp>, AppError> {
+    let out = std::process::Command::new("osascript")
+        .args(["-e", MACOS_FOREGROUND_SCRIPT])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("osascript: {}", e)))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        // -1743：未允许控制 System Events；-25211：未授予辅助功能权限
+        if stderr.contains("-1743") || stderr.contains("-25211") {
+            return Err(AppError::PermissionDenied(
+                "accessibility permission is required to read the foreground app".to_string(),
+            ));
+        }
+        return Err(AppError::CommandFailed(format!(
+            "osascript: {}",
+            stderr.trim()
+        )));
+    }
+
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut lines = text.lines();
+    let Some(name) = lines
+        .next()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+    else {
+        return Ok(None);
+    };
+    Ok(Some(ForegroundApp {
+        name,
+        pid: lines.next().and_then(|s| s.trim().parse().ok()),
+        title: lines
+            .next()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty()),
+    }))
+}
+
+// 直接调用 user32 / kernel32，该命令会被定时轮询，不能每次都启动 PowerShell
+#[cfg(target_os = "windows")]
+fn get_foreground_app_windows() -> Result<Option<ForegroundApp>, AppError> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    // SAFETY: 窗口随时可能关闭，之后的调用对失效句柄返回 0，不会访问无效内存
+    let hwnd = unsafe { GetForegroundWindow() };
+    // 没有前台窗口（如锁屏）
+    if hwnd.is_invalid() {
+        return Ok(None);
+    }
+    let mut pid = 0u32;
+    // SAFETY: pid 是有效的可写位置
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32)) };
+    if pid == 0 {
+        return Ok(None);
+    }
+    let Some(name) = windows_process_name(pid) else {
+        return Ok(None);
+    };
+    // SAFETY: 缓冲区多留一位给结尾的 0，GetWindowTextW 返回实际写入的字符数
+    let title = unsafe {
+        let len = GetWindowTextLengthW(hwnd).max(0) as usize;
+        let mut buffer = vec![0u16; len + 1];
+        let copied = GetWindowTextW(hwnd, &mut buffer).max(0) as usize;
+        String::from_utf16_lossy(&buffer[..copied])
+    };
+    Ok(Some(ForegroundApp {
+        name,
+        pid: Some(pid),
+        title: Some(title).filter(|s| !s.is_empty()),
+    }))
+}
+
+// 与任务管理器 / Get-Process 的进程名一致：可执行文件名去掉 .exe。
+// PROCESS_QUERY_LIMITED_INFORMATION 对以管理员身份运行的进程同样可用
+#[cfg(target_os = "windows")]
+fn windows_process_name(pid: u32) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: buffer 与 size 描述同一块内存；句柄在读取后关闭
+    let path = unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut size = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut size,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+        String::from_utf16_lossy(&buffer[..size as usize])
+    };
+    std::path::Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+// xprop -root _NET_ACTIVE_WINDOW 输出形如 "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"，
+// 再读取该窗口的 _NET_WM_PID（"_NET_WM_PID(CARDINAL) = 1234"）与 _NET_WM_NAME
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_foreground_app_linux() -> Result<Option<ForegroundApp>, AppError> {
+    if std::env::var_os("DISPLAY").is_none() {
+        return Err(AppError::Unsupported(
+            "the foreground window can only be read on X11".to_string(),
+        ));
+    }
+    let active = run_checked("xprop", &["-root", "_NET_ACTIVE_WINDOW"])?;
+    let Some(window) = active
+        .split_whitespace()
+        .last()
+        .filter(|id| id.starts_with("0x") && *id != "0x0")
+    else {
+        return Ok(None);
+    };
+
+    let props = run_checked(
+        "xprop",
+        &["-id", window, "_NET_WM_PID", "_NET_WM_NAME", "WM_CLASS"],
+    )?;
+    let value = |key: &str| {
+        props
+            .lines()
+            .find(|l| l.starts_with(key))
+            .and_then(|l| l.split_once(" = "))
+            .map(|(_, v)| v.trim().to_string())
+    };
+    let pid = value("_NET_WM_PID").and_then(|v| v.parse::<u32>().ok());
+    // 字符串属性带引号；WM_CLASS 形如 "firefox", "Firefox"，取第二项（类名）
+    let title = value("_NET_WM_NAME").map(|v| v.trim_matches('"').to_string());
+    let class = value("WM_CLASS").and_then(|v| {
+        v.split(", ")
+            .last()
+            .map(|c| c.trim_matches('"').to_string())
+    });
+    // 优先使用进程名，与进程列表中的名称一致
+    let process_name = pid.and_then(|pid| {
+        std::fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|s| s.trim().to_string())
+    });
+    let Some(name) = process_name.or(class) else {
+        return Ok(None);
+    };
+    Ok(Some(ForegroundApp {
+        name,
+        pid,
+        title: title.filter(|t| !t.is_empty()),
+    }))
+}
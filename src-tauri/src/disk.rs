@@ -0,0 +1,634 @@
+use crate::error::AppError;
+use crate::state::SysState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{DiskKind, Disks};
+use tauri::{AppHandle, Manager, State};
+
+// 磁盘剩余空间历史：最多每小时记录一次，保留 90 天
+const DISK_TREND_SAMPLE_SECS: u64 = 3600;
+const DISK_TREND_RETENTION_DAYS: u32 = 90;
+const DISK_TREND_FILE: &str = "disk_usage_history.json";
+
+#[derive(Serialize)]
+pub struct DiskInfo {
+    name: String,
+    mount_point: String,
+    file_system: String,
+    total_space: u64,
+    available_space: u64,
+    is_removable: bool,
+    is_read_only: bool,
+    // 介质类型："SSD" / "HDD" / "unknown"
+    disk_type: String,
+    // 卷的用途："data" / "system" / "snapshot"，前端据此隐藏系统卷
+    kind: String,
+}
+
+#[tauri::command]
+pub fn list_disks() -> Vec<DiskInfo> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .map(|disk| {
+            // 部分平台拿不到卷名或文件系统类型，用挂载点 / "unknown" 兜底
+            let mount_point = disk.mount_point().to_string_lossy().into_owned();
+            let name = match disk.name().to_string_lossy() {
+                n if n.trim().is_empty() => mount_point.clone(),
+                n => n.into_owned(),
+            };
+            let file_system = match disk.file_system().to_string_lossy() {
+                fs if fs.trim().is_empty() => "unknown".to_string(),
+                fs => fs.into_owned(),
+            };
+            let disk_type = match disk.kind() {
+                DiskKind::SSD => "SSD",
+                DiskKind::HDD => "HDD",
+                DiskKind::Unknown(_) => "unknown",
+            };
+            DiskInfo {
+                kind: volume_kind(&name, &mount_point).to_string(),
+                name,
+                mount_point,
+                file_system,
+                total_space: disk.total_space(),
+                available_space: disk.available_space(),
+                is_removable: disk.is_removable(),
+                is_read_only: disk.is_read_only(),
+                disk_type: disk_type.to_string(),
+            }
+        })
+        .collect()
+}
+
+// macOS 上 APFS 把系统拆成多个卷：根目录 "/" 是只读的系统快照，
+// /System/Volumes 下除 Data 外都是 Preboot / VM / Update 等系统卷
+#[cfg(target_os = "macos")]
+fn volume_kind(name: &str, mount_point: &str) -> &'static str {
+    if name.starts_with("com.apple.os.update-") {
+        "snapshot"
+    } else if mount_point == "/System/Volumes/Data" {
+        "data"
+    } else if mount_point == "/" || mount_point.starts_with("/System/Volumes/") {
+        "system"
+    } else {
+        "data"
+    }
+}
+
+// Linux 上 snap 包以只读 squashfs 挂载在 /snap 下，/boot 与 EFI 分区视为系统卷
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn volume_kind(_name: &str, mount_point: &str) -> &'static str {
+    if mount_point.starts_with("/snap/") {
+        "snapshot"
+    } else if mount_point == "/boot" || mount_point.starts_with("/boot/") {
+        "system"
+    } else {
+        "data"
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn volume_kind(_name: &str, _mount_point: &str) -> &'static str {
+    "data"
+}
+
+#[derive(Serialize)]
+pub struct DiskIoInfo {
+    name: String,
+    // 开机以来的累计读写字节数，平台未报告该计数时为 None
+    read_bytes: Option<u64>,
+    written_bytes: Option<u64>,
+    // 与上一次调用之间的速率（字节/秒），首次调用或计数器重置时为 None
+    read_rate: Option<u64>,
+    write_rate: Option<u64>,
+}
+
+// 各磁盘的（名称, 累计读取字节数, 累计写入字节数）
+type DiskCounters = Vec<(String, Option<u64>, Option<u64>)>;
+
+// sysinfo 0.32 不提供磁盘级 I/O 计数，各平台分别读取系统计数器；
+// macOS / Windows 需要启动 ioreg / PowerShell，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_disk_io(app: AppHandle) -> Result<Vec<DiskIoInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(move || collect_disk_io(&app.state::<SysState>()))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("disk io task: {}", e)))?
+}
+
+fn collect_disk_io(state: &SysState) -> Result<Vec<DiskIoInfo>, AppError> {
+    let counters = read_disk_counters()?;
+    let now = Instant::now();
+
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    let secs = sys
+        .disk_io_sampled_at
+        .map(|prev| (now - prev).as_secs_f64())
+        .filter(|&secs| secs > 0.0);
+    let rate = |current: Option<u64>, previous: Option<u64>| -> Option<u64> {
+        let delta = current?.checked_sub(previous?)?;
+        Some((delta as f64 / secs?) as u64)
+    };
+
+    let disks = counters
+        .iter()
+        .map(|(name, read, written)| {
+            let prev = sys.disk_io_prev.get(name);
+            DiskIoInfo {
+                name: name.clone(),
+                read_bytes: *read,
+                written_bytes: *written,
+                read_rate: rate(*read, prev.and_then(|p| p.0)),
+                write_rate: rate(*written, prev.and_then(|p| p.1)),
+            }
+        })
+        .collect();
+
+    sys.disk_io_prev = counters
+        .into_iter()
+        .map(|(name, read, written)| (name, (read, written)))
+        .collect();
+    sys.disk_io_sampled_at = Some(now);
+    Ok(disks)
+}
+
+#[cfg(target_os = "linux")]
+fn read_disk_counters() -> Result<DiskCounters, AppError> {
+    let content = std::fs::read_to_string("/proc/diskstats")
+        .map_err(|e| AppError::io("/proc/diskstats", e))?;
+    Ok(parse_diskstats(&content, |name| {
+        std::path::Path::new("/sys/block").join(name).exists()
+    }))
+}
+
+// /proc/diskstats 每行：major minor name reads merged sectors_read ms writes merged sectors_written ...
+// 扇区固定按 512 字节计；只保留整盘设备（is_whole_disk，即 /sys/block 下存在的设备），
+// 跳过分区和 loop / ram 设备
+#[cfg(any(target_os = "linux", test))]
+fn parse_diskstats(content: &str, is_whole_disk: impl Fn(&str) -> bool) -> DiskCounters {
+    let mut disks: DiskCounters = content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let name = *fields.get(2)?;
+            if name.starts_with("loop") || name.starts_with("ram") || !is_whole_disk(name) {
+                return None;
+            }
+            let sectors = |i: usize| fields.get(i)?.parse::<u64>().ok().map(|s| s * 512);
+            Some((name.to_string(), sectors(5), sectors(9)))
+        })
+        .collect();
+    disks.sort();
+    disks
+}
+
+#[cfg(target_os = "macos")]
+fn read_disk_counters() -> Result<DiskCounters, AppError> {
+    let out = crate::util::command("ioreg")
+        .args(["-r", "-c", "IOBlockStorageDriver", "-l", "-w0", "-d", "2"])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("ioreg: {}", e)))?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    Ok(parse_ioreg_disk_stats(&text))
+}
+
+// ioreg 中每个 IOBlockStorageDriver 带有 Statistics，其子节点 IOMedia 带有 BSD Name：
+//   "Statistics" = {"Bytes (Read)"=123,"Bytes (Write)"=456,...}
+//   "BSD Name" = "disk0"
+#[cfg(any(target_os = "macos", test))]
+fn parse_ioreg_disk_stats(text: &str) -> DiskCounters {
+    let stat = |line: &str, key: &str| -> Option<u64> {
+        let rest = &line[line.find(key)? + key.len()..];
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    };
+    let mut disks: Vec<(Option<String>, Option<u64>, Option<u64>)> = Vec::new();
+    for line in text.lines() {
+        if line.contains("\"Statistics\" = {") {
+            let read = stat(line, "\"Bytes (Read)\"=");
+            let written = stat(line, "\"Bytes (Write)\"=");
+            disks.push((None, read, written));
+        } else if let Some(value) = line.split("\"BSD Name\" = ").nth(1) {
+            if let Some(last) = disks.last_mut().filter(|d| d.0.is_none()) {
+                last.0 = Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    disks
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, read, written))| {
+            (name.unwrap_or_else(|| format!("disk{}", i)), read, written)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn read_disk_counters() -> Result<DiskCounters, AppError> {
+    let out = crate::util::command("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_PerfRawData_PerfDisk_PhysicalDisk | Select-Object Name, DiskReadBytesPersec, DiskWriteBytesPersec | ConvertTo-Json",
+        ])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("powershell: {}", e)))?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    parse_perfdisk_json(&text)
+}
+
+// 原始性能计数器中的 DiskReadBytesPersec 等实际是累计字节数，Name 形如 "0 C:"，跳过 _Total
+#[cfg(any(target_os = "windows", test))]
+fn parse_perfdisk_json(text: &str) -> Result<DiskCounters, AppError> {
+    if text.trim().is_empty() {
+        return Err(AppError::Unsupported("physical disk counters unavailable".to_string()));
+    }
+    let json: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| AppError::Parse(format!("PhysicalDisk JSON: {}", e)))?;
+    let items = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+    // 计数器在 JSON 中可能是数字或字符串
+    let counter = |item: &serde_json::Value, key: &str| -> Option<u64> {
+        item.get(key)
+            .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let name = item.get("Name")?.as_str()?;
+            (name != "_Total").then(|| {
+                (
+                    name.to_string(),
+                    counter(item, "DiskReadBytesPersec"),
+                    counter(item, "DiskWriteBytesPersec"),
+                )
+            })
+        })
+        .collect())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn read_disk_counters() -> Result<DiskCounters, AppError> {
+    Err(AppError::Unsupported("disk I/O counters are not available on this platform".to_string()))
+}
+
+#[derive(Serialize)]
+pub struct NvmeTemperature {
+    device: String,
+    model: Option<String>,
+    // 综合温度（摄氏度），读不到时为 None
+    composite_temp: Option<f32>,
+    warning_threshold: Option<f32>,
+    critical_threshold: Option<f32>,
+    // 各温度传感器读数（Sensor 1、Sensor 2 ...）
+    sensors: Vec<f32>,
+}
+
+// sysinfo 的 Components 经常拿不到 NVMe 温度：Linux 优先读 sysfs hwmon，
+// 其余情况用 smartctl（通常需要 root，没有权限时返回空列表）。smartctl 逐个查询设备，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_nvme_temperatures() -> Result<Vec<NvmeTemperature>, AppError> {
+    tauri::async_runtime::spawn_blocking(read_nvme_temperatures)
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("nvme temperature task: {}", e)))
+}
+
+fn read_nvme_temperatures() -> Vec<NvmeTemperature> {
+    #[cfg(target_os = "linux")]
+    {
+        let temps = read_nvme_temperatures_sysfs();
+        if !temps.is_empty() {
+            return temps;
+        }
+    }
+    read_nvme_temperatures_smartctl()
+}
+
+// /sys/class/nvme/nvme0/hwmon*/temp1_input 为综合温度，temp2.. 为各传感器，单位为毫摄氏度；
+// 较旧的内核上 hwmon 目录位于 device/hwmon 下
+#[cfg(target_os = "linux")]
+fn read_nvme_temperatures_sysfs() -> Vec<NvmeTemperature> {
+    use std::path::{Path, PathBuf};
+
+    let Ok(entries) = std::fs::read_dir("/sys/class/nvme") else {
+        return Vec::new();
+    };
+    let find_hwmon = |dir: &Path| -> Option<PathBuf> {
+        [dir.to_path_buf(), dir.join("device/hwmon")]
+            .iter()
+            .filter_map(|d| std::fs::read_dir(d).ok())
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path())
+            .find(|p| {
+                p.file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with("hwmon"))
+            })
+    };
+
+    let mut temps: Vec<NvmeTemperature> = entries
+        .flatten()
+        .map(|entry| {
+            let dir = entry.path();
+            let hwmon = find_hwmon(&dir);
+            let read_celsius = |name: &str| -> Option<f32> {
+                let value = std::fs::read_to_string(hwmon.as_ref()?.join(name)).ok()?;
+                let milli = value.trim().parse::<i64>().ok()?;
+                Some(milli as f32 / 1000.0)
+            };
+            let sensors = (2..=9)
+                .map_while(|i| read_celsius(&format!("temp{}_input", i)))
+                .collect();
+            NvmeTemperature {
+                device: entry.file_name().to_string_lossy().into_owned(),
+                model: std::fs::read_to_string(dir.join("model"))
+                    .ok()
+                    .map(|m| m.trim().to_string()),
+                composite_temp: read_celsius("temp1_input"),
+                warning_threshold: read_celsius("temp1_max"),
+                critical_threshold: read_celsius("temp1_crit"),
+                sensors,
+            }
+        })
+        .collect();
+    temps.sort_by(|a, b| a.device.cmp(&b.device));
+    temps
+}
+
+// 单次 smartctl 调用的超时，设备无响应时 smartctl 可能长时间阻塞
+const SMARTCTL_TIMEOUT_SECS: u64 = 5;
+
+// smartctl --scan -j 列出设备，再对 NVMe 设备执行 smartctl -j -a：
+// "temperature": {"current": 38, "op_limit_max": 84, "critical_limit_max": 85}
+// "nvme_smart_health_information_log": {"temperature": 38, "temperature_sensors": [38, 45]}
+fn read_nvme_temperatures_smartctl() -> Vec<NvmeTemperature> {
+    let smartctl = |args: &[&str]| -> Option<serde_json::Value> {
+        let mut cmd = std::process::Command::new("smartctl");
+        cmd.args(args);
+        let out =
+            crate::util::output_with_timeout(&mut cmd, Duration::from_secs(SMARTCTL_TIMEOUT_SECS))
+                .ok()?;
+        serde_json::from_slice(&out.stdout).ok()
+    };
+    let Some(scan) = smartctl(&["--scan", "-j"]) else {
+        return Vec::new();
+    };
+    let devices = scan
+        .get("devices")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    devices
+        .iter()
+        .filter(|d| d.get("type").and_then(|v| v.as_str()) == Some("nvme"))
+        .filter_map(|d| {
+            let name = d.get("name")?.as_str()?;
+            let info = smartctl(&["-j", "-a", name])?;
+            let temperature = info.get("temperature");
+            let log = info.get("nvme_smart_health_information_log");
+            let celsius = |v: Option<&serde_json::Value>| v?.as_f64().map(|t| t as f32);
+            Some(NvmeTemperature {
+                device: name.to_string(),
+                model: info
+                    .get("model_name")
+                    .and_then(|v| v.as_str())
+                    .map(|m| m.to_string()),
+                composite_temp: celsius(temperature.and_then(|t| t.get("current")))
+                    .or_else(|| celsius(log.and_then(|l| l.get("temperature")))),
+                warning_threshold: celsius(temperature.and_then(|t| t.get("op_limit_max"))),
+                critical_threshold: celsius(temperature.and_then(|t| t.get("critical_limit_max"))),
+                sensors: log
+                    .and_then(|l| l.get("temperature_sensors"))
+                    .and_then(|v| v.as_array())
+                    .map(|values| values.iter().filter_map(|v| celsius(Some(v))).collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+// 持久化在应用数据目录中的磁盘剩余空间历史，按挂载点分组
+#[derive(Default)]
+pub struct DiskUsageHistory {
+    disks: Mutex<HashMap<String, DiskHistory>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct DiskHistory {
+    name: String,
+    points: Vec<DiskUsagePoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DiskUsagePoint {
+    // Unix 时间戳（秒）
+    timestamp: u64,
+    free_bytes: u64,
+}
+
+// device 可以是挂载点或卷名；没有历史时返回空列表
+#[tauri::command]
+pub fn get_disk_usage_trend(
+    history: State<'_, DiskUsageHistory>,
+    device: String,
+    days: u32,
+) -> Vec<DiskUsagePoint> {
+    let disks = history.disks.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(disk) = disks
+        .get(&device)
+        .or_else(|| disks.values().find(|d| d.name == device))
+    else {
+        return Vec::new();
+    };
+    let days = days.clamp(1, DISK_TREND_RETENTION_DAYS) as u64;
+    let since = unix_now().saturating_sub(days * 86_400);
+    disk.points
+        .iter()
+        .filter(|p| p.timestamp >= since)
+        .cloned()
+        .collect()
+}
+
+// 在 setup 中调用：读取已保存的历史，之后每小时采样一次
+pub fn start_usage_sampler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let path = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(DISK_TREND_FILE));
+        if let Some(loaded) = path
+            .as_ref()
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|data| serde_json::from_slice(&data).ok())
+        {
+            *app.state::<DiskUsageHistory>()
+                .disks
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = loaded;
+        }
+
+        loop {
+            let history = app.state::<DiskUsageHistory>();
+            if record_usage_if_due(&history) {
+                if let Some(path) = &path {
+                    save_history(&history, path);
+                }
+            }
+            // 应用可能在整点之间启停，定期检查距上次采样是否已满一小时
+            tokio::time::sleep(Duration::from_secs(600)).await;
+        }
+    });
+}
+
+// 距最近一次采样已满一小时才记录，返回是否有新数据
+fn record_usage_if_due(history: &DiskUsageHistory) -> bool {
+    let now = unix_now();
+    let mut disks = history.disks.lock().unwrap_or_else(|e| e.into_inner());
+    let last = disks
+        .values()
+        .filter_map(|d| d.points.last().map(|p| p.timestamp))
+        .max();
+    if last.is_some_and(|last| now.saturating_sub(last) < DISK_TREND_SAMPLE_SECS) {
+        return false;
+    }
+
+    for disk in Disks::new_with_refreshed_list().list() {
+        let mount_point = disk.mount_point().to_string_lossy().into_owned();
+        let entry = disks.entry(mount_point).or_default();
+        entry.name = disk.name().to_string_lossy().into_owned();
+        entry.points.push(DiskUsagePoint {
+            timestamp: now,
+            free_bytes: disk.available_space(),
+        });
+    }
+    // 丢弃超出保留期的数据，以及已经不再挂载的磁盘
+    let cutoff = now.saturating_sub(DISK_TREND_RETENTION_DAYS as u64 * 86_400);
+    for disk in disks.values_mut() {
+        disk.points.retain(|p| p.timestamp >= cutoff);
+    }
+    disks.retain(|_, d| !d.points.is_empty());
+    true
+}
+
+// 先写临时文件再改名，避免写入中途退出导致文件损坏
+fn save_history(history: &DiskUsageHistory, path: &std::path::Path) {
+    let data = {
+        let disks = history.disks.lock().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_vec(&*disks)
+    };
+    let Ok(data) = data else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let tmp = path.with_extension("json.tmp");
+    if std::fs::write(&tmp, data).is_ok() {
+        let _ = std::fs::rename(&tmp, path);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diskstats_whole_disks_only() {
+        let content = "\
+   7       0 loop0 58 0 2168 16 0 0 0 0 0 24 16 0 0 0 0 0 0
+ 259       0 nvme0n1 182039 52071 12795246 31514 421530 287164 30211792 295731 0 261116 340524
+ 259       1 nvme0n1p1 312 1002 13574 57 2 0 2 0 0 84 58 0 0 0 0 0 0
+   8       0 sda 2210 1043 89123 1420 512 300 40000 900 0 1500 2320
+ 253       0 dm-0 1000 0 8000 100 200 0 1600 50 0 150 150
+   8      16 sdb 12 0 96
+";
+        let disks = parse_diskstats(content, |name| {
+            ["loop0", "nvme0n1", "sda", "sdb"].contains(&name)
+        });
+        assert_eq!(
+            disks,
+            [
+                (
+                    "nvme0n1".to_string(),
+                    Some(12795246 * 512),
+                    Some(30211792 * 512)
+                ),
+                ("sda".to_string(), Some(89123 * 512), Some(40000 * 512)),
+                // 截断的行只缺少写入计数
+                ("sdb".to_string(), Some(96 * 512), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn ioreg_statistics() {
+        let text = r#"
++-o IOBlockStorageDriver  <class IOBlockStorageDriver, id 0x100000437, registered, matched, active, busy 0 (0 ms), retain 8>
+  | {
+  |   "IOClass" = "IOBlockStorageDriver"
+  |   "Statistics" = {"Operations (Write)"=2613291,"Bytes (Read)"=84614660096,"Errors (Write)"=0,"Bytes (Write)"=68424122368,"Operations (Read)"=3081548}
+  | }
+  | 
+  +-o APPLE SSD AP0512Q Media  <class IOMedia, id 0x100000438, registered, matched, active, busy 0 (0 ms), retain 12>
+      {
+        "Content" = "GUID_partition_scheme"
+        "BSD Name" = "disk0"
+      }
++-o IOBlockStorageDriver  <class IOBlockStorageDriver, id 0x100000891, registered, matched, active, busy 0 (0 ms), retain 7>
+  | {
+  |   "Statistics" = {"Operations (Read)"=412,"Bytes (Read)"=1720320}
+  | }
+"#;
+        let disks = parse_ioreg_disk_stats(text);
+        assert_eq!(
+            disks,
+            [
+                ("disk0".to_string(), Some(84614660096), Some(68424122368)),
+                // 没有 BSD Name 时按序号命名，缺少的计数为 None 而不是 0
+                ("disk1".to_string(), Some(1720320), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn perfdisk_counters() {
+        let text = r#"[
+    {"Name": "0 C:", "DiskReadBytesPersec": 123456789, "DiskWriteBytesPersec": "987654321"},
+    {"Name": "1 D:", "DiskReadBytesPersec": 4096, "DiskWriteBytesPersec": null},
+    {"Name": "_Total", "DiskReadBytesPersec": 123460885, "DiskWriteBytesPersec": 987654321}
+]"#;
+        let disks = parse_perfdisk_json(text).unwrap();
+        assert_eq!(
+            disks,
+            [
+                ("0 C:".to_string(), Some(123456789), Some(987654321)),
+                ("1 D:".to_string(), Some(4096), None),
+            ]
+        );
+
+        // 只有一块磁盘时 ConvertTo-Json 输出单个对象
+        let single = r#"{"Name": "0 C:", "DiskReadBytesPersec": 1, "DiskWriteBytesPersec": 2}"#;
+        assert_eq!(parse_perfdisk_json(single).unwrap().len(), 1);
+        assert!(matches!(
+            parse_perfdisk_json("\r\n"),
+            Err(AppError::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse_perfdisk_json("Get-CimInstance : Invalid class"),
+            Err(AppError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,734 @@
+use crate::error::AppError;
+use serde::Serialize;
+
+// 已连接的显示器。各项在当前平台无法获取时为 None
+#[derive(Serialize, Default)]
+pub struct DisplayInfo {
+    // 同一显示器在多次调用之间保持不变，供前端比对列表：
+    // Windows 为设备名（\\.\DISPLAY1），macOS 为 displayID，Linux 为输出名（HDMI-1）
+    pub id: String,
+    // 显示器型号（来自 EDID），读取不到时为输出名或系统的通用名称
+    name: String,
+    native_resolution: Option<Resolution>,
+    // macOS 上为界面的逻辑分辨率（“看起来像”），其他平台为实际像素
+    current_resolution: Option<Resolution>,
+    refresh_rate_hz: Option<f32>,
+    // Windows 为 DPI / 96；macOS 为原生像素与逻辑分辨率之比；Linux 仅 Wayland 提供（整数倍）
+    scale_factor: Option<f32>,
+    // 系统报告的每像素位数，通常为 32（每通道 8 位），10 位色为 30
+    bit_depth: Option<u32>,
+    pub is_primary: bool,
+    // 驱动该显示器的显卡
+    gpu: Option<String>,
+    // 在整个桌面中的左上角坐标（像素），目前只有 X11 提供
+    position: Option<Position>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+pub struct Resolution {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl DisplayInfo {
+    // 截取该显示器所用的 X11 区域 "宽x高+x+y"，关闭的输出没有几何信息
+    #[cfg(target_os = "linux")]
+    pub fn x11_geometry(&self) -> Option<String> {
+        let (size, position) = (self.current_resolution?, self.position?);
+        Some(format!(
+            "{}x{}+{}+{}",
+            size.width, size.height, position.x, position.y
+        ))
+    }
+}
+
+// 每次调用都重新枚举，调用之间插拔显示器只会改变返回的列表
+#[tauri::command]
+pub fn get_displays() -> Result<Vec<DisplayInfo>, AppError> {
+    #[cfg(target_os = "macos")]
+    {
+        get_displays_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_displays_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(get_displays_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(AppError::Unsupported(
+            "display enumeration is not implemented on this platform".to_string(),
+        ))
+    }
+}
+
+// get_displays 的别名
+#[tauri::command]
+pub fn list_displays() -> Result<Vec<DisplayInfo>, AppError> {
+    get_displays()
+}
+
+// "3024 x 1964" / "1920x1080" -> 3024 x 1964，取前两个数字
+#[cfg(any(target_os = "macos", target_os = "linux", test))]
+fn parse_resolution(s: &str) -> Option<Resolution> {
+    let mut numbers = s
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|n| !n.is_empty())
+        .filter_map(|n| n.parse::<u32>().ok());
+    let width = numbers.next()?;
+    let height = numbers.next()?;
+    (width > 0 && height > 0).then_some(Resolution { width, height })
+}
+
+// system_profiler 把显示器放在驱动它的显卡的 spdisplays_ndrvs 下：
+// "_spdisplays_pixels": "3024 x 1964"（原生像素），
+// "_spdisplays_resolution": "1512 x 982 @ 120.00Hz"（逻辑分辨率与刷新率）
+#[cfg(target_os = "macos")]
+fn get_displays_macos() -> Result<Vec<DisplayInfo>, AppError> {
+    let out = crate::util::run_checked("system_profiler", &["SPDisplaysDataType", "-json"])?;
+    let json: serde_json::Value = serde_json::from_str(&out)
+        .map_err(|e| AppError::Parse(format!("SPDisplaysDataType JSON: {}", e)))?;
+    let str_field = |v: &serde_json::Value, key: &str| v.get(key).and_then(|v| v.as_str());
+
+    let mut displays = Vec::new();
+    let gpus = json
+        .get("SPDisplaysDataType")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for gpu in &gpus {
+        let gpu_name = str_field(gpu, "sppci_model").or_else(|| str_field(gpu, "_name"));
+        let monitors = gpu
+            .get("spdisplays_ndrvs")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for monitor in &monitors {
+            let name = str_field(monitor, "_name").unwrap_or("Display").to_string();
+            let mode = str_field(monitor, "_spdisplays_resolution")
+                .or_else(|| str_field(monitor, "spdisplays_resolution"));
+            let native_resolution =
+                str_field(monitor, "_spdisplays_pixels").and_then(parse_resolution);
+            let current_resolution = mode.and_then(parse_resolution);
+            let refresh_rate_hz = mode
+                .and_then(|m| m.split_once('@'))
+                .and_then(|(_, rate)| rate.trim().trim_end_matches("Hz").trim().parse().ok());
+            let scale_factor = match (native_resolution, current_resolution) {
+                (Some(native), Some(current)) => {
+                    Some((native.width as f32 / current.width as f32 * 100.0).round() / 100.0)
+                }
+                _ => None,
+            };
+            // "CGSThirtytwoBitColor" / "CGSThirtyBitColor"
+            let bit_depth = str_field(monitor, "spdisplays_depth").and_then(|depth| {
+                if depth.contains("ThirtytwoBit") {
+                    Some(32)
+                } else if depth.contains("ThirtyBit") {
+                    Some(30)
+                } else if depth.contains("SixteenBit") {
+                    Some(16)
+                } else {
+                    None
+                }
+            });
+            displays.push(DisplayInfo {
+                id: str_field(monitor, "_spdisplays_displayID")
+                    .map(str::to_string)
+                    .unwrap_or_else(|| name.clone()),
+                name,
+                native_resolution,
+                current_resolution,
+                refresh_rate_hz,
+                scale_factor,
+                bit_depth,
+                is_primary: str_field(monitor, "spdisplays_main") == Some("spdisplays_yes"),
+                gpu: gpu_name.map(str::to_string),
+                position: None,
+            });
+        }
+    }
+    Ok(displays)
+}
+
+// EnumDisplayDevices 枚举连接到桌面的输出及其显示器，EnumDisplaySettings 读取当前模式，
+// 支持的最大模式视为原生分辨率；GetDpiForMonitor 需要先声明按显示器感知 DPI，否则总是返回 96。
+// 显示器型号来自 WmiMonitorID，按设备 ID 中的硬件 ID（如 GSM5B7F）对应
+#[cfg(target_os = "windows")]
+const WINDOWS_DISPLAYS_QUERY: &str = r#"
+Add-Type @"
+using System;
+using System.Collections.Generic;
+using System.Runtime.InteropServices;
+public static class Displays {
+    [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+    public struct DISPLAY_DEVICE {
+        public int cb;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 32)] public string DeviceName;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)] public string DeviceString;
+        public int StateFlags;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)] public string DeviceID;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 128)] public string DeviceKey;
+    }
+    [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+    public struct DEVMODE {
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 32)] public string dmDeviceName;
+        public short dmSpecVersion; public short dmDriverVersion; public short dmSize; public short dmDriverExtra;
+        public int dmFields; public int dmPositionX; public int dmPositionY; public int dmDisplayOrientation; public int dmDisplayFixedOutput;
+        public short dmColor; public short dmDuplex; public short dmYResolution; public short dmTTOption; public short dmCollate;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 32)] public string dmFormName;
+        public short dmLogPixels; public int dmBitsPerPel; public int dmPelsWidth; public int dmPelsHeight;
+        public int dmDisplayFlags; public int dmDisplayFrequency;
+        public int dmICMMethod; public int dmICMIntent; public int dmMediaType; public int dmDitherType;
+        public int dmReserved1; public int dmReserved2; public int dmPanningWidth; public int dmPanningHeight;
+    }
+    [StructLayout(LayoutKind.Sequential)] public struct RECT { public int Left, Top, Right, Bottom; }
+    [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+    public struct MONITORINFOEX {
+        public int cbSize; public RECT rcMonitor; public RECT rcWork; public int dwFlags;
+        [MarshalAs(UnmanagedType.ByValTStr, SizeConst = 32)] public string szDevice;
+    }
+    public delegate bool MonitorEnumProc(IntPtr hMonitor, IntPtr hdc, IntPtr rect, IntPtr data);
+    [DllImport("user32.dll", CharSet = CharSet.Unicode)] public static extern bool EnumDisplayDevices(string device, uint index, ref DISPLAY_DEVICE dd, uint flags);
+    [DllImport("user32.dll", CharSet = CharSet.Unicode)] public static extern bool EnumDisplaySettings(string device, int mode, ref DEVMODE dm);
+    [DllImport("user32.dll")] public static extern bool EnumDisplayMonitors(IntPtr hdc, IntPtr clip, MonitorEnumProc proc, IntPtr data);
+    [DllImport("user32.dll", CharSet = CharSet.Unicode)] public static extern bool GetMonitorInfo(IntPtr hMonitor, ref MONITORINFOEX info);
+    [DllImport("shcore.dll")] public static extern int SetProcessDpiAwareness(int value);
+    [DllImport("shcore.dll")] public static extern int GetDpiForMonitor(IntPtr hMonitor, int dpiType, out uint dpiX, out uint dpiY);
+    public static Dictionary<string, uint> MonitorDpi() {
+        SetProcessDpiAwareness(2);
+        var result = new Dictionary<string, uint>();
+        EnumDisplayMonitors(IntPtr.Zero, IntPtr.Zero, (h, hdc, rect, data) => {
+            var info = new MONITORINFOEX();
+            info.cbSize = Marshal.SizeOf(info);
+            uint x, y;
+            if (GetMonitorInfo(h, ref info) && GetDpiForMonitor(h, 0, out x, out y) == 0) { result[info.szDevice] = x; }
+            return true;
+        }, IntPtr.Zero);
+        return result;
+    }
+}
+"@
+$dpi = [Displays]::MonitorDpi()
+$friendly = @{}
+Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorID -ErrorAction SilentlyContinue | ForEach-Object {
+    $name = -join ($_.UserFriendlyName | Where-Object { $_ -ne 0 } | ForEach-Object { [char]$_ })
+    $friendly[($_.InstanceName -split '\\')[1]] = $name
+}
+$result = @()
+for ($i = 0; ; $i++) {
+    $adapter = New-Object Displays+DISPLAY_DEVICE
+    $adapter.cb = [Runtime.InteropServices.Marshal]::SizeOf($adapter)
+    if (-not [Displays]::EnumDisplayDevices($null, $i, [ref]$adapter, 0)) { break }
+    # DISPLAY_DEVICE_ATTACHED_TO_DESKTOP
+    if (($adapter.StateFlags -band 1) -eq 0) { continue }
+    $monitor = New-Object Displays+DISPLAY_DEVICE
+    $monitor.cb = $adapter.cb
+    [void][Displays]::EnumDisplayDevices($adapter.DeviceName, 0, [ref]$monitor, 0)
+    $current = New-Object Displays+DEVMODE
+    $current.dmSize = [Runtime.InteropServices.Marshal]::SizeOf($current)
+    # ENUM_CURRENT_SETTINGS
+    if (-not [Displays]::EnumDisplaySettings($adapter.DeviceName, -1, [ref]$current)) { continue }
+    $mode = New-Object Displays+DEVMODE
+    $mode.dmSize = $current.dmSize
+    $nativeWidth = 0; $nativeHeight = 0
+    for ($m = 0; [Displays]::EnumDisplaySettings($adapter.DeviceName, $m, [ref]$mode); $m++) {
+        if ($mode.dmPelsWidth * $mode.dmPelsHeight -gt $nativeWidth * $nativeHeight) {
+            $nativeWidth = $mode.dmPelsWidth; $nativeHeight = $mode.dmPelsHeight
+        }
+    }
+    $hardwareId = ($monitor.DeviceID -split '\\')[1]
+    $result += [PSCustomObject]@{
+        Id = $adapter.DeviceName
+        Name = if ($hardwareId -and $friendly[$hardwareId]) { $friendly[$hardwareId] } else { $monitor.DeviceString }
+        Width = $current.dmPelsWidth
+        Height = $current.dmPelsHeight
+        NativeWidth = $nativeWidth
+        NativeHeight = $nativeHeight
+        RefreshRate = $current.dmDisplayFrequency
+        BitsPerPel = $current.dmBitsPerPel
+        Dpi = $dpi[$adapter.DeviceName]
+        # DISPLAY_DEVICE_PRIMARY_DEVICE
+        Primary = ($adapter.StateFlags -band 4) -ne 0
+        Gpu = $adapter.DeviceString
+    }
+}
+ConvertTo-Json -InputObject @($result)
+"#;
+
+#[cfg(target_os = "windows")]
+fn get_displays_windows() -> Result<Vec<DisplayInfo>, AppError> {
+    let out = crate::util::run_checked(
+        "powershell",
+        &["-NoProfile", "-Command", WINDOWS_DISPLAYS_QUERY],
+    )?;
+    let json: serde_json::Value =
+        serde_json::from_str(&out).map_err(|e| AppError::Parse(format!("display JSON: {}", e)))?;
+    let items = json.as_array().cloned().unwrap_or_default();
+    let number = |item: &serde_json::Value, key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|&v| v > 0)
+    };
+    let text = |item: &serde_json::Value, key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let resolution = |item: &serde_json::Value, width: &str, height: &str| {
+        Some(Resolution {
+            width: number(item, width)?,
+            height: number(item, height)?,
+        })
+    };
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let id = text(item, "Id")?;
+            Some(DisplayInfo {
+                name: text(item, "Name").unwrap_or_else(|| id.clone()),
+                id,
+                native_resolution: resolution(item, "NativeWidth", "NativeHeight"),
+                current_resolution: resolution(item, "Width", "Height"),
+                // 0 / 1 表示使用硬件默认刷新率
+                refresh_rate_hz: number(item, "RefreshRate")
+                    .filter(|&hz| hz > 1)
+                    .map(|hz| hz as f32),
+                scale_factor: number(item, "Dpi").map(|dpi| dpi as f32 / 96.0),
+                bit_depth: number(item, "BitsPerPel"),
+                is_primary: item
+                    .get("Primary")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                gpu: text(item, "Gpu"),
+                position: None,
+            })
+        })
+        .collect())
+}
+
+// X11（含 XWayland）上解析 xrandr，纯 Wayland 会话解析 wayland-info；
+// 两者都不可用时只列出 DRM 中已连接的接口。型号、原生分辨率与显卡来自 DRM（EDID）
+#[cfg(target_os = "linux")]
+fn get_displays_linux() -> Vec<DisplayInfo> {
+    let connectors = drm_connectors();
+    let mut displays = if std::env::var_os("DISPLAY").is_some() {
+        xrandr_displays()
+    } else {
+        Vec::new()
+    };
+    if displays.is_empty() && std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        displays = wayland_outputs();
+    }
+    if displays.is_empty() {
+        return connectors
+            .into_iter()
+            .map(|c| DisplayInfo {
+                name: c.monitor_name.unwrap_or_else(|| c.name.clone()),
+                id: c.name,
+                native_resolution: c.native_resolution,
+                gpu: c.gpu,
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    for display in &mut displays {
+        let Some(connector) = connectors
+            .iter()
+            .find(|c| c.name == normalize_output_name(&display.id))
+        else {
+            continue;
+        };
+        if let Some(name) = &connector.monitor_name {
+            display.name = name.clone();
+        }
+        display.native_resolution = display.native_resolution.or(connector.native_resolution);
+        display.gpu = connector.gpu.clone();
+    }
+    displays
+}
+
+// xrandr 报告的已连接输出，顺序与 get_displays 在 X11 上的结果一致
+#[cfg(target_os = "linux")]
+pub fn xrandr_displays() -> Vec<DisplayInfo> {
+    crate::util::run_checked("xrandr", &["--query"])
+        .map(|out| parse_xrandr(&out))
+        .unwrap_or_default()
+}
+
+// 合成器报告的输出名，XWayland 下 xrandr 只能看到 XWAYLAND0 之类的虚拟名称
+#[cfg(target_os = "linux")]
+pub fn wayland_outputs() -> Vec<DisplayInfo> {
+    crate::util::run_checked("wayland-info", &[])
+        .map(|out| parse_wayland_info(&out))
+        .unwrap_or_default()
+}
+
+// 输出名在 DRM 与 X11 之间不完全一致（card0-HDMI-A-1 与 HDMI-1），统一后再对应；
+// 对不上时只是缺少型号与显卡信息
+#[cfg(target_os = "linux")]
+fn normalize_output_name(name: &str) -> String {
+    name.replacen("HDMI-A-", "HDMI-", 1)
+}
+
+#[cfg(target_os = "linux")]
+struct DrmConnector {
+    // 去掉 cardN- 前缀并统一后的接口名
+    name: String,
+    monitor_name: Option<String>,
+    native_resolution: Option<Resolution>,
+    gpu: Option<String>,
+}
+
+// /sys/class/drm/card0-HDMI-A-1：status 为 connected 的接口，modes 第一行为首选（原生）模式
+#[cfg(target_os = "linux")]
+fn drm_connectors() -> Vec<DrmConnector> {
+    use std::fs;
+
+    let lspci = crate::lspci_gpus();
+    let mut entries: Vec<_> = fs::read_dir("/sys/class/drm")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let (card, connector) = file_name.split_once('-')?;
+            let path = entry.path();
+            if fs::read_to_string(path.join("status")).ok()?.trim() != "connected" {
+                return None;
+            }
+            let native_resolution = fs::read_to_string(path.join("modes"))
+                .ok()
+                .and_then(|modes| modes.lines().next().and_then(parse_resolution));
+            let monitor_name = fs::read(path.join("edid"))
+                .ok()
+                .and_then(|edid| edid_monitor_name(&edid));
+            let slot = fs::canonicalize(format!("/sys/class/drm/{}/device", card))
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+            let gpu = slot.and_then(|slot| {
+                lspci
+                    .iter()
+                    .find(|g| g.slot == slot)
+                    .map(|g| g.name.clone())
+            });
+            Some(DrmConnector {
+                name: normalize_output_name(connector),
+                monitor_name,
+                native_resolution,
+                gpu,
+            })
+        })
+        .collect()
+}
+
+// EDID 基本块中 4 个 18 字节的描述符（偏移 54 起），标签 0xFC 为显示器名称，以 0x0A 结尾
+#[cfg(any(target_os = "linux", test))]
+fn edid_monitor_name(edid: &[u8]) -> Option<String> {
+    (0..4).find_map(|i| {
+        let descriptor = edid.get(54 + i * 18..72 + i * 18)?;
+        if descriptor[..3] != [0, 0, 0] || descriptor[3] != 0xFC {
+            return None;
+        }
+        let text = &descriptor[5..];
+        let end = text.iter().position(|&b| b == 0x0A).unwrap_or(text.len());
+        let name = String::from_utf8_lossy(&text[..end]).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    })
+}
+
+// xrandr --query 输出形如：
+// eDP-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 194mm
+//    1920x1080     60.01*+  59.97    59.96
+// 模式行中 * 为当前刷新率，+ 为首选（原生）模式；已连接但关闭的输出没有几何信息
+#[cfg(any(target_os = "linux", test))]
+fn parse_xrandr(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    let mut in_connected = false;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let mut parts = line.split_whitespace();
+            let (Some(name), Some(status)) = (parts.next(), parts.next()) else {
+                in_connected = false;
+                continue;
+            };
+            in_connected = status == "connected";
+            if in_connected {
+                let rest: Vec<&str> = parts.collect();
+                // 几何信息形如 "1920x1080+1920+0"
+                let geometry: Vec<&str> = rest
+                    .iter()
+                    .find(|p| p.contains('x') && p.contains('+'))
+                    .map(|geometry| geometry.split('+').collect())
+                    .unwrap_or_default();
+                let coordinate = |i: usize| geometry.get(i).and_then(|v| v.parse::<i32>().ok());
+                displays.push(DisplayInfo {
+                    id: name.to_string(),
+                    name: name.to_string(),
+                    is_primary: rest.first() == Some(&"primary"),
+                    current_resolution: geometry.first().and_then(|size| parse_resolution(size)),
+                    position: match (coordinate(1), coordinate(2)) {
+                        (Some(x), Some(y)) => Some(Position { x, y }),
+                        _ => None,
+                    },
+                    ..Default::default()
+                });
+            }
+            continue;
+        }
+        let Some(display) = displays.last_mut().filter(|_| in_connected) else {
+            continue;
+        };
+        let mut parts = line.split_whitespace();
+        let Some(resolution) = parts.next().and_then(parse_resolution) else {
+            continue;
+        };
+        for rate in parts {
+            if rate.contains('+') && display.native_resolution.is_none() {
+                display.native_resolution = Some(resolution);
+            }
+            if rate.contains('*') {
+                display.current_resolution = display.current_resolution.or(Some(resolution));
+                display.refresh_rate_hz = rate.trim_end_matches(['*', '+']).parse().ok();
+            }
+        }
+    }
+    displays
+}
+
+// wayland-info 中每个 wl_output 形如：
+//     name: DP-1
+//     x: 0, y: 0, scale: 2,
+//     make: 'Dell Inc.', model: 'DELL U2720Q',
+//     mode:
+//         width: 3840 px, height: 2160 px, refresh: 59.997 Hz,
+//         flags: current preferred
+// 各版本的排版略有不同，按 "键: 值" 逐项解析
+#[cfg(any(target_os = "linux", test))]
+fn parse_wayland_info(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    let mut in_output = false;
+    let (mut make, mut model) = (None::<String>, None::<String>);
+    let mut mode: (Option<u32>, Option<u32>, Option<f32>) = (None, None, None);
+    let number = |value: &str| {
+        value
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    for line in output.lines() {
+        if line.contains("interface:") {
+            in_output = line.contains("'wl_output'");
+            if in_output {
+                displays.push(DisplayInfo::default());
+                (make, model) = (None, None);
+            }
+            continue;
+        }
+        let Some(display) = displays.last_mut().filter(|_| in_output) else {
+            continue;
+        };
+        for field in line.split(',') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('\'');
+            match key.trim() {
+                "name" if display.id.is_empty() => {
+                    display.id = value.to_string();
+                    display.name = value.to_string();
+                }
+                "scale" => display.scale_factor = number(value).parse().ok(),
+                "make" => make = Some(value.to_string()).filter(|v| !v.is_empty()),
+                "model" => model = Some(value.to_string()).filter(|v| !v.is_empty()),
+                "width" if value.ends_with("px") => mode.0 = number(value).parse().ok(),
+                "height" if value.ends_with("px") => mode.1 = number(value).parse().ok(),
+                "refresh" => mode.2 = number(value).parse().ok(),
+                "flags" => {
+                    let resolution = match mode {
+                        (Some(width), Some(height), _) => Some(Resolution { width, height }),
+                        _ => None,
+                    };
+                    if value.contains("preferred") {
+                        display.native_resolution = resolution;
+                    }
+                    if value.contains("current") {
+                        display.current_resolution = resolution;
+                        display.refresh_rate_hz = mode.2;
+                    }
+                    mode = (None, None, None);
+                }
+                _ => {}
+            }
+        }
+        if let Some(model) = &model {
+            display.name = match &make {
+                Some(make) if !model.starts_with(make.as_str()) => format!("{} {}", make, model),
+                _ => model.clone(),
+            };
+        }
+    }
+    // Wayland 没有主显示器的概念，以第一个输出代替
+    displays.retain(|d| !d.id.is_empty());
+    if let Some(first) = displays.first_mut() {
+        first.is_primary = true;
+    }
+    displays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(resolution: Option<Resolution>) -> Option<(u32, u32)> {
+        resolution.map(|r| (r.width, r.height))
+    }
+
+    #[test]
+    fn resolution_formats() {
+        assert_eq!(size(parse_resolution("3024 x 1964")), Some((3024, 1964)));
+        assert_eq!(size(parse_resolution("1920x1080")), Some((1920, 1080)));
+        assert_eq!(size(parse_resolution("2560x1440i")), Some((2560, 1440)));
+        assert_eq!(
+            size(parse_resolution("1512 x 982 @ 120.00Hz")),
+            Some((1512, 982))
+        );
+        assert_eq!(size(parse_resolution("0x0")), None);
+        assert_eq!(size(parse_resolution("unknown")), None);
+    }
+
+    #[test]
+    fn xrandr_outputs() {
+        let output = "\
+Screen 0: minimum 320 x 200, current 4480 x 1440, maximum 16384 x 16384
+eDP-1 connected 1920x1080+2560+360 (normal left inverted right x axis y axis) 344mm x 194mm
+   1920x1080     60.01 +  59.97*   59.96    48.00
+   1680x1050     59.95    59.88
+HDMI-1 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm
+   2560x1440     59.95*+  74.97
+   1920x1080     60.00    50.00
+DP-1 disconnected (normal left inverted right x axis y axis)
+DP-2 connected (normal left inverted right x axis y axis)
+   3840x2160     60.00 +  30.00
+   2560x1440     59.95
+";
+        let displays = parse_xrandr(output);
+        assert_eq!(displays.len(), 3);
+
+        let laptop = &displays[0];
+        assert_eq!(laptop.id, "eDP-1");
+        assert!(!laptop.is_primary);
+        assert_eq!(size(laptop.current_resolution), Some((1920, 1080)));
+        assert_eq!(size(laptop.native_resolution), Some((1920, 1080)));
+        assert_eq!(laptop.refresh_rate_hz, Some(59.97));
+        assert_eq!(laptop.position.map(|p| (p.x, p.y)), Some((2560, 360)));
+
+        let external = &displays[1];
+        assert_eq!(external.id, "HDMI-1");
+        assert!(external.is_primary);
+        assert_eq!(size(external.native_resolution), Some((2560, 1440)));
+        assert_eq!(external.refresh_rate_hz, Some(59.95));
+        assert_eq!(external.position.map(|p| (p.x, p.y)), Some((0, 0)));
+
+        // 已连接但关闭的输出：只有首选模式，没有当前分辨率与位置
+        let off = &displays[2];
+        assert_eq!(off.id, "DP-2");
+        assert_eq!(size(off.native_resolution), Some((3840, 2160)));
+        assert_eq!(size(off.current_resolution), None);
+        assert_eq!(off.refresh_rate_hz, None);
+        assert!(off.position.is_none());
+    }
+
+    #[test]
+    fn wayland_info_outputs() {
+        let output = "\
+interface: 'wl_compositor',                              version:  6, name:  1
+interface: 'wl_output',                                  version:  4, name: 59
+        name: DP-1
+        x: 0, y: 0, scale: 2,
+        physical_width: 600 mm, physical_height: 340 mm,
+        make: 'Dell Inc.', model: 'DELL U2720Q',
+        subpixel_orientation: unknown, output_transform: normal,
+        mode:
+                width: 3840 px, height: 2160 px, refresh: 59.997 Hz,
+                flags: current preferred
+        mode:
+                width: 1920 px, height: 1080 px, refresh: 60.000 Hz,
+                flags: none
+interface: 'wl_output',                                  version:  4, name: 60
+        name: eDP-1
+        x: 1920, y: 0, scale: 1,
+        make: 'BOE', model: 'BOE 0x0BCA',
+        mode:
+                width: 2256 px, height: 1504 px, refresh: 59.999 Hz,
+                flags: preferred
+        mode:
+                width: 1920 px, height: 1280 px, refresh: 60.000 Hz,
+                flags: current
+interface: 'wl_seat',                                    version:  8, name: 61
+        name: seat0
+";
+        let displays = parse_wayland_info(output);
+        assert_eq!(displays.len(), 2);
+
+        let dell = &displays[0];
+        assert_eq!(dell.id, "DP-1");
+        assert_eq!(dell.name, "Dell Inc. DELL U2720Q");
+        assert!(dell.is_primary);
+        assert_eq!(dell.scale_factor, Some(2.0));
+        assert_eq!(size(dell.native_resolution), Some((3840, 2160)));
+        assert_eq!(size(dell.current_resolution), Some((3840, 2160)));
+        assert_eq!(dell.refresh_rate_hz, Some(59.997));
+
+        // 型号以厂商名开头时不重复厂商
+        let laptop = &displays[1];
+        assert_eq!(laptop.id, "eDP-1");
+        assert_eq!(laptop.name, "BOE 0x0BCA");
+        assert!(!laptop.is_primary);
+        assert_eq!(size(laptop.native_resolution), Some((2256, 1504)));
+        assert_eq!(size(laptop.current_resolution), Some((1920, 1280)));
+        assert_eq!(laptop.refresh_rate_hz, Some(60.0));
+    }
+
+    #[test]
+    fn edid_name_descriptor() {
+        // 基本块：描述符依次为详细时序、序列号（0xFF）、名称（0xFC）、刷新率范围（0xFD）
+        let edid: [u8; 128] = [
+            0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x10, 0xac, 0xe6, 0xa0, 0x4c, 0x4b,
+            0x32, 0x30, 0x0c, 0x1e, 0x01, 0x04, 0xb5, 0x3c, 0x22, 0x78, 0x3b, 0x4f, 0x15, 0xae,
+            0x50, 0x47, 0xa3, 0x26, 0x0e, 0x50, 0x54, 0xa5, 0x4b, 0x00, 0x71, 0x4f, 0x81, 0x80,
+            0xa9, 0xc0, 0xd1, 0xc0, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x4d, 0xd0,
+            0x00, 0xa0, 0xf0, 0x70, 0x3e, 0x80, 0x30, 0x20, 0x35, 0x00, 0x55, 0x50, 0x21, 0x00,
+            0x00, 0x1a, 0x00, 0x00, 0x00, 0xff, 0x00, 0x34, 0x57, 0x4e, 0x52, 0x53, 0x32, 0x33,
+            0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x44, 0x45, 0x4c,
+            0x4c, 0x20, 0x55, 0x32, 0x37, 0x32, 0x30, 0x51, 0x0a, 0x20, 0x00, 0x00, 0x00, 0xfd,
+            0x00, 0x31, 0x4b, 0x1d, 0x71, 0x3c, 0x01, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+            0x01, 0x4c,
+        ];
+        assert_eq!(edid_monitor_name(&edid).as_deref(), Some("DELL U2720Q"));
+        // 截断的 EDID 与没有名称描述符的 EDID
+        assert_eq!(edid_monitor_name(&edid[..100]), None);
+        let mut unnamed = edid;
+        unnamed[93] = 0xFE;
+        assert_eq!(edid_monitor_name(&unnamed), None);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::Serialize;
+use std::{fmt, io};
+
+// 命令统一的错误类型，序列化为 { "kind": "CommandFailed", "message": "..." }
+// 前端据此区分“没有数据”和“探测失败”
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message")]
+pub enum AppError {
+    // 外部命令无法启动或返回失败
+    CommandFailed(String),
+    // 命令输出或接口返回无法解析
+    Parse(String),
+    // 音频主机 / 设备枚举失败
+    Audio(String),
+    // 网络请求失败
+    Network(String),
+    // 当前平台不支持该功能
+    Unsupported(String),
+    // 指定 pid 的进程不存在（可能已退出）
+    ProcessNotFound(u32),
+    // 权限不足或操作被拒绝
+    PermissionDenied(String),
+    // 文件或系统 I/O 失败
+    #[cfg_attr(
+        not(any(target_os = "linux", target_os = "macos", target_os = "windows")),
+        allow(dead_code)
+    )]
+    Io(String),
+    // 外部命令或网络请求超时
+    Timeout(String),
+    // 前端传入的参数不合法
+    InvalidArgument(String),
+    // 同类操作正在进行，需等待其结束
+    Busy(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::CommandFailed(msg) => write!(f, "command failed: {}", msg),
+            AppError::Parse(msg) => write!(f, "parse error: {}", msg),
+            AppError::Audio(msg) => write!(f, "audio error: {}", msg),
+            AppError::Network(msg) => write!(f, "network error: {}", msg),
+            AppError::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+            AppError::ProcessNotFound(pid) => write!(f, "process {} not found", pid),
+            AppError::PermissionDenied(msg) => write!(f, "permission denied: {}", msg),
+            AppError::Io(msg) => write!(f, "io error: {}", msg),
+            AppError::Timeout(msg) => write!(f, "timed out: {}", msg),
+            AppError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+            AppError::Busy(msg) => write!(f, "busy: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl AppError {
+    // 按 io::Error 的类型映射到对应的变体，context 说明失败的操作
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub fn io(context: &str, err: io::Error) -> AppError {
+        let msg = format!("{}: {}", context, err);
+        match err.kind() {
+            io::ErrorKind::TimedOut => AppError::Timeout(msg),
+            io::ErrorKind::PermissionDenied => AppError::PermissionDenied(msg),
+            _ => AppError::Io(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_and_message() {
+        let err = AppError::Parse("SPDisplaysDataType JSON: EOF".to_string());
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({ "kind": "Parse", "message": "SPDisplaysDataType JSON: EOF" })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::ProcessNotFound(42)).unwrap(),
+            serde_json::json!({ "kind": "ProcessNotFound", "message": 42 })
+        );
+        assert_eq!(
+            AppError::ProcessNotFound(42).to_string(),
+            "process 42 not found"
+        );
+    }
+
+    #[test]
+    fn io_errors_map_to_kinds() {
+        let kind_of = |kind: io::ErrorKind| AppError::io("/proc/diskstats", io::Error::from(kind));
+        assert!(matches!(
+            kind_of(io::ErrorKind::TimedOut),
+            AppError::Timeout(_)
+        ));
+        assert!(matches!(
+            kind_of(io::ErrorKind::PermissionDenied),
+            AppError::PermissionDenied(_)
+        ));
+        match kind_of(io::ErrorKind::NotFound) {
+            AppError::Io(msg) => assert!(msg.starts_with("/proc/diskstats: ")),
+            other => panic!("expected Io, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use crate::error::AppError;
+use crate::nvidia;
+use crate::state::SysState;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+// 实时 GPU 负载，各厂商的数据统一为同一结构；单项不支持（笔记本上常见）时为 None
+#[derive(Serialize)]
+pub struct GpuMetrics {
+    pub index: u32,
+    pub name: String,
+    pub vendor: String,
+    pub utilization_pct: Option<f32>,
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+    // 摄氏度
+    pub temperature: Option<f32>,
+    // 瓦
+    pub power_draw: Option<f32>,
+}
+
+// 占用 GPU 的进程；无法获取的项为 None
+#[derive(Serialize)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    // 多块显卡或多个引擎时为各部分之和
+    pub gpu_percent: Option<f32>,
+    pub vram_bytes: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct GpuProcessReport {
+    // 当前平台 / 显卡无法按进程统计时为 false，与“没有进程在使用 GPU”区分
+    supported: bool,
+    processes: Vec<GpuProcess>,
+}
+
+// 默认返回的进程数
+const DEFAULT_GPU_PROCESS_LIMIT: usize = 20;
+
+// 按 GPU 使用率（其次显存）从高到低排列，最多返回 limit 个：
+// - Windows：“GPU Engine”性能计数器（与任务管理器的 GPU 列一致），失败时使用 NVIDIA 的数据
+// - Linux：仅 NVIDIA（NVML / nvidia-smi）
+// - macOS：powermetrics 的进程 GPU 时间，需要 root
+// 性能计数器与 powermetrics 都要采样一段时间，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_gpu_processes(
+    app: AppHandle,
+    limit: Option<usize>,
+) -> Result<GpuProcessReport, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_gpu_processes(&app.state::<SysState>(), limit)
+    })
+    .await
+    .map_err(|e| AppError::CommandFailed(format!("gpu process task: {}", e)))
+}
+
+fn collect_gpu_processes(state: &SysState, limit: Option<usize>) -> GpuProcessReport {
+    let Some(mut processes) = platform_gpu_processes() else {
+        return GpuProcessReport {
+            supported: false,
+            processes: Vec::new(),
+        };
+    };
+    processes.sort_by(|a, b| {
+        b.gpu_percent
+            .unwrap_or(0.0)
+            .total_cmp(&a.gpu_percent.unwrap_or(0.0))
+            .then(b.vram_bytes.unwrap_or(0).cmp(&a.vram_bytes.unwrap_or(0)))
+    });
+    processes.truncate(limit.unwrap_or(DEFAULT_GPU_PROCESS_LIMIT));
+
+    // 计数器与 NVML 只提供 PID，名称从进程表补齐
+    if processes.iter().any(|p| p.name.is_empty()) {
+        let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+        sys.refresh_processes();
+        for process in processes.iter_mut().filter(|p| p.name.is_empty()) {
+            if let Some(p) = sys.system.process(sysinfo::Pid::from_u32(process.pid)) {
+                process.name = p.name().to_string_lossy().into_owned();
+            }
+        }
+    }
+    GpuProcessReport {
+        supported: true,
+        processes,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn platform_gpu_processes() -> Option<Vec<GpuProcess>> {
+    windows_gpu_processes().or_else(nvidia::nvidia_processes)
+}
+
+#[cfg(target_os = "linux")]
+fn platform_gpu_processes() -> Option<Vec<GpuProcess>> {
+    nvidia::nvidia_processes()
+}
+
+#[cfg(target_os = "macos")]
+fn platform_gpu_processes() -> Option<Vec<GpuProcess>> {
+    macos_gpu_processes()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn platform_gpu_processes() -> Option<Vec<GpuProcess>> {
+    None
+}
+
+// 计数器实例名形如 "pid_1234_luid_0x00000000_0x0000D1A5_phys_0_eng_0_engtype_3D"。
+// 与任务管理器相同：同一进程先按引擎类型求和，再取各类型中的最大值；专用显存按进程求和
+#[cfg(target_os = "windows")]
+const WINDOWS_GPU_PROCESS_QUERY: &str = r#"
+[Console]::OutputEncoding = [Text.Encoding]::UTF8
+(Get-Counter '\GPU Engine(*)\Utilization Percentage', '\GPU Process Memory(*)\Dedicated Usage' -ErrorAction SilentlyContinue).CounterSamples |
+    Select-Object InstanceName, Path, CookedValue | ConvertTo-Json
+"#;
+
+#[cfg(target_os = "windows")]
+fn windows_gpu_processes() -> Option<Vec<GpuProcess>> {
+    use std::collections::HashMap;
+
+    let out = crate::util::run_checked(
+        "powershell",
+        &["-NoProfile", "-Command", WINDOWS_GPU_PROCESS_QUERY],
+    )
+    .ok()?;
+    let json: serde_json::Value = serde_json::from_str(&out).ok()?;
+    let samples = json.as_array().cloned().unwrap_or_else(|| vec![json]);
+
+    let mut engines: HashMap<(u32, String), f64> = HashMap::new();
+    let mut memory: HashMap<u32, u64> = HashMap::new();
+    for sample in &samples {
+        let instance = sample
+            .get("InstanceName")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let path = sample
+            .get("Path")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let value = sample
+            .get("CookedValue")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let Some(pid) = instance
+            .strip_prefix("pid_")
+            .and_then(|rest| rest.split('_').next())
+            .and_then(|pid| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if path.ends_with("utilization percentage") {
+            let engine = instance
+                .rsplit_once("engtype_")
+                .map(|(_, engine)| engine.to_ascii_lowercase())
+                .unwrap_or_default();
+            *engines.entry((pid, engine)).or_default() += value;
+        } else if path.ends_with("dedicated usage") {
+            *memory.entry(pid).or_default() += value as u64;
+        }
+    }
+
+    let mut usage: HashMap<u32, f64> = HashMap::new();
+    for ((pid, _), value) in engines {
+        let max = usage.entry(pid).or_default();
+        *max = max.max(value);
+    }
+    let mut pids: Vec<u32> = usage.keys().chain(memory.keys()).copied().collect();
+    pids.sort_unstable();
+    pids.dedup();
+    Some(
+        pids.into_iter()
+            // 系统空闲进程（PID 0）不计入
+            .filter(|&pid| pid != 0)
+            .map(|pid| GpuProcess {
+                pid,
+                name: String::new(),
+                gpu_percent: usage.get(&pid).map(|&v| v.min(100.0) as f32),
+                vram_bytes: memory.get(&pid).copied(),
+            })
+            .collect(),
+    )
+}
+
+// powermetrics 的 tasks 采样中每行依次为名称、PID、CPU ms/s、User%、两列 Deadlines、两列 Wakeups、GPU ms/s。
+// 名称可能包含空格，因此从右侧取各列；GPU ms/s 除以 10 即占用百分比
+#[cfg(target_os = "macos")]
+fn macos_gpu_processes() -> Option<Vec<GpuProcess>> {
+    // 非 root 运行时 powermetrics 必然失败，不启动子进程
+    // SAFETY: geteuid 没有参数，总是成功
+    if unsafe { libc::geteuid() } != 0 {
+        return None;
+    }
+    let out = std::process::Command::new("powermetrics")
+        .args([
+            "--samplers",
+            "tasks",
+            "--show-process-gpu",
+            "-n",
+            "1",
+            "-i",
+            "1000",
+        ])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut processes = Vec::new();
+    let mut in_tasks = false;
+    for line in text.lines() {
+        if line.contains("GPU ms/s") {
+            in_tasks = true;
+            continue;
+        }
+        if !in_tasks {
+            continue;
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 9 || tokens[0] == "ALL_TASKS" {
+            continue;
+        }
+        let (name, columns) = tokens.split_at(tokens.len() - 8);
+        let (Ok(pid), Ok(gpu_ms)) = (columns[0].parse::<u32>(), columns[7].parse::<f32>()) else {
+            continue;
+        };
+        processes.push(GpuProcess {
+            pid,
+            name: name.join(" "),
+            gpu_percent: Some((gpu_ms / 10.0).min(100.0)),
+            vram_bytes: None,
+        });
+    }
+    Some(processes)
+}
+
+// 各厂商的后端按运行时检测结果启用，都不可用时返回空列表。
+// 前端会轮询此命令，nvidia-smi / 性能计数器可能耗时较长，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_gpu_metrics(app: AppHandle) -> Result<Vec<GpuMetrics>, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_gpu_metrics(&app.state::<crate::GpuCache>())
+    })
+    .await
+    .map_err(|e| AppError::CommandFailed(format!("gpu metrics task: {}", e)))
+}
+
+fn collect_gpu_metrics(gpu_cache: &crate::GpuCache) -> Vec<GpuMetrics> {
+    let mut metrics = nvidia::nvidia_metrics();
+    metrics.extend(amd_metrics(gpu_cache));
+    for (index, gpu) in metrics.iter_mut().enumerate() {
+        gpu.index = index as u32;
+    }
+    metrics
+}
+
+// 通过 wgpu 枚举图形适配器，只得到名称、厂商、类型与驱动信息，没有显存数据。
+// 同一块显卡会在多个后端各出现一次，按名称去重；CPU 软件渲染器（llvmpipe、WARP）不计入。
+// 无头 / CI 环境中没有可用的适配器，返回空列表
+#[cfg(feature = "wgpu-fallback")]
+pub fn wgpu_gpus() -> Vec<crate::GpuInfo> {
+    let backends = wgpu::Backends::VULKAN | wgpu::Backends::METAL | wgpu::Backends::DX12;
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends,
+        ..Default::default()
+    });
+    let mut gpus: Vec<crate::GpuInfo> = Vec::new();
+    for adapter in instance.enumerate_adapters(backends) {
+        let info = adapter.get_info();
+        let kind = match info.device_type {
+            wgpu::DeviceType::IntegratedGpu => "integrated",
+            wgpu::DeviceType::DiscreteGpu => "discrete",
+            wgpu::DeviceType::Cpu => continue,
+            _ => "unknown",
+        };
+        if info.name.is_empty() || gpus.iter().any(|gpu| gpu.name == info.name) {
+            continue;
+        }
+        let driver = format!("{} {}", info.driver, info.driver_info);
+        gpus.push(crate::GpuInfo {
+            vendor: crate::pci_vendor_name(&format!("{:04X}", info.vendor)).to_string(),
+            driver_version: Some(driver.trim().to_string()).filter(|v| !v.is_empty()),
+            kind: kind.to_string(),
+            backend: Some(format!("{:?}", info.backend)),
+            name: info.name,
+            ..Default::default()
+        });
+    }
+    gpus
+}
+
+// amdgpu 驱动在 sysfs 中提供使用率与显存；APU 的 mem_info_vram_total 即 BIOS 划出的专用显存，
+// 不包含共享的系统内存。温度与功耗来自 hwmon（毫摄氏度、微瓦）。
+// 名称按 PCI 地址取自显卡信息缓存，缓存中没有对应显卡时才调用 lspci
+#[cfg(target_os = "linux")]
+fn amd_metrics(gpu_cache: &crate::GpuCache) -> Vec<GpuMetrics> {
+    use std::fs;
+
+    let read = |path: &std::path::Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let number = |path: &std::path::Path| read(path).and_then(|v| v.parse::<u64>().ok());
+    let cached = crate::cached_gpu_list(gpu_cache).unwrap_or_default();
+    let mut lspci: Option<Vec<crate::LspciGpu>> = None;
+
+    let mut cards: Vec<_> = fs::read_dir("/sys/class/drm")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .and_then(|n| n.strip_prefix("card"))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort_by_key(|e| e.file_name());
+
+    cards
+        .into_iter()
+        .filter_map(|card| {
+            let device = card.path().join("device");
+            if read(&device.join("vendor")).as_deref() != Some("0x1002") {
+                return None;
+            }
+            // 只有 amdgpu 提供 gpu_busy_percent，旧的 radeon 驱动跳过
+            let busy = number(&device.join("gpu_busy_percent"))?;
+            let slot = fs::canonicalize(&device)
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+            let name = slot
+                .and_then(|slot| {
+                    cached
+                        .iter()
+                        .find(|g| g.pci_bus_id.as_deref() == Some(slot.as_str()))
+                        .map(|g| g.name.clone())
+                        .or_else(|| {
+                            lspci
+                                .get_or_insert_with(crate::lspci_gpus)
+                                .iter()
+                                .find(|g| g.slot == slot)
+                                .map(|g| g.name.clone())
+                        })
+                })
+                .unwrap_or_else(|| format!("AMD GPU ({})", card.file_name().to_string_lossy()));
+            let hwmon = fs::read_dir(device.join("hwmon"))
+                .ok()
+                .and_then(|mut entries| entries.next())
+                .and_then(|entry| entry.ok())
+                .map(|entry| entry.path());
+            let hwmon_value = |file: &str| hwmon.as_ref().and_then(|dir| number(&dir.join(file)));
+
+            Some(GpuMetrics {
+                index: 0,
+                name,
+                vendor: "AMD".to_string(),
+                utilization_pct: Some(busy as f32),
+                memory_used: number(&device.join("mem_info_vram_used")),
+                memory_total: number(&device.join("mem_info_vram_total")).filter(|&v| v > 0),
+                temperature: hwmon_value("temp1_input").map(|t| t as f32 / 1000.0),
+                power_draw: hwmon_value("power1_average")
+                    .or_else(|| hwmon_value("power1_input"))
+                    .map(|uw| uw as f32 / 1_000_000.0),
+            })
+        })
+        .collect()
+}
+
+// GPU 性能计数器按适配器 LUID 区分，无法直接对应到显卡名称，
+// 因此只在 AMD 是唯一显卡时使用；计数器名称随系统语言变化，非英文系统上可能查询不到
+#[cfg(target_os = "windows")]
+const WINDOWS_GPU_COUNTER_QUERY: &str = r#"
+$samples = (Get-Counter '\GPU Engine(*engtype_3D)\Utilization Percentage', '\GPU Adapter Memory(*)\Dedicated Usage' -ErrorAction SilentlyContinue).CounterSamples
+[PSCustomObject]@{
+    Utilization = ($samples | Where-Object { $_.Path -like '*utilization percentage' } | Measure-Object CookedValue -Sum).Sum
+    DedicatedUsage = ($samples | Where-Object { $_.Path -like '*dedicated usage' } | Measure-Object CookedValue -Sum).Sum
+} | ConvertTo-Json
+"#;
+
+#[cfg(target_os = "windows")]
+fn amd_metrics(gpu_cache: &crate::GpuCache) -> Vec<GpuMetrics> {
+    // 优先使用缓存（过期也无妨，只需要名称与显存总量），尚未探测过时才探测
+    let Some(gpus) =
+        crate::cached_gpu_list(gpu_cache).or_else(|| crate::get_gpu_info(gpu_cache, false).ok())
+    else {
+        return Vec::new();
+    };
+    let [gpu] = gpus.as_slice() else {
+        return Vec::new();
+    };
+    if gpu.vendor != "AMD" {
+        return Vec::new();
+    }
+    let Ok(out) = crate::util::run_checked(
+        "powershell",
+        &["-NoProfile", "-Command", WINDOWS_GPU_COUNTER_QUERY],
+    ) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&out) else {
+        return Vec::new();
+    };
+    vec![GpuMetrics {
+        index: 0,
+        name: gpu.name.clone(),
+        vendor: "AMD".to_string(),
+        // 各进程在 3D 引擎上的占用之和，多引擎并行时可能超过 100
+        utilization_pct: json
+            .get("Utilization")
+            .and_then(|v| v.as_f64())
+            .map(|v| v.min(100.0) as f32),
+        memory_used: json
+            .get("DedicatedUsage")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u64),
+        memory_total: gpu.vram_bytes,
+        temperature: None,
+        power_draw: None,
+    }]
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn amd_metrics(_gpu_cache: &crate::GpuCache) -> Vec<GpuMetrics> {
+    Vec::new()
+}
@@ -0,0 +1,294 @@
+use crate::error::AppError;
+use serde::Serialize;
+
+// 整机、主板与固件信息；平台不提供或厂商未填写的项为 None
+#[derive(Serialize, Default)]
+pub struct HardwareInfo {
+    manufacturer: Option<String>,
+    model: Option<String>,
+    serial_number: Option<String>,
+    board_manufacturer: Option<String>,
+    board_model: Option<String>,
+    bios_vendor: Option<String>,
+    bios_version: Option<String>,
+    // YYYY-MM-DD
+    firmware_date: Option<String>,
+}
+
+// 序列号属于敏感信息，redact_serial 为 true 时只保留最后 4 位
+#[tauri::command]
+pub fn get_hardware_info(redact_serial: bool) -> Result<HardwareInfo, AppError> {
+    let mut info = read_hardware_info()?;
+    if redact_serial {
+        info.serial_number = info.serial_number.as_deref().map(redact);
+    }
+    Ok(info)
+}
+
+fn redact(serial: &str) -> String {
+    let chars: Vec<char> = serial.chars().collect();
+    // 太短的序列号整体遮盖，避免原样返回
+    let keep = if chars.len() > 4 { 4 } else { 0 };
+    let masked = "*".repeat(chars.len() - keep);
+    masked + &chars[chars.len() - keep..].iter().collect::<String>()
+}
+
+// 厂商常留下占位字符串，视为未填写
+fn clean_value(value: &str) -> Option<String> {
+    const PLACEHOLDERS: [&str; 7] = [
+        "to be filled by o.e.m.",
+        "default string",
+        "system serial number",
+        "system product name",
+        "system manufacturer",
+        "not applicable",
+        "none",
+    ];
+    let value = value.trim();
+    if value.is_empty() || PLACEHOLDERS.contains(&value.to_ascii_lowercase().as_str()) {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn read_hardware_info() -> Result<HardwareInfo, AppError> {
+    let output = std::process::Command::new("system_profiler")
+        .args(["SPHardwareDataType", "-json"])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("system_profiler: {}", e)))?;
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .map_err(|e| AppError::Parse(format!("SPHardwareDataType JSON: {}", e)))?;
+    parse_sp_hardware(&json)
+}
+
+// Mac 没有独立的 BIOS 厂商与主板型号，固件版本取 Boot ROM 版本。
+// 解析函数不依赖系统调用，测试时在所有平台上编译
+#[cfg(any(target_os = "macos", test))]
+fn parse_sp_hardware(json: &serde_json::Value) -> Result<HardwareInfo, AppError> {
+    let hw = json
+        .get("SPHardwareDataType")
+        .and_then(|v| v.get(0))
+        .ok_or_else(|| AppError::Parse("SPHardwareDataType: missing entry".to_string()))?;
+    let field = |key: &str| hw.get(key).and_then(|v| v.as_str()).and_then(clean_value);
+
+    let model = match (field("machine_name"), field("machine_model")) {
+        (Some(name), Some(id)) => Some(format!("{} ({})", name, id)),
+        (name, id) => name.or(id),
+    };
+    Ok(HardwareInfo {
+        manufacturer: Some("Apple".to_string()),
+        model,
+        serial_number: field("serial_number"),
+        board_manufacturer: None,
+        board_model: field("model_number"),
+        bios_vendor: Some("Apple".to_string()),
+        bios_version: field("boot_rom_version"),
+        firmware_date: None,
+    })
+}
+
+#[cfg(target_os = "windows")]
+const WINDOWS_HARDWARE_QUERY: &str = "\
+$cs = Get-CimInstance Win32_ComputerSystem; \
+$board = Get-CimInstance Win32_BaseBoard | Select-Object -First 1; \
+$bios = Get-CimInstance Win32_BIOS | Select-Object -First 1; \
+[PSCustomObject]@{ \
+Manufacturer = $cs.Manufacturer; Model = $cs.Model; \
+BiosSerial = $bios.SerialNumber; BoardSerial = $board.SerialNumber; \
+BoardManufacturer = $board.Manufacturer; BoardProduct = $board.Product; \
+BiosVendor = $bios.Manufacturer; BiosVersion = $bios.SMBIOSBIOSVersion; \
+ReleaseDate = if ($bios.ReleaseDate) { $bios.ReleaseDate.ToString('yyyy-MM-dd') } else { $null } \
+} | ConvertTo-Json";
+
+#[cfg(target_os = "windows")]
+fn read_hardware_info() -> Result<HardwareInfo, AppError> {
+    let stdout = crate::util::run_checked(
+        "powershell",
+        &["-NoProfile", "-Command", WINDOWS_HARDWARE_QUERY],
+    )?;
+    let json = serde_json::from_str::<serde_json::Value>(&stdout)
+        .map_err(|e| AppError::Parse(format!("hardware JSON: {}", e)))?;
+    Ok(parse_windows_hardware(&json))
+}
+
+// 整机序列号优先取 BIOS，组装机常只在主板上填写
+#[cfg(any(target_os = "windows", test))]
+fn parse_windows_hardware(json: &serde_json::Value) -> HardwareInfo {
+    let field = |key: &str| json.get(key).and_then(|v| v.as_str()).and_then(clean_value);
+    HardwareInfo {
+        manufacturer: field("Manufacturer"),
+        model: field("Model"),
+        serial_number: field("BiosSerial").or_else(|| field("BoardSerial")),
+        board_manufacturer: field("BoardManufacturer"),
+        board_model: field("BoardProduct"),
+        bios_vendor: field("BiosVendor"),
+        bios_version: field("BiosVersion"),
+        firmware_date: field("ReleaseDate"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_hardware_info() -> Result<HardwareInfo, AppError> {
+    const DMI_DIR: &str = "/sys/class/dmi/id";
+    if !std::path::Path::new(DMI_DIR).is_dir() {
+        return Err(AppError::Unsupported(
+            "DMI information is not available on this system".to_string(),
+        ));
+    }
+    Ok(parse_dmi(|name| {
+        std::fs::read_to_string(format!("{}/{}", DMI_DIR, name)).ok()
+    }))
+}
+
+// 序列号文件通常只有 root 可读，读取失败时为 None
+#[cfg(any(target_os = "linux", test))]
+fn parse_dmi(read: impl Fn(&str) -> Option<String>) -> HardwareInfo {
+    let field = |name: &str| read(name).as_deref().and_then(clean_value);
+    HardwareInfo {
+        manufacturer: field("sys_vendor"),
+        model: field("product_name"),
+        serial_number: field("product_serial").or_else(|| field("board_serial")),
+        board_manufacturer: field("board_vendor"),
+        board_model: field("board_name"),
+        bios_vendor: field("bios_vendor"),
+        bios_version: field("bios_version"),
+        firmware_date: field("bios_date").map(|date| dmi_date(&date)),
+    }
+}
+
+// DMI 的 bios_date 为 MM/DD/YYYY，换成与其他平台一致的 YYYY-MM-DD；其他格式原样返回
+#[cfg(any(target_os = "linux", test))]
+fn dmi_date(date: &str) -> String {
+    let parts: Vec<&str> = date.split('/').collect();
+    match parts[..] {
+        [month, day, year]
+            if month.len() == 2
+                && day.len() == 2
+                && year.len() == 4
+                && parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit())) =>
+        {
+            format!("{}-{}-{}", year, month, day)
+        }
+        _ => date.to_string(),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn read_hardware_info() -> Result<HardwareInfo, AppError> {
+    Err(AppError::Unsupported(
+        "hardware info is not supported on this platform".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parse_dmi_reads_sysfs_fields() {
+        let files: HashMap<&str, &str> = [
+            ("sys_vendor", "LENOVO\n"),
+            ("product_name", "20XW0055US\n"),
+            ("board_vendor", "LENOVO\n"),
+            ("board_name", "20XW0055US\n"),
+            ("board_serial", "L1HF16S00KV\n"),
+            ("bios_vendor", "LENOVO\n"),
+            ("bios_version", "N32ET86W (1.62 )\n"),
+            ("bios_date", "03/20/2023\n"),
+        ]
+        .into_iter()
+        .collect();
+        // product_serial 只有 root 可读，读取失败
+        let info = parse_dmi(|name| files.get(name).map(|v| v.to_string()));
+
+        assert_eq!(info.manufacturer.as_deref(), Some("LENOVO"));
+        assert_eq!(info.model.as_deref(), Some("20XW0055US"));
+        assert_eq!(info.serial_number.as_deref(), Some("L1HF16S00KV"));
+        assert_eq!(info.bios_version.as_deref(), Some("N32ET86W (1.62 )"));
+        assert_eq!(info.firmware_date.as_deref(), Some("2023-03-20"));
+    }
+
+    #[test]
+    fn parse_dmi_drops_placeholders() {
+        let info = parse_dmi(|name| match name {
+            "sys_vendor" => Some("System manufacturer\n".to_string()),
+            "product_name" => Some("To Be Filled By O.E.M.\n".to_string()),
+            "board_vendor" => Some("ASUSTeK COMPUTER INC.\n".to_string()),
+            _ => None,
+        });
+        assert_eq!(info.manufacturer, None);
+        assert_eq!(info.model, None);
+        assert_eq!(
+            info.board_manufacturer.as_deref(),
+            Some("ASUSTeK COMPUTER INC.")
+        );
+        assert_eq!(info.firmware_date, None);
+    }
+
+    #[test]
+    fn dmi_date_converts_to_iso() {
+        assert_eq!(dmi_date("03/20/2023"), "2023-03-20");
+        assert_eq!(dmi_date("12/01/2019"), "2019-12-01");
+        // 非 MM/DD/YYYY 的值原样保留
+        assert_eq!(dmi_date("2023-03-20"), "2023-03-20");
+        assert_eq!(dmi_date("3/20/2023"), "3/20/2023");
+    }
+
+    #[test]
+    fn parse_sp_hardware_reads_first_entry() {
+        let json = serde_json::json!({
+            "SPHardwareDataType": [{
+                "_name": "hardware_overview",
+                "boot_rom_version": "10151.101.3",
+                "chip_type": "Apple M2 Pro",
+                "machine_model": "Mac14,10",
+                "machine_name": "MacBook Pro",
+                "model_number": "MPHE3LL/A",
+                "serial_number": "C02XYZ123ABC"
+            }]
+        });
+        let info = parse_sp_hardware(&json).unwrap();
+        assert_eq!(info.manufacturer.as_deref(), Some("Apple"));
+        assert_eq!(info.model.as_deref(), Some("MacBook Pro (Mac14,10)"));
+        assert_eq!(info.serial_number.as_deref(), Some("C02XYZ123ABC"));
+        assert_eq!(info.board_model.as_deref(), Some("MPHE3LL/A"));
+        assert_eq!(info.bios_version.as_deref(), Some("10151.101.3"));
+        assert_eq!(info.firmware_date, None);
+
+        let empty = serde_json::json!({ "SPHardwareDataType": [] });
+        assert!(matches!(parse_sp_hardware(&empty), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_windows_hardware_prefers_bios_serial() {
+        let json = serde_json::json!({
+            "Manufacturer": "Dell Inc.",
+            "Model": "XPS 15 9520",
+            "BiosSerial": "8XK4LM3",
+            "BoardSerial": "/8XK4LM3/CNCMK0024G00F4/",
+            "BoardManufacturer": "Dell Inc.",
+            "BoardProduct": "0RH1JY",
+            "BiosVendor": "Dell Inc.",
+            "BiosVersion": "1.17.0",
+            "ReleaseDate": "2023-08-14"
+        });
+        let info = parse_windows_hardware(&json);
+        assert_eq!(info.model.as_deref(), Some("XPS 15 9520"));
+        assert_eq!(info.serial_number.as_deref(), Some("8XK4LM3"));
+        assert_eq!(info.board_model.as_deref(), Some("0RH1JY"));
+        assert_eq!(info.firmware_date.as_deref(), Some("2023-08-14"));
+
+        // 组装机的 BIOS 序列号常为占位字符串，改用主板序列号
+        let json = serde_json::json!({
+            "BiosSerial": "Default string",
+            "BoardSerial": "MB-1234567890",
+            "ReleaseDate": null
+        });
+        let info = parse_windows_hardware(&json);
+        assert_eq!(info.serial_number.as_deref(), Some("MB-1234567890"));
+        assert_eq!(info.firmware_date, None);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::error::AppError;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct LocaleInfo {
+    // 按优先级排列的语言标签，如 ["zh-CN", "en-US"]，无法获取时为空
+    locales: Vec<String>,
+    timezone: Option<TimezoneInfo>,
+    // 系统时钟，ISO 8601 格式；时区获取失败时 local_time 为 None
+    local_time: Option<String>,
+    utc_time: String,
+    unix_time: u64,
+}
+
+#[derive(Serialize)]
+pub struct TimezoneInfo {
+    // IANA 名称（Asia/Shanghai），无法获取时为 None
+    name: Option<String>,
+    // 当前相对 UTC 的偏移（秒），东区为正
+    utc_offset_secs: i32,
+    dst_active: Option<bool>,
+    // 时区缩写（CST、PDT），Windows 不提供
+    abbreviation: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_timezone_info() -> Result<TimezoneInfo, AppError> {
+    #[cfg(unix)]
+    {
+        Ok(get_timezone_info_unix())
+    }
+    #[cfg(windows)]
+    {
+        Ok(get_timezone_info_windows())
+    }
+}
+
+// 用于复现问题时核对用户的语言、时区与系统时钟
+#[tauri::command]
+pub fn get_locale_info() -> LocaleInfo {
+    let unix_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let timezone = get_timezone_info().ok();
+    LocaleInfo {
+        locales: preferred_locales(),
+        local_time: timezone
+            .as_ref()
+            .map(|tz| format_timestamp(unix_time, tz.utc_offset_secs)),
+        utc_time: format_timestamp(unix_time, 0),
+        unix_time,
+        timezone,
+    }
+}
+
+// 格式化为 "2024-05-01T08:30:00+08:00"，偏移为 0 时以 Z 结尾
+fn format_timestamp(unix_time: u64, offset_secs: i32) -> String {
+    let local = unix_time as i64 + offset_secs as i64;
+    let (days, secs) = (local.div_euclid(86_400), local.rem_euclid(86_400));
+    // 由 1970-01-01 起的天数换算公历日期（Howard Hinnant 的 civil_from_days）
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let offset = if offset_secs == 0 {
+        "Z".to_string()
+    } else {
+        let sign = if offset_secs < 0 { '-' } else { '+' };
+        let abs = offset_secs.unsigned_abs();
+        format!("{}{:02}:{:02}", sign, abs / 3600, abs % 3600 / 60)
+    };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        offset
+    )
+}
+
+// 环境变量优先（LANGUAGE 可包含多个语言），图形界面启动时可能未设置，
+// 再回退到系统级配置文件
+#[cfg(target_os = "linux")]
+fn preferred_locales() -> Vec<String> {
+    let mut raw: Vec<String> = Vec::new();
+    if let Ok(language) = std::env::var("LANGUAGE") {
+        raw.extend(language.split(':').map(str::to_string));
+    }
+    for key in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        if let Ok(value) = std::env::var(key) {
+            raw.push(value);
+        }
+    }
+    if raw.is_empty() {
+        for path in ["/etc/locale.conf", "/etc/default/locale"] {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                raw.extend(content.lines().filter_map(|line| {
+                    let value = line.trim().strip_prefix("LANG=")?;
+                    Some(value.trim_matches('"').to_string())
+                }));
+            }
+        }
+    }
+
+    let mut locales: Vec<String> = Vec::new();
+    for locale in raw.iter().filter_map(|l| posix_to_language_tag(l)) {
+        if !locales.contains(&locale) {
+            locales.push(locale);
+        }
+    }
+    locales
+}
+
+// "zh_CN.UTF-8" / "de_DE@euro" -> "zh-CN" / "de-DE"，C 与 POSIX 不是实际语言
+#[cfg(target_os = "linux")]
+fn posix_to_language_tag(locale: &str) -> Option<String> {
+    let base = locale.split(['.', '@']).next()?.trim();
+    if base.is_empty() || base == "C" || base == "POSIX" {
+        return None;
+    }
+    Some(base.replace('_', "-"))
+}
+
+// AppleLanguages 为 plist 数组：( "zh-Hans-CN", "en-US" )
+#[cfg(target_os = "macos")]
+fn preferred_locales() -> Vec<String> {
+    crate::util::run_checked("defaults", &["read", "-g", "AppleLanguages"])
+        .map(|out| {
+            out.lines()
+                .map(|line| line.trim().trim_end_matches(',').trim_matches('"'))
+                .filter(|item| !item.is_empty() && *item != "(" && *item != ")")
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 用户语言列表之后补充当前区域格式（Get-Culture），两者可能不同
+#[cfg(target_os = "windows")]
+fn preferred_locales() -> Vec<String> {
+    let Ok(out) = crate::util::run_checked(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "@((Get-WinUserLanguageList).LanguageTag) + (Get-Culture).Name | ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&out) else {
+        return Vec::new();
+    };
+    let mut locales: Vec<String> = Vec::new();
+    let items = json.as_array().cloned().unwrap_or_else(|| vec![json]);
+    for tag in items.iter().filter_map(|v| v.as_str()) {
+        if !tag.is_empty() && !locales.iter().any(|l| l == tag) {
+            locales.push(tag.to_string());
+        }
+    }
+    locales
+}
+
+#[cfg(unix)]
+fn get_timezone_info_unix() -> TimezoneInfo {
+    // localtime_r 按当前时区规则换算，tm_gmtoff / tm_isdst / tm_zone 即偏移、夏令时与缩写
+    // SAFETY: tm 由 localtime_r 填充，tm_zone 指向 libc 内部的静态字符串
+    let (offset, dst, abbreviation) = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            (0, None, None)
+        } else {
+            let abbreviation = (!tm.tm_zone.is_null()).then(|| {
+                std::ffi::CStr::from_ptr(tm.tm_zone)
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            // tm_isdst 为负表示无法确定
+            let dst = (tm.tm_isdst >= 0).then_some(tm.tm_isdst > 0);
+            (tm.tm_gmtoff as i32, dst, abbreviation)
+        }
+    };
+
+    TimezoneInfo {
+        name: unix_timezone_name(),
+        utc_offset_secs: offset,
+        dst_active: dst,
+        abbreviation,
+    }
+}
+
+// 依次尝试 $TZ、/etc/localtime 链接目标（.../zoneinfo/Asia/Shanghai）与 /etc/timezone
+#[cfg(unix)]
+fn unix_timezone_name() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':');
+        if !tz.is_empty() {
+            // TZ 也可能是文件路径
+            return Some(tz.rsplit_once("zoneinfo/").map_or(tz, |(_, name)| name).to_string());
+        }
+    }
+    if let Ok(target) = std::fs::read_link("/etc/localtime") {
+        let target = target.to_string_lossy().into_owned();
+        if let Some((_, name)) = target.rsplit_once("zoneinfo/") {
+            return Some(name.to_string());
+        }
+    }
+    std::fs::read_to_string("/etc/timezone")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// 时区名称通过 WinRT 的 Windows.Globalization.Calendar 获取，返回的是 IANA 名称而不是
+// Windows 时区 ID（China Standard Time）；偏移与夏令时由 CRT 计算，不需要启动 PowerShell
+#[cfg(windows)]
+fn get_timezone_info_windows() -> TimezoneInfo {
+    let (utc_offset_secs, dst_active) = crt_utc_offset();
+    TimezoneInfo {
+        name: iana_time_zone::get_timezone().ok(),
+        utc_offset_secs,
+        dst_active,
+        abbreviation: None,
+    }
+}
+
+// localtime_s 按系统时区换算，与 gmtime_s 的结果相减即为偏移
+#[cfg(windows)]
+fn crt_utc_offset() -> (i32, Option<bool>) {
+    // SAFETY: local / utc 由 localtime_s / gmtime_s 填充，返回非 0 时不读取
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut local: libc::tm = std::mem::zeroed();
+        let mut utc: libc::tm = std::mem::zeroed();
+        if libc::localtime_s(&mut local, &now) != 0 || libc::gmtime_s(&mut utc, &now) != 0 {
+            return (0, None);
+        }
+        let fields = |tm: &libc::tm| {
+            (
+                tm.tm_year,
+                tm.tm_yday,
+                tm.tm_hour * 3600 + tm.tm_min * 60 + tm.tm_sec,
+            )
+        };
+        // tm_isdst 为负表示无法确定
+        let dst = (local.tm_isdst >= 0).then_some(local.tm_isdst > 0);
+        (offset_between(fields(&local), fields(&utc)), dst)
+    }
+}
+
+// 参数为 (年, 年内第几天, 当天秒数)。本地时间与 UTC 最多相差一天，跨年时 tm_yday 从 0 重新计数
+#[cfg(any(windows, test))]
+fn offset_between(local: (i32, i32, i32), utc: (i32, i32, i32)) -> i32 {
+    let (local_year, local_yday, local_secs) = local;
+    let (utc_year, utc_yday, utc_secs) = utc;
+    let days = match local_year.cmp(&utc_year) {
+        std::cmp::Ordering::Greater => 1,
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => local_yday - utc_yday,
+    };
+    days * 86_400 + local_secs - utc_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_within_the_same_day() {
+        // 2024-04-10 16:30 (UTC+8) / 08:30 UTC
+        assert_eq!(
+            offset_between((124, 100, 59_400), (124, 100, 30_600)),
+            8 * 3600
+        );
+        assert_eq!(offset_between((124, 100, 30_600), (124, 100, 30_600)), 0);
+    }
+
+    #[test]
+    fn offset_across_midnight_and_new_year() {
+        // UTC+8 的 04:00 对应前一天 20:00 UTC
+        assert_eq!(
+            offset_between((124, 101, 14_400), (124, 100, 72_000)),
+            8 * 3600
+        );
+        // UTC-5 的 2023-12-31 21:00 对应 2024-01-01 02:00 UTC
+        assert_eq!(
+            offset_between((123, 364, 75_600), (124, 0, 7_200)),
+            -5 * 3600
+        );
+        // UTC+5:45 的 2024-01-01 00:30 对应 2023-12-31 18:45 UTC
+        assert_eq!(
+            offset_between((124, 0, 1_800), (123, 364, 67_500)),
+            5 * 3600 + 45 * 60
+        );
+    }
+
+    #[test]
+    fn timestamps_include_the_offset() {
+        assert_eq!(format_timestamp(0, 0), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_timestamp(1_714_552_200, 8 * 3600),
+            "2024-05-01T16:30:00+08:00"
+        );
+        assert_eq!(
+            format_timestamp(1_704_067_200, -(5 * 3600 + 30 * 60)),
+            "2023-12-31T18:30:00-05:30"
+        );
+    }
+}
@@ -1,14 +1,65 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use serde::Serialize;
-use std::time::Instant;
-use sysinfo::{System, Networks};
+mod audio;
+mod bench;
+mod camera;
+mod cpu;
+mod desktop;
+mod disk;
+mod displays;
+mod error;
+mod gpu;
+mod hardware;
+mod locale;
+mod memory;
+mod metrics;
+mod network;
+mod nvidia;
+mod power;
+mod process;
+mod runtimes;
+mod screenshot;
+mod sensors;
+mod sockets;
+mod state;
+mod users;
+mod util;
+mod virt;
+
+use error::AppError;
+use serde::{Deserialize, Serialize};
+use state::SysState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tauri::{Emitter, Manager, State};
 
 #[derive(Serialize)]
 struct NetworkIface {
     name: String,
+    // 接口启动以来的累计字节数
     received: u64,
     transmitted: u64,
+    // 两次调用之间的速率（字节/秒），没有上一次采样（首次调用或新出现的接口）时为 None
+    rx_bytes_per_sec: Option<u64>,
+    tx_bytes_per_sec: Option<u64>,
+    // 全零（虚拟接口常见）时为 None
+    mac_address: Option<String>,
+    addresses: Vec<IfaceAddress>,
+    mtu: Option<u32>,
+    is_up: Option<bool>,
+    is_loopback: bool,
+    // 链路速率（Mbps），无线网卡和 macOS 上通常没有
+    speed_mbps: Option<u64>,
+    // 见 network::iface_kind
+    kind: String,
+}
+
+#[derive(Serialize)]
+struct IfaceAddress {
+    address: String,
+    prefix: u8,
 }
 
 #[derive(Serialize)]
@@ -18,105 +69,727 @@ struct CpuCore {
     frequency: u64,
 }
 
-#[derive(Serialize)]
+// 单个逻辑核心的读数
+struct CpuReading {
+    name: String,
+    brand: String,
+    usage: f32,
+    frequency: u64,
+}
+
+// CPU 概况的数据来源，测试中用固定数据代替 sysinfo
+trait CpuSource {
+    fn readings(&self) -> Vec<CpuReading>;
+    fn physical_cores(&self) -> Option<usize>;
+}
+
+impl CpuSource for System {
+    fn readings(&self) -> Vec<CpuReading> {
+        self.cpus()
+            .iter()
+            .map(|cpu| CpuReading {
+                name: cpu.name().to_string(),
+                brand: cpu.brand().to_string(),
+                usage: cpu.cpu_usage(),
+                frequency: cpu.frequency(),
+            })
+            .collect()
+    }
+
+    fn physical_cores(&self) -> Option<usize> {
+        self.physical_core_count()
+    }
+}
+
+// 填充 CPU 型号、核心数与使用率。精简的虚拟机或沙箱中 CPU 列表可能暂时为空，
+// 此时逻辑核心数与核心列表都为 None，使用率为 0
+fn fill_cpu_info(info: &mut SystemInfo, source: &impl CpuSource) {
+    let readings = source.readings();
+    let cpu_brand = readings
+        .first()
+        .map(|cpu| cpu.brand.trim())
+        .filter(|brand| !brand.is_empty())
+        .unwrap_or("Unknown")
+        .to_string();
+    let cpu_usage = if readings.is_empty() {
+        0.0
+    } else {
+        readings.iter().map(|c| c.usage).sum::<f32>() / readings.len() as f32
+    };
+
+    info.cpu_brand = Some(cpu_brand);
+    info.cpu_physical_cores = source.physical_cores();
+    info.cpu_arch = Some(std::env::consts::ARCH.to_string());
+    info.cpu_usage = Some(cpu_usage);
+    if !readings.is_empty() {
+        info.cpu_logical_cores = Some(readings.len());
+        info.cpu_cores = Some(
+            readings
+                .into_iter()
+                .map(|cpu| CpuCore {
+                    name: cpu.name,
+                    usage: cpu.usage,
+                    frequency: cpu.frequency,
+                })
+                .collect(),
+        );
+    }
+}
+
+#[derive(Serialize, Default, Clone)]
 struct GpuInfo {
     name: String,
     vendor: String,
-    vram: Option<String>,
+    // 显存总量（字节）
+    vram_bytes: Option<u64>,
+    // 由 vram_bytes 换算的可读字符串，如 "8 GB" / "1536 MB"；统一内存架构下为 "shared"
+    vram_display: Option<String>,
+    // Apple Silicon：GPU 与 CPU 共用系统内存，没有独立显存
+    unified_memory: bool,
+    // 仅 macOS：GPU 核心数与支持的 Metal 版本（如 "Metal 3"）
+    gpu_cores: Option<u32>,
+    metal_family: Option<String>,
+    vram_used: Option<u64>,
+    vram_usage_pct: Option<f32>,
+    vram_pressure: Option<String>,
+    driver_version: Option<String>,
+    // 仅 NVIDIA（需要驱动自带的 NVML）：GPU 使用率（百分比）与温度（℃）
+    utilization: Option<f32>,
+    temperature: Option<f32>,
+    // 驱动主显示器的显卡；无法判断时为列表中的第一块
+    is_primary: bool,
+    // "integrated" / "discrete" / "unknown"
+    kind: String,
+    // 仅 wgpu 回退路径：适配器所用的图形 API（"Vulkan" / "Metal" / "Dx12"）
+    backend: Option<String>,
+    // PCI 地址（如 "0000:01:00.0"），仅用于与 NVML 的设备对应
+    #[serde(skip)]
+    pci_bus_id: Option<String>,
 }
 
-#[derive(Serialize)]
+// 显存占用率阈值（百分比）
+const VRAM_PRESSURE_HIGH_PCT: f32 = 85.0;
+const VRAM_PRESSURE_CRITICAL_PCT: f32 = 95.0;
+
+impl GpuInfo {
+    // 根据已用/总显存计算占用率和压力等级，缺少任一数值时保持 None
+    fn update_vram_usage(&mut self) {
+        let (Some(used), Some(total)) = (self.vram_used, self.vram_bytes) else {
+            return;
+        };
+        if total == 0 {
+            return;
+        }
+        let pct = (used as f64 / total as f64 * 100.0) as f32;
+        let pressure = if pct >= VRAM_PRESSURE_CRITICAL_PCT {
+            "critical"
+        } else if pct >= VRAM_PRESSURE_HIGH_PCT {
+            "high"
+        } else {
+            "ok"
+        };
+        self.vram_usage_pct = Some(pct);
+        self.vram_pressure = Some(pressure.to_string());
+    }
+}
+
+#[derive(Serialize, Default)]
 struct SystemInfo {
     os_name: Option<String>,
     hostname: Option<String>,
     kernel_version: Option<String>,
     os_version: Option<String>,
-    cpu_brand: String,
+    // 仅 Windows：如 "Windows 11 Pro"、"23H2"、"22631.3447"
+    os_edition: Option<String>,
+    os_display_version: Option<String>,
+    os_build: Option<String>,
+    // 是否运行在虚拟机或容器中
+    virtualization: Option<virt::VirtInfo>,
+    cpu_brand: Option<String>,
     cpu_physical_cores: Option<usize>,
-    cpu_logical_cores: usize,
-    cpu_arch: String,
-    cpu_usage: f32,
-    cpu_cores: Vec<CpuCore>,
-    total_memory: u64,
-    used_memory: u64,
-    total_swap: u64,
-    used_swap: u64,
-    uptime: u64,
-    network_ifaces: Vec<NetworkIface>,
-    gpus: Vec<GpuInfo>,
+    cpu_logical_cores: Option<usize>,
+    cpu_arch: Option<String>,
+    cpu_usage: Option<f32>,
+    cpu_cores: Option<Vec<CpuCore>>,
+    // 缓存大小、指令集扩展与各核心频率范围
+    cpu_details: Option<cpu::CpuDetails>,
+    // 仅 macOS / Linux
+    load_average: Option<cpu::LoadAvg>,
+    // CPU 使用率实际对应的采样窗口（毫秒），可能与请求的 sample_ms 不同
+    effective_sample_ms: Option<u64>,
+    total_memory: Option<u64>,
+    // Linux / Windows 上即 total - available，与 free 命令的 used 不同（后者不含 buff/cache 之外的可回收部分）；
+    // macOS 上为活跃 + 联动 + 压缩 + 推测页之和，与 total - available 不一定相等
+    used_memory: Option<u64>,
+    // 可用内存（含可回收的缓存），比 used_memory 更能反映实际压力
+    available_memory: Option<u64>,
+    // 完全未使用的内存，不含缓存，通常远小于 available_memory
+    free_memory: Option<u64>,
+    // used_memory / total_memory（百分比）
+    memory_used_pct: Option<f32>,
+    memory_breakdown: Option<memory::MemoryBreakdown>,
+    // 仅 macOS
+    memory_pressure: Option<memory::MemoryPressure>,
+    total_swap: Option<u64>,
+    used_swap: Option<u64>,
+    uptime: Option<u64>,
+    // 开机时间（Unix 时间戳，秒），与 uptime 由同一时刻推算，两者之和即采集时刻
+    boot_time: Option<u64>,
+    network_ifaces: Option<Vec<NetworkIface>>,
+    gpus: Option<Vec<GpuInfo>>,
+    // 部分分区采集失败时记录在这里，其余分区照常返回
+    warnings: Vec<AppError>,
+    // 采集完成的时间（Unix 时间戳，秒），前端据此显示数据时间并判断是否过期
+    collected_at: u64,
+}
+
+// get_system_info 可按需请求的分区，未请求的分区不采集并序列化为 null
+#[derive(Clone, Copy)]
+struct Sections {
+    os: bool,
+    cpu: bool,
+    memory: bool,
+    network: bool,
+    gpu: bool,
+}
+
+impl Sections {
+    fn all() -> Self {
+        Sections {
+            os: true,
+            cpu: true,
+            memory: true,
+            network: true,
+            gpu: true,
+        }
+    }
+
+    // 拼错的分区名会导致返回空数据，因此直接报错
+    fn from_names(names: Option<Vec<String>>) -> Result<Self, AppError> {
+        let Some(names) = names else {
+            return Ok(Sections::all());
+        };
+        let mut sections = Sections {
+            os: false,
+            cpu: false,
+            memory: false,
+            network: false,
+            gpu: false,
+        };
+        for name in &names {
+            let flag = match name.to_ascii_lowercase().as_str() {
+                "os" => &mut sections.os,
+                "cpu" => &mut sections.cpu,
+                "memory" => &mut sections.memory,
+                "network" => &mut sections.network,
+                "gpu" => &mut sections.gpu,
+                _ => {
+                    return Err(AppError::InvalidArgument(format!(
+                        "unknown section {:?}; expected os, cpu, memory, network or gpu",
+                        name
+                    )))
+                }
+            };
+            *flag = true;
+        }
+        Ok(sections)
+    }
+}
+
+// CPU 使用率采样窗口：默认 200ms，限制在 50~2000ms
+const DEFAULT_CPU_SAMPLE_MS: u64 = 200;
+const MIN_CPU_SAMPLE_MS: u64 = 50;
+const MAX_CPU_SAMPLE_MS: u64 = 2000;
+
+// 刷新共享 System 的 CPU 数据，返回使用率实际对应的采样窗口
+fn sample_cpu(state: &SysState, sample: Duration) -> Duration {
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    let since_last = sys.cpu_refreshed_at.map(|t| t.elapsed());
+    let max_baseline_age = Duration::from_millis(MAX_CPU_SAMPLE_MS);
+
+    match (since_last, sys.cpu_window) {
+        // 刚刚刷新过（例如实时推送正在运行），直接复用当前数据
+        (Some(elapsed), Some(window)) if elapsed < sample => window,
+        // 上一次刷新可作为基线，再刷新一次即可，无需等待
+        (Some(elapsed), _) if elapsed >= sample && elapsed <= max_baseline_age => {
+            sys.refresh_cpu();
+            elapsed
+        }
+        // 没有可用基线：刷新、等待、再刷新，等待期间不持有锁
+        _ => {
+            sys.refresh_cpu();
+            drop(sys);
+            std::thread::sleep(sample);
+            let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+            sys.refresh_cpu();
+            sys.cpu_window.unwrap_or(sample)
+        }
+    }
+}
+
+// 启动后不会变化的信息（CPU 缓存与频率范围、虚拟化环境、Windows 版本），需要读取 sysfs
+// 或启动外部进程，首次请求时探测一次后缓存
+#[derive(Default)]
+struct StaticInfoCache {
+    cpu_details: OnceLock<cpu::CpuDetails>,
+    virtualization: OnceLock<virt::VirtInfo>,
+    #[cfg(target_os = "windows")]
+    windows_version: OnceLock<Option<WindowsVersion>>,
 }
 
+// CPU 采样需要等待，内存与网络分区每次都要读取系统计数或启动外部进程，在阻塞线程中执行
 #[tauri::command]
-fn get_system_info() -> SystemInfo {
-    // 初始化带 CPU 信息的 System
-    let mut sys = System::new_all();
-    
-    // 首次刷新
-    sys.refresh_all();
-    // 等待一小段时间再刷新 CPU 以获得准确使用率
-    std::thread::sleep(std::time::Duration::from_millis(200));
-    sys.refresh_cpu_all();
-
-    // CPU 信息
-    let cpus = sys.cpus();
-    let cpu_brand = if !cpus.is_empty() {
-        cpus[0].brand().to_string()
-    } else {
-        "Unknown".to_string()
-    };
-    
-    let cpu_usage: f32 = if !cpus.is_empty() {
-        cpus.iter().map(|c| c.cpu_usage()).sum::<f32>() / cpus.len() as f32
-    } else {
-        0.0
+async fn get_system_info(
+    app: tauri::AppHandle,
+    sections: Option<Vec<String>>,
+    sample_ms: Option<u64>,
+    // 忽略显卡列表的缓存重新探测
+    force_refresh: Option<bool>,
+) -> Result<SystemInfo, AppError> {
+    let sections = Sections::from_names(sections)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_system_info(&app, sections, sample_ms, force_refresh.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| AppError::CommandFailed(format!("system info task: {}", e)))
+}
+
+fn collect_system_info(
+    app: &tauri::AppHandle,
+    sections: Sections,
+    sample_ms: Option<u64>,
+    force_refresh: bool,
+) -> SystemInfo {
+    let state = app.state::<SysState>();
+    let statics = app.state::<StaticInfoCache>();
+    let mut info = SystemInfo::default();
+
+    if sections.os {
+        info.os_name = System::name();
+        info.hostname = System::host_name();
+        info.kernel_version = System::kernel_version();
+        info.os_version = System::os_version();
+        #[cfg(target_os = "windows")]
+        if let Some(version) = statics.windows_version.get_or_init(windows_version_info) {
+            info.os_edition = version.edition.clone();
+            info.os_display_version = version.display_version.clone();
+            info.os_build = version.build.clone();
+        }
+        info.virtualization = Some(
+            statics
+                .virtualization
+                .get_or_init(virt::virtualization)
+                .clone(),
+        );
+        let (boot_time, uptime) = boot_time_and_uptime();
+        info.boot_time = Some(boot_time);
+        info.uptime = Some(uptime);
+    }
+
+    if sections.cpu {
+        let sample_ms = sample_ms
+            .unwrap_or(DEFAULT_CPU_SAMPLE_MS)
+            .clamp(MIN_CPU_SAMPLE_MS, MAX_CPU_SAMPLE_MS);
+        let window = sample_cpu(&state, Duration::from_millis(sample_ms));
+        info.effective_sample_ms = Some(window.as_millis() as u64);
+
+        let sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+        fill_cpu_info(&mut info, &sys.system);
+        // 负载只用核心数做归一化，核心列表为空时改用标准库的结果
+        let logical_cores = info
+            .cpu_logical_cores
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+        info.load_average = cpu::load_average(logical_cores);
+    }
+
+    if sections.cpu {
+        // 需要读取 sysfs 或调用外部命令，不持有共享状态的锁
+        info.cpu_details = info.cpu_logical_cores.map(|cores| {
+            statics
+                .cpu_details
+                .get_or_init(|| cpu::cpu_details(cores))
+                .clone()
+        });
+    }
+
+    if sections.memory {
+        {
+            let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+            let sys = &mut sys.system;
+            sys.refresh_memory();
+            info.total_memory = Some(sys.total_memory());
+            info.used_memory = Some(sys.used_memory());
+            info.available_memory = Some(sys.available_memory());
+            info.free_memory = Some(sys.free_memory());
+            info.memory_used_pct = (sys.total_memory() > 0)
+                .then(|| (sys.used_memory() as f64 / sys.total_memory() as f64 * 100.0) as f32);
+            info.total_swap = Some(sys.total_swap());
+            info.used_swap = Some(sys.used_swap());
+        }
+        // Windows 上需要调用 PowerShell，不持有共享状态的锁
+        info.memory_breakdown = Some(memory::memory_breakdown());
+        info.memory_pressure = memory::memory_pressure();
+    }
+
+    if sections.network {
+        info.network_ifaces = Some(collect_network_ifaces(&state));
+    }
+
+    if sections.gpu {
+        match get_gpu_info(&app.state::<GpuCache>(), force_refresh) {
+            Ok(gpus) => info.gpus = Some(gpus),
+            Err(e) => info.warnings.push(e),
+        }
+    }
+
+    info.collected_at = unix_now();
+    info
+}
+
+// 网络接口的地址、MAC、状态与流量，默认不含回环接口
+#[tauri::command]
+fn get_network_interfaces(
+    state: State<'_, SysState>,
+    include_loopback: Option<bool>,
+) -> Vec<NetworkIface> {
+    let mut ifaces = collect_network_ifaces(&state);
+    if !include_loopback.unwrap_or(false) {
+        ifaces.retain(|iface| !iface.is_loopback);
+    }
+    ifaces
+}
+
+fn collect_network_ifaces(state: &SysState) -> Vec<NetworkIface> {
+    // 链路信息在 Windows 上需要调用 PowerShell，在持有锁之前获取
+    let mut links = network::link_details();
+    // 网络接口，速率基于共享状态中上一次刷新的计数
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    let secs = sys
+        .refresh_networks()
+        .map(|elapsed| elapsed.as_secs_f64())
+        .filter(|&secs| secs > 0.0);
+    let prev_totals = std::mem::take(&mut sys.network_totals);
+    // 计数变小说明接口重启或计数器回绕，该次速率记为 0，并以新的计数作为基线
+    let rate = |current: u64, prev: u64, secs: f64| {
+        current
+            .checked_sub(prev)
+            .map_or(0, |delta| (delta as f64 / secs) as u64)
     };
-    
-    let cpu_cores: Vec<CpuCore> = cpus
+    let mut ifaces = sys
+        .networks
         .iter()
-        .map(|cpu| CpuCore {
-            name: cpu.name().to_string(),
-            usage: cpu.cpu_usage(),
-            frequency: cpu.frequency(),
+        .map(|(name, data)| {
+            let link = links.remove(name).unwrap_or_default();
+            let totals = (data.total_received(), data.total_transmitted());
+            let rates = secs.zip(prev_totals.get(name)).map(|(secs, &(rx, tx))| {
+                (rate(totals.0, rx, secs), rate(totals.1, tx, secs))
+            });
+            let addresses: Vec<IfaceAddress> = data
+                .ip_networks()
+                .iter()
+                .map(|net| IfaceAddress {
+                    address: net.addr.to_string(),
+                    prefix: net.prefix,
+                })
+                .collect();
+            let kind = network::iface_kind(name);
+            let is_loopback = kind == "loopback"
+                || (!data.ip_networks().is_empty()
+                    && data.ip_networks().iter().all(|net| net.addr.is_loopback()));
+            let mac = data.mac_address();
+            NetworkIface {
+                name: name.clone(),
+                received: totals.0,
+                transmitted: totals.1,
+                rx_bytes_per_sec: rates.map(|r| r.0),
+                tx_bytes_per_sec: rates.map(|r| r.1),
+                mac_address: (!mac.is_unspecified()).then(|| mac.to_string()),
+                addresses,
+                mtu: link.mtu,
+                is_up: link.is_up,
+                is_loopback,
+                speed_mbps: link.speed_mbps,
+                kind: if is_loopback { "loopback" } else { kind }.to_string(),
+            }
         })
+        .collect::<Vec<_>>();
+    sys.network_totals = ifaces
+        .iter()
+        .map(|iface| (iface.name.clone(), (iface.received, iface.transmitted)))
         .collect();
+    ifaces.sort_by(|a, b| a.name.cmp(&b.name));
+    ifaces
+}
 
-    // GPU 信息
-    let gpus = get_gpu_info();
+#[cfg(target_os = "windows")]
+#[derive(Clone)]
+struct WindowsVersion {
+    edition: Option<String>,
+    display_version: Option<String>,
+    build: Option<String>,
+}
+
+// 读取 HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion，打不开时返回 None，不影响其他字段
+#[cfg(target_os = "windows")]
+fn windows_version_info() -> Option<WindowsVersion> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
+        .ok()?;
+    let string = |name: &str| key.get_value::<String, _>(name).ok().filter(|v| !v.is_empty());
+
+    let build_number = string("CurrentBuild");
+    // Windows 11 的 ProductName 仍写着 "Windows 10"，按内部版本号（22000 起）修正
+    let is_windows_11 = build_number
+        .as_deref()
+        .and_then(|b| b.parse::<u32>().ok())
+        .is_some_and(|b| b >= 22000);
+    let edition = string("ProductName").map(|name| {
+        if is_windows_11 {
+            name.replacen("Windows 10", "Windows 11", 1)
+        } else {
+            name
+        }
+    });
+    // 20H2 之前的系统只有 ReleaseId（如 "1909"）
+    let display_version = string("DisplayVersion").or_else(|| string("ReleaseId"));
+    let build = build_number.map(|build| match key.get_value::<u32, _>("UBR") {
+        Ok(ubr) => format!("{}.{}", build, ubr),
+        Err(_) => build,
+    });
+
+    Some(WindowsVersion {
+        edition,
+        display_version,
+        build,
+    })
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// 以开机时间为准推算 uptime，避免分别读取时跨秒造成 1 秒误差
+fn boot_time_and_uptime() -> (u64, u64) {
+    let boot_time = System::boot_time();
+    (boot_time, unix_now().saturating_sub(boot_time))
+}
+
+#[derive(Serialize)]
+struct UptimeInfo {
+    uptime_secs: u64,
+    // 开机时间（Unix 时间戳，秒）
+    boot_time: u64,
+    // 如 "3d 4h 12m"
+    formatted: String,
+}
 
-    // 网络接口
-    let networks = Networks::new_with_refreshed_list();
-    let ifaces = networks
+// 由后端根据开机时间计算，前端不必依赖本机时钟换算
+#[tauri::command]
+fn get_uptime() -> UptimeInfo {
+    let (boot_time, uptime_secs) = boot_time_and_uptime();
+    UptimeInfo {
+        uptime_secs,
+        boot_time,
+        formatted: util::format_duration(uptime_secs, 3),
+    }
+}
+
+// 显卡列表的缓存有效期
+const GPU_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+// 显卡列表需要启动外部进程探测（数百毫秒到数秒），且很少变化：启动时探测一次后缓存，
+// 过期或强制刷新（如刚接入外接显卡）时重新探测。探测失败或结果为空（多为子进程启动失败 /
+// 超时）时不缓存，下次读取时重试
+#[derive(Default)]
+struct GpuCache {
+    gpus: Mutex<Option<(Instant, Vec<GpuInfo>)>>,
+}
+
+// 获取 GPU 信息：列表取自缓存，NVML 的实时数据（不启动子进程）每次读取时更新
+fn get_gpu_info(cache: &GpuCache, force_refresh: bool) -> Result<Vec<GpuInfo>, AppError> {
+    let fresh = {
+        let cached = cache.gpus.lock().unwrap_or_else(|e| e.into_inner());
+        match cached.as_ref() {
+            Some((probed_at, gpus)) if !force_refresh && probed_at.elapsed() < GPU_CACHE_TTL => {
+                Some(gpus.clone())
+            }
+            _ => None,
+        }
+    };
+    // 探测可能耗时数秒，不持有锁，以免阻塞其他读取缓存的命令
+    let mut gpus = match fresh {
+        Some(gpus) => gpus,
+        None => {
+            let probed = probe_gpu_info();
+            let mut cached = cache.gpus.lock().unwrap_or_else(|e| e.into_inner());
+            match probed {
+                Ok(gpus) if !gpus.is_empty() => {
+                    *cached = Some((Instant::now(), gpus.clone()));
+                    gpus
+                }
+                // 重新探测失败或超时（返回空列表）时沿用过期的结果
+                other => match cached.as_ref() {
+                    Some((_, gpus)) => gpus.clone(),
+                    None => other?,
+                },
+            }
+        }
+    };
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    apply_nvml_stats(&mut gpus);
+    for gpu in &mut gpus {
+        gpu.vram_display = if gpu.unified_memory {
+            Some("shared".to_string())
+        } else {
+            gpu.vram_bytes.map(format_vram)
+        };
+        gpu.update_vram_usage();
+    }
+    Ok(gpus)
+}
+
+// 只读取缓存中的显卡列表（过期的也算），不触发探测；从未探测成功时为 None
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn cached_gpu_list(cache: &GpuCache) -> Option<Vec<GpuInfo>> {
+    let cached = cache.gpus.lock().unwrap_or_else(|e| e.into_inner());
+    cached.as_ref().map(|(_, gpus)| gpus.clone())
+}
+
+fn probe_gpu_info() -> Result<Vec<GpuInfo>, AppError> {
+    let gpus = get_platform_gpu_info();
+    // 平台探测失败或没有找到显卡（缺少系统工具、沙盒环境等）时改用 wgpu 枚举
+    #[cfg(feature = "wgpu-fallback")]
+    let gpus = match gpus {
+        Ok(gpus) if !gpus.is_empty() => Ok(gpus),
+        other => {
+            let adapters = gpu::wgpu_gpus();
+            if adapters.is_empty() {
+                other
+            } else {
+                Ok(adapters)
+            }
+        }
+    };
+    let mut gpus = gpus?;
+    for gpu in &mut gpus {
+        if gpu.kind.is_empty() {
+            gpu.kind = "unknown".to_string();
+        }
+    }
+    if !gpus.iter().any(|gpu| gpu.is_primary) {
+        if let Some(first) = gpus.first_mut() {
+            first.is_primary = true;
+        }
+    }
+    Ok(gpus)
+}
+
+// 重新探测显卡（如插拔外接显卡、更新驱动之后）；探测要启动子进程，在阻塞线程中执行
+#[tauri::command]
+async fn refresh_gpu_info(app: tauri::AppHandle) -> Result<Vec<GpuInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(move || get_gpu_info(&app.state::<GpuCache>(), true))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("gpu refresh task: {}", e)))?
+}
+
+// 按 PCI 地址（Linux）或名称（Windows）把 NVML 的实时数据对应到显卡上；
+// 只有一块 NVIDIA 显卡时直接对应
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn apply_nvml_stats(gpus: &mut [GpuInfo]) {
+    if !gpus.iter().any(|gpu| gpu.vendor == "NVIDIA") {
+        return;
+    }
+    let stats = nvidia::nvml_gpus();
+    let single = stats.len() == 1 && gpus.iter().filter(|g| g.vendor == "NVIDIA").count() == 1;
+    for stat in stats {
+        let Some(gpu) = gpus
+            .iter_mut()
+            .filter(|g| g.vendor == "NVIDIA")
+            .find(|gpu| {
+                single
+                    || match (&gpu.pci_bus_id, &stat.bus_id) {
+                        (Some(a), Some(b)) => a == b,
+                        _ => stat.name.as_deref() == Some(gpu.name.as_str()),
+                    }
+            })
+        else {
+            continue;
+        };
+        gpu.utilization = stat.utilization;
+        gpu.temperature = stat.temperature;
+        gpu.vram_bytes = gpu.vram_bytes.or(stat.memory_total);
+        gpu.vram_used = stat.memory_used.or(gpu.vram_used);
+    }
+}
+
+// 整 GB 时以 GB 显示，否则以 MB 显示
+fn format_vram(bytes: u64) -> String {
+    const MB: u64 = 1 << 20;
+    const GB: u64 = 1 << 30;
+    if bytes >= GB && bytes % GB == 0 {
+        format!("{} GB", bytes / GB)
+    } else {
+        format!("{} MB", bytes / MB)
+    }
+}
+
+#[derive(Serialize)]
+struct TotalVram {
+    total_bytes: u64,
+    // 只有所有计入的显卡都报告已用显存时才有值，避免部分求和造成误导
+    used_bytes: Option<u64>,
+    adapter_count: usize,
+}
+
+// 缓存过期时需要重新探测显卡，在阻塞线程中执行
+#[tauri::command]
+async fn get_total_vram(app: tauri::AppHandle) -> Result<TotalVram, AppError> {
+    tauri::async_runtime::spawn_blocking(move || total_vram(&app.state::<GpuCache>()))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("total vram task: {}", e)))?
+}
+
+fn total_vram(gpu_cache: &GpuCache) -> Result<TotalVram, AppError> {
+    // 只统计报告了显存数值的显卡
+    let gpus: Vec<GpuInfo> = get_gpu_info(gpu_cache, false)?
+        .into_iter()
+        .filter(|gpu| gpu.vram_bytes.is_some())
+        .collect();
+
+    let total_bytes = gpus.iter().filter_map(|gpu| gpu.vram_bytes).sum();
+    let used_bytes = gpus
         .iter()
-        .map(|(name, data)| NetworkIface {
-            name: name.clone(),
-            received: data.received(),
-            transmitted: data.transmitted(),
-        })
-        .collect::<Vec<_>>();
+        .map(|gpu| gpu.vram_used)
+        .sum::<Option<u64>>()
+        .filter(|_| !gpus.is_empty());
+    Ok(TotalVram {
+        total_bytes,
+        used_bytes,
+        adapter_count: gpus.len(),
+    })
+}
+
+// 显卡探测命令的超时：system_profiler / PowerShell 偶尔会卡住，超时后结束子进程并按没有显卡处理
+const GPU_PROBE_TIMEOUT_SECS: u64 = 5;
+
+// 运行显卡探测命令并返回 stdout，超时返回 None
+fn gpu_probe_output(program: &str, args: &[&str]) -> Result<Option<Vec<u8>>, AppError> {
+    let mut cmd = std::process::Command::new(program);
+    cmd.args(args);
+    match util::output_with_timeout(&mut cmd, Duration::from_secs(GPU_PROBE_TIMEOUT_SECS)) {
+        Ok(out) => Ok(Some(out.stdout)),
+        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => Ok(None),
+        Err(e) => Err(AppError::CommandFailed(format!("{}: {}", program, e))),
+    }
+}
 
-    SystemInfo {
-        os_name: System::name(),
-        hostname: System::host_name(),
-        kernel_version: System::kernel_version(),
-        os_version: System::os_version(),
-        cpu_brand,
-        cpu_physical_cores: sys.physical_core_count(),
-        cpu_logical_cores: cpus.len(),
-        cpu_arch: std::env::consts::ARCH.to_string(),
-        cpu_usage,
-        cpu_cores,
-        total_memory: sys.total_memory(),
-        used_memory: sys.used_memory(),
-        total_swap: sys.total_swap(),
-        used_swap: sys.used_swap(),
-        uptime: System::uptime(),
-        network_ifaces: ifaces,
-        gpus,
-    }
-}
-
-// 获取 GPU 信息
-fn get_gpu_info() -> Vec<GpuInfo> {
+fn get_platform_gpu_info() -> Result<Vec<GpuInfo>, AppError> {
     #[cfg(target_os = "macos")]
     {
         get_gpu_info_macos()
@@ -125,376 +798,1730 @@ fn get_gpu_info() -> Vec<GpuInfo> {
     {
         get_gpu_info_windows()
     }
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(target_os = "linux")]
     {
-        vec![]
+        Ok(get_gpu_info_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(AppError::Unsupported("GPU detection is not implemented on this platform".to_string()))
     }
 }
 
-#[cfg(target_os = "macos")]
-fn get_gpu_info_macos() -> Vec<GpuInfo> {
-    use std::process::Command;
-    
-    let output = Command::new("system_profiler")
-        .args(["SPDisplaysDataType", "-json"])
-        .output();
-    
-    match output {
-        Ok(out) => {
-            if let Ok(json_str) = String::from_utf8(out.stdout) {
-                parse_macos_gpu_json(&json_str)
-            } else {
-                vec![]
-            }
-        }
-        Err(_) => vec![],
+#[cfg(target_os = "linux")]
+fn get_gpu_info_linux() -> Vec<GpuInfo> {
+    linux_gpus(
+        std::path::Path::new("/sys/class/drm"),
+        lspci_gpus(),
+        &nvidia_smi_memory(),
+    )
+}
+
+// 遍历 drm_dir 下的 card*，每块显卡一个目录（card0-DP-1 之类为显示接口，跳过），
+// 再以 lspci 的结果补齐没有加载驱动的显卡；nvidia_memory 以 PCI 地址为键
+#[cfg(target_os = "linux")]
+fn linux_gpus(
+    drm_dir: &std::path::Path,
+    lspci: Vec<LspciGpu>,
+    nvidia_memory: &std::collections::HashMap<String, (u64, u64)>,
+) -> Vec<GpuInfo> {
+    use std::fs;
+
+    let read = |path: &std::path::Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let mut gpus = Vec::new();
+    let mut seen_slots = std::collections::HashSet::new();
+
+    let mut cards: Vec<_> = fs::read_dir(drm_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .and_then(|n| n.strip_prefix("card"))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort_by_key(|e| e.file_name());
+
+    for card in cards {
+        let device = card.path().join("device");
+        // 没有 PCI 厂商 ID 的是虚拟设备（simpledrm 等）
+        let (Some(vendor_id), Some(device_id)) =
+            (read(&device.join("vendor")), read(&device.join("device")))
+        else {
+            continue;
+        };
+        let vendor_id = vendor_id.trim_start_matches("0x").to_ascii_uppercase();
+        let device_id = device_id.trim_start_matches("0x").to_ascii_uppercase();
+        // device 链接指向 PCI 地址，如 .../0000:01:00.0
+        let slot = fs::canonicalize(&device)
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+        let name = slot
+            .as_ref()
+            .and_then(|slot| lspci.iter().find(|g| &g.slot == slot))
+            .map(|g| g.name.clone())
+            .unwrap_or_else(|| format!("PCI device {}:{}", vendor_id, device_id));
+        let vendor = pci_vendor_name(&vendor_id).to_string();
+
+        // amdgpu 提供显存总量与已用量，NVIDIA 专有驱动没有对应文件，改用 nvidia-smi
+        let nvidia = slot.as_ref().and_then(|slot| nvidia_memory.get(slot));
+        let vram = read(&device.join("mem_info_vram_total"))
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|&v| v > 0)
+            .or_else(|| nvidia.map(|&(total, _)| total));
+        let vram_used = read(&device.join("mem_info_vram_used"))
+            .and_then(|v| v.parse::<u64>().ok())
+            .or_else(|| nvidia.map(|&(_, used)| used));
+        let kind = pci_gpu_kind(&vendor, vram).to_string();
+        seen_slots.extend(slot.clone());
+        // boot_vga 为 1 表示固件启动时使用的显卡，即主显卡
+        let is_primary = read(&device.join("boot_vga")).as_deref() == Some("1");
+        let driver_version = fs::read_link(device.join("driver"))
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .and_then(|driver| linux_driver_version(&driver));
+
+        gpus.push(GpuInfo {
+            name,
+            vendor,
+            vram_bytes: vram,
+            vram_used,
+            driver_version,
+            is_primary,
+            kind,
+            pci_bus_id: slot,
+            ..Default::default()
+        });
+    }
+
+    // 未加载驱动的显卡不会出现在 /sys/class/drm 下，以 lspci 的结果补齐
+    for gpu in lspci.into_iter().filter(|g| !seen_slots.contains(&g.slot)) {
+        let vendor = pci_vendor_name(&gpu.vendor_id).to_string();
+        let nvidia = nvidia_memory.get(&gpu.slot);
+        let vram = nvidia.map(|&(total, _)| total);
+        gpus.push(GpuInfo {
+            name: gpu.name,
+            kind: pci_gpu_kind(&vendor, vram).to_string(),
+            vendor,
+            vram_bytes: vram,
+            vram_used: nvidia.map(|&(_, used)| used),
+            pci_bus_id: Some(gpu.slot),
+            ..Default::default()
+        });
     }
+
+    gpus
+}
+
+// 树外模块（nvidia 等）在 /sys/module/<驱动>/version 中提供版本；
+// amdgpu、i915 等随内核发布的驱动没有独立版本号，以内核版本代替
+#[cfg(target_os = "linux")]
+fn linux_driver_version(driver: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/sys/module/{}/version", driver))
+        .ok()
+        .or_else(|| {
+            let stdout = gpu_probe_output("modinfo", &["-F", "version", driver]).ok()??;
+            Some(String::from_utf8_lossy(&stdout).into_owned())
+        })
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .or_else(|| {
+            System::kernel_version().map(|kernel| format!("{} (kernel {})", driver, kernel))
+        })
+}
+
+#[cfg(target_os = "linux")]
+struct LspciGpu {
+    slot: String,
+    // 大写十六进制，如 "10DE"
+    vendor_id: String,
+    name: String,
+}
+
+// 只列出显示类设备：0300 VGA 兼容控制器、0302 3D 控制器（无显示输出的计算卡 / 笔记本独显）
+// 未安装 pciutils 时为空
+#[cfg(target_os = "linux")]
+fn lspci_gpus() -> Vec<LspciGpu> {
+    ["::0300", "::0302"]
+        .iter()
+        .filter_map(|class| gpu_probe_output("lspci", &["-mm", "-nn", "-D", "-d", class]).ok()?)
+        .flat_map(|stdout| parse_lspci_gpus(&String::from_utf8_lossy(&stdout)))
+        .collect()
+}
+
+// lspci -mm -nn -D 输出形如：
+// 0000:01:00.0 "VGA compatible controller [0300]" "NVIDIA Corporation [10de]" "GA104 [GeForce RTX 3070] [2484]" -ra1 ...
+// 按引号切分后奇数位置为引号内的字段：类别、厂商、设备名，末尾方括号内为 ID
+#[cfg(target_os = "linux")]
+fn parse_lspci_gpus(output: &str) -> Vec<LspciGpu> {
+    let split_id = |field: &str| -> Option<(String, String)> {
+        let (name, id) = field.trim_end_matches(']').rsplit_once(" [")?;
+        Some((name.to_string(), id.to_ascii_uppercase()))
+    };
+    output
+        .lines()
+        .filter_map(|line| {
+            let (slot, rest) = line.split_once(' ')?;
+            let fields: Vec<&str> = rest.split('"').skip(1).step_by(2).collect();
+            let (_, vendor_id) = split_id(fields.get(1)?)?;
+            let (name, _) = split_id(fields.get(2)?)?;
+            Some(LspciGpu {
+                slot: slot.to_string(),
+                vendor_id,
+                name,
+            })
+        })
+        .collect()
+}
+
+// nvidia-smi 的显存单位为 MiB，未安装 NVIDIA 驱动时为空
+#[cfg(target_os = "linux")]
+fn nvidia_smi_memory() -> std::collections::HashMap<String, (u64, u64)> {
+    let Ok(Some(stdout)) = gpu_probe_output(
+        "nvidia-smi",
+        &[
+            "--query-gpu=pci.bus_id,memory.total,memory.used",
+            "--format=csv,noheader,nounits",
+        ],
+    ) else {
+        return std::collections::HashMap::new();
+    };
+    String::from_utf8_lossy(&stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let slot = nvidia::normalize_bus_id(fields.first()?)?;
+            let mib = |i: usize| fields.get(i)?.parse::<u64>().ok().map(|v| v << 20);
+            Some((slot, (mib(1)?, mib(2)?)))
+        })
+        .collect()
 }
 
 #[cfg(target_os = "macos")]
-fn parse_macos_gpu_json(json_str: &str) -> Vec<GpuInfo> {
+fn get_gpu_info_macos() -> Result<Vec<GpuInfo>, AppError> {
+    let Some(stdout) = gpu_probe_output("system_profiler", &["SPDisplaysDataType", "-json"])?
+    else {
+        return Ok(Vec::new());
+    };
+    let json_str = String::from_utf8(stdout)
+        .map_err(|e| AppError::Parse(format!("system_profiler output: {}", e)))?;
+    let mut gpus = parse_macos_gpu_json(&json_str)?;
+    // macOS 的显卡驱动随系统发布，没有单独的版本号，以系统版本与构建号代替
+    let build = gpu_probe_output("sysctl", &["-n", "kern.osversion"])
+        .ok()
+        .flatten()
+        .map(|stdout| String::from_utf8_lossy(&stdout).trim().to_string())
+        .filter(|build| !build.is_empty());
+    let driver_version = match (System::os_version(), build) {
+        (Some(version), Some(build)) => Some(format!("macOS {} ({})", version, build)),
+        (Some(version), None) => Some(format!("macOS {}", version)),
+        (None, build) => build,
+    };
+    for gpu in &mut gpus {
+        gpu.driver_version = driver_version.clone();
+    }
+    Ok(gpus)
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn parse_macos_gpu_json(json_str: &str) -> Result<Vec<GpuInfo>, AppError> {
     // 简单解析 macOS GPU JSON
     let mut gpus = vec![];
     
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_str) {
-        if let Some(displays) = json.get("SPDisplaysDataType").and_then(|v| v.as_array()) {
-            for display in displays {
-                let name = display.get("sppci_model")
-                    .or_else(|| display.get("_name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown GPU")
-                    .to_string();
-                
-                let vendor = display.get("sppci_vendor")
-                    .or_else(|| display.get("spdisplays_vendor"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown")
-                    .to_string();
-                
-                let vram = display.get("sppci_vram")
-                    .or_else(|| display.get("spdisplays_vram"))
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-                
-                gpus.push(GpuInfo { name, vendor, vram });
-            }
-        }
+    let json = serde_json::from_str::<serde_json::Value>(json_str)
+        .map_err(|e| AppError::Parse(format!("SPDisplaysDataType JSON: {}", e)))?;
+    let displays = json
+        .get("SPDisplaysDataType")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| AppError::Parse("missing SPDisplaysDataType array".to_string()))?;
+    for display in displays {
+        let name = display.get("sppci_model")
+            .or_else(|| display.get("_name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown GPU")
+            .to_string();
+        
+        // 新版系统的厂商为 "sppci_vendor_Apple" 形式
+        let vendor = display.get("sppci_vendor")
+            .or_else(|| display.get("spdisplays_vendor"))
+            .and_then(|v| v.as_str())
+            .map(|v| v.strip_prefix("sppci_vendor_").unwrap_or(v))
+            .unwrap_or("Unknown")
+            .to_string();
+        
+        let vram = display.get("sppci_vram")
+            .or_else(|| display.get("spdisplays_vram"))
+            .and_then(|v| v.as_str())
+            .and_then(parse_vram_bytes);
+        
+        // Apple Silicon 的 GPU 集成在 SoC 中，只报告 Metal 支持而没有独立显存；
+        // Intel 核显的 vram 为共享内存（"spdisplays_vram_shared"）
+        let kind = if vendor.contains("Apple")
+            || vendor.contains("Intel")
+            || display.get("spdisplays_vram_shared").is_some()
+        {
+            "integrated"
+        } else if vram.is_some() {
+            "discrete"
+        } else if display.get("spdisplays_mtlgpufamilysupport").is_some() {
+            "integrated"
+        } else {
+            "unknown"
+        };
+        let unified_memory = vendor.contains("Apple") && vram.is_none();
+        let gpu_cores = display
+            .get("sppci_cores")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.trim().parse().ok());
+        // "spdisplays_metal3" -> "Metal 3"
+        let metal_family = display
+            .get("spdisplays_mtlgpufamilysupport")
+            .or_else(|| display.get("spdisplays_metal"))
+            .and_then(|v| v.as_str())
+            .map(|v| match v.strip_prefix("spdisplays_metal") {
+                Some(version) if !version.is_empty() => format!("Metal {}", version),
+                _ => v.to_string(),
+            });
+        // 连接着主显示器的显卡，双显卡机型上即当前实际使用的显卡
+        let is_primary = display
+            .get("spdisplays_ndrvs")
+            .and_then(|v| v.as_array())
+            .is_some_and(|monitors| {
+                monitors.iter().any(|m| {
+                    m.get("spdisplays_main").and_then(|v| v.as_str()) == Some("spdisplays_yes")
+                })
+            });
+        
+        gpus.push(GpuInfo {
+            name,
+            vendor,
+            vram_bytes: vram,
+            unified_memory,
+            gpu_cores,
+            metal_family,
+            is_primary,
+            kind: kind.to_string(),
+            ..Default::default()
+        });
     }
     
-    gpus
+    Ok(gpus)
+}
+
+// 解析 system_profiler 中 "8 GB" / "1536 MB" 形式的显存字符串为字节数
+#[cfg(any(target_os = "macos", test))]
+fn parse_vram_bytes(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = s.split_at(unit_start);
+    let value: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        "TB" => 1 << 40,
+        _ => return None,
+    };
+    Some((value * multiplier as f64) as u64)
 }
 
 #[cfg(target_os = "windows")]
-fn get_gpu_info_windows() -> Vec<GpuInfo> {
-    use std::process::Command;
-    
-    // 使用 WMIC 获取 GPU 信息
-    let output = Command::new("wmic")
-        .args(["path", "win32_VideoController", "get", "Name,AdapterRAM,DriverVersion", "/format:csv"])
-        .output();
-    
-    match output {
-        Ok(out) => {
-            if let Ok(csv_str) = String::from_utf8(out.stdout) {
-                parse_windows_gpu_csv(&csv_str)
-            } else {
-                vec![]
-            }
-        }
-        Err(_) => {
-            // 备用方案：PowerShell
-            get_gpu_info_windows_powershell()
-        }
-    }
+fn get_gpu_info_windows() -> Result<Vec<GpuInfo>, AppError> {
+    // WMI 直接通过 COM 查询，无需启动 PowerShell（通常要 2~3 秒）；失败时才回退
+    get_gpu_info_windows_wmi().or_else(|_| get_gpu_info_windows_powershell())
 }
 
+// WMI 行到 GpuInfo 的映射不依赖 COM，测试时在所有平台上编译
+#[cfg(any(target_os = "windows", test))]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Win32_VideoController")]
+struct Win32VideoController {
+    #[serde(rename = "Name")]
+    name: Option<String>,
+    #[serde(rename = "AdapterCompatibility")]
+    adapter_compatibility: Option<String>,
+    #[serde(rename = "DriverVersion")]
+    driver_version: Option<String>,
+    #[serde(rename = "AdapterRAM")]
+    adapter_ram: Option<u32>,
+    #[serde(rename = "PNPDeviceID")]
+    pnp_device_id: Option<String>,
+    #[serde(rename = "CurrentHorizontalResolution")]
+    current_horizontal_resolution: Option<u32>,
+}
+
+// 在独立线程中初始化 COM：主线程已被 WebView2 初始化为 STA，
+// 在其上以 MTA 初始化会失败。WMI 服务无响应时同样按超时处理，查询线程留在后台自行结束
 #[cfg(target_os = "windows")]
-fn parse_windows_gpu_csv(csv_str: &str) -> Vec<GpuInfo> {
-    let mut gpus = vec![];
-    
-    for line in csv_str.lines().skip(1) {
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() >= 3 {
-            let adapter_ram = parts.get(1).unwrap_or(&"");
-            let name = parts.get(2).unwrap_or(&"Unknown").trim().to_string();
-            
-            if name.is_empty() || name == "Name" {
-                continue;
-            }
-            
-            let vram = if let Ok(bytes) = adapter_ram.parse::<u64>() {
-                if bytes > 0 {
-                    Some(format!("{} MB", bytes / 1024 / 1024))
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-            
-            gpus.push(GpuInfo {
-                name,
-                vendor: "Unknown".to_string(),
-                vram,
-            });
+fn get_gpu_info_windows_wmi() -> Result<Vec<GpuInfo>, AppError> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let rows = (|| -> Result<Vec<Win32VideoController>, String> {
+            let com = wmi::COMLibrary::new().map_err(|e| e.to_string())?;
+            let connection = wmi::WMIConnection::new(com).map_err(|e| e.to_string())?;
+            connection.query().map_err(|e| e.to_string())
+        })();
+        let _ = tx.send(rows);
+    });
+    let rows = match rx.recv_timeout(Duration::from_secs(GPU_PROBE_TIMEOUT_SECS)) {
+        Ok(rows) => rows.map_err(|e| AppError::CommandFailed(format!("wmi: {}", e)))?,
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+            return Err(AppError::CommandFailed(
+                "wmi: query thread panicked".to_string(),
+            ))
         }
+    };
+
+    let registry_vram = registry_vram_sizes();
+    let mut gpus: Vec<GpuInfo> = Vec::new();
+    for row in rows {
+        let primary_taken = gpus.iter().any(|gpu| gpu.is_primary);
+        gpus.push(gpu_from_wmi_row(row, &registry_vram, primary_taken));
+    }
+    Ok(gpus)
+}
+
+// primary_taken 为 true 时说明前面已有主显卡
+#[cfg(any(target_os = "windows", test))]
+fn gpu_from_wmi_row(
+    row: Win32VideoController,
+    registry_vram: &std::collections::HashMap<String, u64>,
+    primary_taken: bool,
+) -> GpuInfo {
+    let name = row
+        .name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Unknown GPU".to_string());
+    // PNPDeviceID 中的厂商 ID 最可靠，识别不了时再看 AdapterCompatibility
+    let vendor = match vendor_from_pci_id(row.pnp_device_id.as_deref().unwrap_or_default()) {
+        vendor if vendor != "Unknown" => vendor,
+        _ => vendor_from_adapter_compatibility(row.adapter_compatibility.as_deref()),
+    };
+    // AdapterRAM 是 32 位字段，显存 >= 4GB 时会被截断，优先使用注册表中的 64 位值
+    let vram = registry_vram
+        .get(&name)
+        .copied()
+        .filter(|&v| v > 0)
+        .or(row.adapter_ram.map(u64::from))
+        .filter(|&v| v > 0);
+    let kind = pci_gpu_kind(&vendor, vram).to_string();
+    // 只有正在输出画面的显卡才有当前分辨率，取第一块作为主显卡
+    let is_primary = !primary_taken && row.current_horizontal_resolution.is_some_and(|w| w > 0);
+
+    GpuInfo {
+        name,
+        vendor,
+        vram_bytes: vram,
+        driver_version: row
+            .driver_version
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()),
+        is_primary,
+        kind,
+        ..Default::default()
     }
-    
-    gpus
 }
 
+// "NVIDIA" / "Advanced Micro Devices, Inc." / "Intel Corporation"
+#[cfg(any(target_os = "windows", test))]
+fn vendor_from_adapter_compatibility(value: Option<&str>) -> String {
+    let value = value.unwrap_or_default().to_ascii_lowercase();
+    let vendor = if value.contains("nvidia") {
+        "NVIDIA"
+    } else if value.contains("advanced micro devices")
+        || value.contains("amd")
+        || value.contains("ati ")
+    {
+        "AMD"
+    } else if value.contains("intel") {
+        "Intel"
+    } else {
+        "Unknown"
+    };
+    vendor.to_string()
+}
+
+// 显卡驱动注册表项中的 HardwareInformation.qwMemorySize，按 DriverDesc（即显卡名称）索引。
+// 通常为 REG_QWORD，部分驱动写成 REG_BINARY，两者都是小端字节
 #[cfg(target_os = "windows")]
-fn get_gpu_info_windows_powershell() -> Vec<GpuInfo> {
-    use std::process::Command;
-    
-    let output = Command::new("powershell")
-        .args(["-Command", "Get-WmiObject Win32_VideoController | Select-Object Name, AdapterRAM | ConvertTo-Json"])
-        .output();
-    
-    match output {
-        Ok(out) => {
-            if let Ok(json_str) = String::from_utf8(out.stdout) {
-                parse_windows_gpu_powershell(&json_str)
-            } else {
-                vec![]
-            }
-        }
-        Err(_) => vec![],
+fn registry_vram_sizes() -> std::collections::HashMap<String, u64> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let Ok(class) = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(
+        r"SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}",
+    ) else {
+        return std::collections::HashMap::new();
+    };
+    class
+        .enum_keys()
+        .flatten()
+        .filter(|name| name.starts_with('0'))
+        .filter_map(|name| {
+            let key = class.open_subkey(&name).ok()?;
+            let desc: String = key.get_value("DriverDesc").ok()?;
+            let raw = key.get_raw_value("HardwareInformation.qwMemorySize").ok()?;
+            Some((desc, u64_from_le_bytes(&raw.bytes)?))
+        })
+        .collect()
+}
+
+// 1~8 个小端字节组成的无符号整数
+#[cfg(any(target_os = "windows", test))]
+fn u64_from_le_bytes(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return None;
+    }
+    Some(
+        bytes
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b)),
+    )
+}
+
+// 从 PNPDeviceID 中的 VEN_XXXX 识别显卡厂商
+#[cfg(any(target_os = "windows", test))]
+fn vendor_from_pci_id(pnp_device_id: &str) -> String {
+    let id = pnp_device_id.to_ascii_uppercase();
+    let vendor_id = id
+        .find("VEN_")
+        .and_then(|i| id.get(i + 4..i + 8))
+        .unwrap_or_default();
+    pci_vendor_name(vendor_id).to_string()
+}
+
+// PCI 厂商 ID（大写十六进制）对应的显卡厂商
+#[cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    feature = "wgpu-fallback",
+    test
+))]
+fn pci_vendor_name(vendor_id: &str) -> &'static str {
+    match vendor_id {
+        "10DE" => "NVIDIA",
+        "1002" | "1022" => "AMD",
+        "8086" => "Intel",
+        "106B" => "Apple",
+        _ => "Unknown",
     }
 }
 
+// 按厂商与专用显存推断显卡类型：Intel 为核显（Arc 独显除外），NVIDIA 为独显；
+// AMD APU 通常只在 BIOS 中划出 512MB 左右的专用显存
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
+fn pci_gpu_kind(vendor: &str, vram_bytes: Option<u64>) -> &'static str {
+    const DISCRETE_MIN_VRAM: u64 = 2 << 30;
+    let large_vram = vram_bytes.is_some_and(|b| b >= DISCRETE_MIN_VRAM);
+    match vendor {
+        "NVIDIA" => "discrete",
+        "Intel" if large_vram => "discrete",
+        "Intel" => "integrated",
+        "AMD" if large_vram => "discrete",
+        "AMD" if vram_bytes.is_some() => "integrated",
+        _ => "unknown",
+    }
+}
+
+// AdapterRAM 是 32 位字段，显存 >= 4GB 的显卡会被截断为 4GB（或更小的值），
+// 因此同时读取显卡驱动注册表项中的 HardwareInformation.qwMemorySize（64 位），
+// 按 DriverDesc 与显卡名称匹配。输出默认使用 OEM 代码页，非 ASCII 的显卡名称无法按 UTF-8 解析，
+// 先切换为 UTF-8
+#[cfg(target_os = "windows")]
+const WINDOWS_GPU_QUERY: &str = r#"
+[Console]::OutputEncoding = [Text.Encoding]::UTF8
+$reg = Get-ItemProperty 'HKLM:\SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}\0*' -ErrorAction SilentlyContinue
+Get-CimInstance Win32_VideoController | ForEach-Object {
+    $name = $_.Name
+    $entry = $reg | Where-Object { $_.DriverDesc -eq $name } | Select-Object -First 1
+    [PSCustomObject]@{
+        Name = $_.Name
+        AdapterRAM = $_.AdapterRAM
+        DriverVersion = $_.DriverVersion
+        PNPDeviceID = $_.PNPDeviceID
+        CurrentHorizontalResolution = $_.CurrentHorizontalResolution
+        QwMemorySize = $entry.'HardwareInformation.qwMemorySize'
+    }
+} | ConvertTo-Json
+"#;
+
 #[cfg(target_os = "windows")]
-fn parse_windows_gpu_powershell(json_str: &str) -> Vec<GpuInfo> {
+fn get_gpu_info_windows_powershell() -> Result<Vec<GpuInfo>, AppError> {
+    let Some(stdout) =
+        gpu_probe_output("powershell", &["-NoProfile", "-Command", WINDOWS_GPU_QUERY])?
+    else {
+        return Ok(Vec::new());
+    };
+    let json_str = String::from_utf8(stdout)
+        .map_err(|e| AppError::Parse(format!("powershell output: {}", e)))?;
+    parse_windows_gpu_powershell(&json_str)
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn parse_windows_gpu_powershell(json_str: &str) -> Result<Vec<GpuInfo>, AppError> {
     let mut gpus = vec![];
+    // 将 OutputEncoding 设为 UTF-8 后，Windows PowerShell 5.1 会在输出开头写入 BOM
+    let json_str = json_str.trim_start_matches('\u{feff}');
+    
+    // 没有显卡时 ConvertTo-Json 不输出任何内容
+    if json_str.trim().is_empty() {
+        return Ok(gpus);
+    }
     
     // 可能是单个对象或数组
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_str) {
-        let items = if json.is_array() {
-            json.as_array().map(|v| v.to_vec()).unwrap_or_default()
-        } else {
-            vec![json]
-        };
+    let json = serde_json::from_str::<serde_json::Value>(json_str)
+        .map_err(|e| AppError::Parse(format!("Win32_VideoController JSON: {}", e)))?;
+    let items = if json.is_array() {
+        json.as_array().map(|v| v.to_vec()).unwrap_or_default()
+    } else {
+        vec![json]
+    };
+    
+    for item in items {
+        let name = item.get("Name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown GPU")
+            .to_string();
         
-        for item in items {
-            let name = item.get("Name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown GPU")
-                .to_string();
-            
-            let vram = item.get("AdapterRAM")
+        // 注册表中的 64 位值优先，缺失时才使用可能被截断的 AdapterRAM
+        let vram = item.get("QwMemorySize")
+            .and_then(parse_qw_memory_size)
+            .or_else(|| item.get("AdapterRAM").and_then(|v| v.as_u64()))
+            .filter(|&v| v > 0);
+        let vendor = vendor_from_pci_id(
+            item.get("PNPDeviceID").and_then(|v| v.as_str()).unwrap_or_default(),
+        );
+        let driver_version = item.get("DriverVersion")
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        let kind = pci_gpu_kind(&vendor, vram).to_string();
+        // 只有正在输出画面的显卡才有当前分辨率，取第一块作为主显卡
+        let is_primary = !gpus.iter().any(|gpu: &GpuInfo| gpu.is_primary)
+            && item
+                .get("CurrentHorizontalResolution")
                 .and_then(|v| v.as_u64())
-                .filter(|&v| v > 0)
-                .map(|v| format!("{} MB", v / 1024 / 1024));
-            
-            gpus.push(GpuInfo {
-                name,
-                vendor: "Unknown".to_string(),
-                vram,
-            });
-        }
+                .is_some_and(|w| w > 0);
+        
+        gpus.push(GpuInfo {
+            name,
+            vendor,
+            vram_bytes: vram,
+            driver_version,
+            is_primary,
+            kind,
+            ..Default::default()
+        });
     }
     
-    gpus
+    Ok(gpus)
+}
+
+// qwMemorySize 通常为 REG_QWORD（数字），部分驱动写成 REG_BINARY（小端字节数组）
+#[cfg(any(target_os = "windows", test))]
+fn parse_qw_memory_size(value: &serde_json::Value) -> Option<u64> {
+    if let Some(bytes) = value.as_u64() {
+        return Some(bytes);
+    }
+    let bytes = value
+        .as_array()?
+        .iter()
+        .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect::<Option<Vec<u8>>>()?;
+    u64_from_le_bytes(&bytes)
 }
 
 #[derive(Serialize)]
 struct AudioDevices {
     inputs: Vec<String>,
     outputs: Vec<String>,
-    default_input: Option<String>,
-    default_output: Option<String>,
+    // 默认设备在去重后列表中的下标，没有默认设备时为 None
+    default_input_index: Option<usize>,
+    default_output_index: Option<usize>,
 }
 
 #[tauri::command]
-fn list_audio_devices() -> AudioDevices {
+fn list_audio_devices() -> Result<AudioDevices, AppError> {
     use cpal::traits::{DeviceTrait, HostTrait};
 
     let host = cpal::default_host();
 
-    let mut inputs = Vec::new();
-    if let Ok(mut devs) = host.input_devices() {
-        for d in devs.by_ref() {
-            inputs.push(d.name().unwrap_or_else(|_| "Unknown".to_string()));
-        }
-    }
-
-    let mut outputs = Vec::new();
-    if let Ok(mut devs) = host.output_devices() {
-        for d in devs.by_ref() {
-            outputs.push(d.name().unwrap_or_else(|_| "Unknown".to_string()));
-        }
-    }
+    // 枚举失败返回错误，主机上确实没有设备时返回空列表
+    let inputs: Vec<String> = host
+        .input_devices()
+        .map_err(|e| AppError::Audio(format!("input devices: {}", e)))?
+        .map(|d| d.name().unwrap_or_else(|_| "Unknown".to_string()))
+        .collect();
+    let default_input = host.default_input_device().and_then(|d| d.name().ok());
+    let (inputs, default_input_index) =
+        audio::dedup_names_with_default(inputs, default_input.as_deref());
 
-    let default_input = host
-        .default_input_device()
-        .and_then(|d| d.name().ok());
-    let default_output = host
-        .default_output_device()
-        .and_then(|d| d.name().ok());
+    let outputs: Vec<String> = host
+        .output_devices()
+        .map_err(|e| AppError::Audio(format!("output devices: {}", e)))?
+        .map(|d| d.name().unwrap_or_else(|_| "Unknown".to_string()))
+        .collect();
+    let default_output = host.default_output_device().and_then(|d| d.name().ok());
+    let (outputs, default_output_index) =
+        audio::dedup_names_with_default(outputs, default_output.as_deref());
 
-    AudioDevices {
+    Ok(AudioDevices {
         inputs,
         outputs,
-        default_input,
-        default_output,
-    }
+        default_input_index,
+        default_output_index,
+    })
 }
 
 #[tauri::command]
-fn list_cameras() -> Vec<String> {
+fn list_cameras() -> Result<Vec<String>, AppError> {
     // 摄像头枚举在跨平台上较复杂，此处返回系统默认信息
     // 可后续通过平台特定 API 扩展
     #[cfg(target_os = "macos")]
     {
         // macOS: 通过 system_profiler 获取摄像头
-        if let Ok(output) = std::process::Command::new("system_profiler")
+        let output = std::process::Command::new("system_profiler")
             .args(["SPCameraDataType", "-json"])
             .output()
-        {
-            if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
-                if let Some(cameras) = json.get("SPCameraDataType").and_then(|v| v.as_array()) {
-                    return cameras
-                        .iter()
-                        .filter_map(|c| c.get("_name").and_then(|n| n.as_str()).map(|s| s.to_string()))
-                        .collect();
-                }
-            }
-        }
-        Vec::new()
+            .map_err(|e| AppError::CommandFailed(format!("system_profiler: {}", e)))?;
+        let json = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+            .map_err(|e| AppError::Parse(format!("SPCameraDataType JSON: {}", e)))?;
+        // 没有摄像头时 system_profiler 不输出 SPCameraDataType 数组
+        let cameras = json
+            .get("SPCameraDataType")
+            .and_then(|v| v.as_array())
+            .map(|cameras| {
+                cameras
+                    .iter()
+                    .filter_map(|c| c.get("_name").and_then(|n| n.as_str()).map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(cameras)
     }
     #[cfg(target_os = "windows")]
     {
-        // Windows: 简单返回提示，可通过 WMI 扩展
-        vec!["Windows 摄像头枚举待扩展".to_string()]
+        Ok(camera::windows_camera_names())
     }
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        Vec::new()
+        Err(AppError::Unsupported("camera enumeration is not implemented on this platform".to_string()))
     }
 }
 
 #[derive(Serialize, Default)]
 struct NetTestResult {
+    // IPv4 地址
     external_ip: Option<String>,
+    // 没有 IPv6 连接时为 None
+    external_ipv6: Option<String>,
+    // 单独测量的 DNS 解析耗时，用于区分 DNS 慢与链路慢
+    dns_lookup_ms: Option<u128>,
     http_latency_ms: Option<u128>,
     download_mbps: Option<f64>,
     upload_mbps: Option<f64>,
     error: Option<String>,
 }
 
+// 默认测速地址（国内可访问的服务）
+const DEFAULT_IP_URLS: [&str; 3] = [
+    "https://myip.ipip.net/json",
+    "https://ip.useragentinfo.com/json",
+    "https://whois.pconline.com.cn/ipJson.jsp?json=true",
+];
+// 只有 AAAA 记录的地址，返回纯文本的 IPv6 地址
+const DEFAULT_IPV6_URLS: [&str; 2] = ["https://6.ipw.cn", "https://api6.ipify.org"];
+const DEFAULT_LATENCY_URL: &str = "https://www.baidu.com/img/flexible/logo/pc/peak-result.png";
+const DEFAULT_DOWNLOAD_URLS: [&str; 2] = [
+    "https://dldir1.qq.com/qqfile/qq/PCQQ9.7.17/QQ9.7.17.29225.exe", // 腾讯
+    "https://npm.taobao.org/mirrors/node/v18.0.0/node-v18.0.0.tar.gz", // 淘宝镜像
+];
+// 国内缺少公开上传测速端点，使用 httpbin 的 POST 接口测量
+const DEFAULT_UPLOAD_URL: &str = "https://httpbin.org/post";
+
+// 网络测试的自定义地址，未提供的项使用默认值
+#[derive(Deserialize, Default)]
+struct NetTestConfig {
+    ip_urls: Option<Vec<String>>,
+    ipv6_urls: Option<Vec<String>>,
+    // 用于测量 DNS 解析耗时的主机名，默认为延迟测试地址的主机
+    dns_host: Option<String>,
+    latency_url: Option<String>,
+    download_urls: Option<Vec<String>>,
+    upload_url: Option<String>,
+}
+
+// 合并默认值并校验地址，只接受 http / https
+struct NetTestEndpoints {
+    ip_urls: Vec<reqwest::Url>,
+    ipv6_urls: Vec<reqwest::Url>,
+    dns_host: String,
+    latency_url: reqwest::Url,
+    download_urls: Vec<reqwest::Url>,
+    upload_url: reqwest::Url,
+}
+
+impl NetTestConfig {
+    fn into_endpoints(self) -> Result<NetTestEndpoints, AppError> {
+        let parse_list = |urls: Option<Vec<String>>, defaults: &[&str]| {
+            match urls.filter(|urls| !urls.is_empty()) {
+                Some(urls) => urls.iter().map(|u| parse_test_url(u)).collect::<Result<Vec<_>, _>>(),
+                None => defaults.iter().map(|u| parse_test_url(u)).collect(),
+            }
+        };
+
+        let latency_url =
+            parse_test_url(self.latency_url.as_deref().unwrap_or(DEFAULT_LATENCY_URL))?;
+        let dns_host = match self.dns_host.map(|h| h.trim().to_string()) {
+            Some(host) if !host.is_empty() => host,
+            _ => latency_url.host_str().unwrap_or_default().to_string(),
+        };
+        Ok(NetTestEndpoints {
+            ip_urls: parse_list(self.ip_urls, &DEFAULT_IP_URLS)?,
+            ipv6_urls: parse_list(self.ipv6_urls, &DEFAULT_IPV6_URLS)?,
+            dns_host,
+            latency_url,
+            download_urls: parse_list(self.download_urls, &DEFAULT_DOWNLOAD_URLS)?,
+            upload_url: parse_test_url(self.upload_url.as_deref().unwrap_or(DEFAULT_UPLOAD_URL))?,
+        })
+    }
+}
+
+fn parse_test_url(url: &str) -> Result<reqwest::Url, AppError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| AppError::Network(format!("invalid url {:?}: {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::Network(format!("unsupported url scheme: {:?}", url)));
+    }
+    Ok(parsed)
+}
+
+// 网络测试进度事件，各阶段占用的百分比区间：
+// ip 0~25，dns 25~30，latency 30~40，download 40~75，upload 75~100
+#[derive(Serialize, Clone)]
+struct NetTestProgress {
+    phase: &'static str,
+    percent: u8,
+    // 阶段结束时表示是否成功，进行中为 None
+    ok: Option<bool>,
+}
+
+fn emit_progress(app: &tauri::AppHandle, phase: &'static str, percent: u8, ok: Option<bool>) {
+    let _ = app.emit("nettest-progress", NetTestProgress { phase, percent, ok });
+}
+
+// 下载测速请求的字节范围（约 3MB）
+const DOWNLOAD_RANGE_BYTES: u64 = 3_000_001;
+
 #[tauri::command]
-async fn run_network_test() -> NetTestResult {
-    let client = match reqwest::Client::builder()
+async fn run_network_test(
+    app: tauri::AppHandle,
+    net_test: State<'_, state::NetTestState>,
+    config: Option<NetTestConfig>,
+) -> Result<NetTestResult, AppError> {
+    let endpoints = config.unwrap_or_default().into_endpoints()?;
+    let client = http_client(Duration::from_secs(30), None)?;
+
+    // 新的测试开始时清除上一次的取消请求
+    let cancelled = &net_test.cancelled;
+    cancelled.store(false, Ordering::SeqCst);
+
+    let mut result = NetTestResult::default();
+    if run_test_phases(&app, &client, endpoints, cancelled, &mut result).await.is_none() {
+        // 被取消时返回已完成阶段的结果
+        emit_progress(&app, "cancelled", 100, Some(false));
+        result.error = Some("cancelled".to_string());
+        return Ok(result);
+    }
+
+    // 部分测试项失败时记录在 error 中，全部失败才返回错误
+    let failures: Vec<&str> = [
+        (result.external_ip.is_none(), "external ip lookup failed"),
+        (result.dns_lookup_ms.is_none(), "dns lookup failed"),
+        (result.http_latency_ms.is_none(), "latency test failed"),
+        (result.download_mbps.is_none(), "download test failed"),
+        (result.upload_mbps.is_none(), "upload test failed"),
+    ]
+    .into_iter()
+    .filter_map(|(failed, msg)| failed.then_some(msg))
+    .collect();
+
+    if failures.len() == 5 {
+        return Err(AppError::Network(failures.join("; ")));
+    }
+    if !failures.is_empty() {
+        result.error = Some(failures.join("; "));
+    }
+
+    Ok(result)
+}
+
+// local_address 为 0.0.0.0 / :: 时只通过 IPv4 / IPv6 建立连接
+fn http_client(
+    timeout: Duration,
+    local_address: Option<std::net::IpAddr>,
+) -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
         .user_agent("hisen-desk/0.1")
-        .timeout(std::time::Duration::from_secs(30))
+        .timeout(timeout)
+        .local_address(local_address)
         .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            return NetTestResult {
-                error: Some(format!("client error: {}", e)),
-                ..Default::default()
-            }
+        .map_err(|e| AppError::Network(format!("client error: {}", e)))
+}
+
+// 只查询外网 IP，不运行完整的网络测试；ip_urls 为空时使用默认地址
+#[tauri::command]
+async fn get_public_ip(ip_urls: Option<Vec<String>>) -> Result<String, AppError> {
+    let endpoints = NetTestConfig {
+        ip_urls,
+        ..Default::default()
+    }
+    .into_endpoints()?;
+    let client = http_client(Duration::from_secs(10), None)?;
+    resolve_public_ip(&client, &endpoints.ip_urls)
+        .await
+        .ok_or_else(|| AppError::Network("external ip lookup failed".to_string()))
+}
+
+// 依次尝试各地址，返回第一个成功解析的 IP
+async fn resolve_public_ip(client: &reqwest::Client, urls: &[reqwest::Url]) -> Option<String> {
+    for url in urls {
+        let Ok(resp) = client.get(url.clone()).send().await else {
+            continue;
+        };
+        let Ok(text) = resp.text().await else {
+            continue;
+        };
+        if let Some(ip) = parse_ip_response(url, &text) {
+            return Some(ip);
+        }
+    }
+    None
+}
+
+// 按服务解析返回内容：
+// - ipip.net：{"ret": "ok", "data": {"ip": "x.x.x.x", "location": [...]}}
+// - pconline：JSONP 形式的 if(window.IPCallBack) {IPCallBack({"ip": "x.x.x.x", ...});}
+//   （带 json=true 时没有外层包装）
+// - useragentinfo 及自定义地址：{"ip": "x.x.x.x", ...}，或只返回 IP 的纯文本（如 ipify）
+// 取到的值必须是合法的 IP 地址
+fn parse_ip_response(url: &reqwest::Url, body: &str) -> Option<String> {
+    let json = |text: &str| serde_json::from_str::<serde_json::Value>(text).ok();
+    let ip = match url.host_str() {
+        Some("myip.ipip.net") => json(body)?.pointer("/data/ip")?.as_str()?.to_string(),
+        Some("whois.pconline.com.cn") => {
+            // 外层的 if 语句也带花括号，优先按 "({" 和 "})" 定位参数
+            let start = body
+                .find("({")
+                .map_or_else(|| body.find('{'), |i| Some(i + 1))?;
+            let end = body.rfind("})").or_else(|| body.rfind('}'))?;
+            json(body.get(start..=end)?)?
+                .get("ip")?
+                .as_str()?
+                .to_string()
         }
+        _ => match json(body) {
+            Some(v) => v
+                .get("ip")
+                .or_else(|| v.pointer("/data/ip"))?
+                .as_str()?
+                .to_string(),
+            None => body.trim().to_string(),
+        },
     };
+    let ip = ip.trim();
+    ip.parse::<std::net::IpAddr>().ok().map(|_| ip.to_string())
+}
 
-    let mut result = NetTestResult::default();
+#[tauri::command]
+fn cancel_network_test(net_test: State<'_, state::NetTestState>) {
+    net_test.cancelled.store(true, Ordering::SeqCst);
+}
 
-    // External IP (使用国内可访问的服务)
-    // 尝试多个备用地址
-    let ip_urls = [
-        "https://myip.ipip.net/json",
-        "https://ip.useragentinfo.com/json",
-        "https://whois.pconline.com.cn/ipJson.jsp?json=true",
-    ];
-    
-    for url in ip_urls {
-        if let Ok(resp) = client.get(url).send().await {
-            if let Ok(text) = resp.text().await {
-                // 尝试解析 JSON
-                if let Ok(v) = serde_json::from_str::<serde_json::Value>(&text) {
-                    // ipip.net 格式: {"ip": "x.x.x.x", ...}
-                    if let Some(ip) = v.get("ip").and_then(|x| x.as_str()) {
-                        result.external_ip = Some(ip.to_string());
-                        break;
-                    }
-                    // pconline 格式: {"ip": "x.x.x.x", ...}
-                    if let Some(ip) = v.get("ip").and_then(|x| x.as_str()) {
-                        result.external_ip = Some(ip.to_string());
-                        break;
-                    }
-                }
+// 等待 future 完成，期间定期检查取消标志；取消时直接丢弃 future，进行中的请求随之中断
+async fn until_cancelled<F: std::future::Future>(cancelled: &AtomicBool, fut: F) -> Option<F::Output> {
+    tokio::select! {
+        out = fut => Some(out),
+        _ = async {
+            while !cancelled.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(100)).await;
             }
-        }
+        } => None,
     }
+}
 
-    // HTTP latency (使用国内网站测试延迟)
+// 依次执行各测试阶段，结果写入 result；被取消时返回 None
+async fn run_test_phases(
+    app: &tauri::AppHandle,
+    client: &reqwest::Client,
+    endpoints: NetTestEndpoints,
+    cancelled: &AtomicBool,
+    result: &mut NetTestResult,
+) -> Option<()> {
+    // External IP，IPv4 与 IPv6 分别绑定对应协议的本地地址同时查询，各自依次尝试多个备用地址
+    emit_progress(app, "ip", 0, None);
+    let lookup = |local: std::net::IpAddr, urls: &[reqwest::Url]| {
+        let client = http_client(Duration::from_secs(10), Some(local)).ok();
+        let urls = urls.to_vec();
+        async move {
+            let ip = resolve_public_ip(&client?, &urls).await?;
+            let is_v6 = ip.parse::<std::net::IpAddr>().ok()?.is_ipv6();
+            (is_v6 == local.is_ipv6()).then_some(ip)
+        }
+    };
+    let v4 = lookup(std::net::Ipv4Addr::UNSPECIFIED.into(), &endpoints.ip_urls);
+    let v6 = lookup(std::net::Ipv6Addr::UNSPECIFIED.into(), &endpoints.ipv6_urls);
+    (result.external_ip, result.external_ipv6) =
+        until_cancelled(cancelled, async { tokio::join!(v4, v6) }).await?;
+
+    emit_progress(app, "ip", 25, Some(result.external_ip.is_some()));
+
+    // DNS 解析耗时，只计算解析本身；解析不到地址也视为失败
     let start = Instant::now();
-    let latency = client
-        .get("https://www.baidu.com/img/flexible/logo/pc/peak-result.png")
-        .send()
-        .await
+    let lookup = tokio::net::lookup_host((endpoints.dns_host.as_str(), 0));
+    let dns = until_cancelled(cancelled, lookup)
+        .await?
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .map(|_| start.elapsed().as_millis());
+    result.dns_lookup_ms = dns;
+    emit_progress(app, "dns", 30, Some(dns.is_some()));
+
+    // HTTP latency
+    let start = Instant::now();
+    let latency = until_cancelled(cancelled, client.get(endpoints.latency_url).send())
+        .await?
         .map(|_| start.elapsed().as_millis())
         .ok();
     result.http_latency_ms = latency;
+    emit_progress(app, "latency", 40, Some(latency.is_some()));
 
-    // Approx download speed (使用国内CDN测速，约3MB)
-    // 使用阿里云/腾讯云等国内CDN的测试文件
-    let download_urls = [
-        "https://dldir1.qq.com/qqfile/qq/PCQQ9.7.17/QQ9.7.17.29225.exe", // 腾讯
-        "https://npm.taobao.org/mirrors/node/v18.0.0/node-v18.0.0.tar.gz", // 淘宝镜像
-    ];
-    
+    // Approx download speed (约3MB)，按块读取以便报告进度，每块之后都可以被取消
     let start_dl = Instant::now();
-    for url in download_urls {
-        // 只下载前3MB来测速
-        if let Ok(resp) = client
+    for url in endpoints.download_urls {
+        let request = client
             .get(url)
-            .header("Range", "bytes=0-3000000")
-            .send()
-            .await 
-        {
-            if let Ok(bytes) = resp.bytes().await {
-                if bytes.len() > 100000 { // 确保下载了足够数据
-                    let size = bytes.len() as f64;
-                    let secs = (start_dl.elapsed().as_millis().max(1) as f64) / 1000.0;
-                    let mbps = (size * 8.0) / 1_000_000.0 / secs;
-                    result.download_mbps = Some(mbps);
-                    break;
-                }
+            .header("Range", format!("bytes=0-{}", DOWNLOAD_RANGE_BYTES - 1))
+            .send();
+        let Ok(mut resp) = until_cancelled(cancelled, request).await? else {
+            continue;
+        };
+        let mut size: u64 = 0;
+        let mut last_percent = 40;
+        while let Ok(Some(chunk)) = until_cancelled(cancelled, resp.chunk()).await? {
+            size += chunk.len() as u64;
+            let percent = 40 + (size.min(DOWNLOAD_RANGE_BYTES) * 35 / DOWNLOAD_RANGE_BYTES) as u8;
+            if percent != last_percent {
+                emit_progress(app, "download", percent, None);
+                last_percent = percent;
             }
         }
+        if size > 100_000 { // 确保下载了足够数据
+            let secs = (start_dl.elapsed().as_millis().max(1) as f64) / 1000.0;
+            let mbps = (size as f64 * 8.0) / 1_000_000.0 / secs;
+            result.download_mbps = Some(mbps);
+            break;
+        }
     }
+    emit_progress(app, "download", 75, Some(result.download_mbps.is_some()));
 
-    // Approx upload speed (使用httpbin.org的国内镜像或备用方案)
-    // 由于国内缺少公开上传测速端点，这里使用POST请求测量
-    let upload_data = vec![0u8; 500_000]; // 500KB
-    let start_ul = Instant::now();
-    
-    // 尝试使用 httpbin 测试上传
-    if let Ok(_resp) = client
-        .post("https://httpbin.org/post")
-        .body(upload_data.clone())
+    // Approx upload speed
+    let progress_app = app.clone();
+    let upload = measure_upload(client, endpoints.upload_url, move |percent| {
+        emit_progress(&progress_app, "upload", percent, None)
+    });
+    result.upload_mbps = until_cancelled(cancelled, upload).await?;
+    emit_progress(app, "upload", 100, Some(result.upload_mbps.is_some()));
+    Some(())
+}
+
+// 上传测速的数据量与分块大小
+const UPLOAD_SIZE: usize = 5 * 1024 * 1024;
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+// 只计算请求体传输的时间：先用空请求建立连接（DNS + TLS 握手），上传复用连接池中的连接；
+// 计时从请求体第一次被读取（连接已就绪）开始，到服务端返回响应（已收完请求体）为止。
+// on_progress 收到 75 ~ 99 的总进度百分比
+async fn measure_upload(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    on_progress: impl Fn(u8) + Send + Sync + 'static,
+) -> Option<f64> {
+    use futures_util::stream;
+    use std::sync::Arc;
+
+    client
+        .post(url.clone())
+        .body(Vec::new())
         .send()
         .await
-    {
-        let size = upload_data.len() as f64;
-        let secs = (start_ul.elapsed().as_millis().max(1) as f64) / 1000.0;
-        let mbps = (size * 8.0) / 1_000_000.0 / secs;
-        result.upload_mbps = Some(mbps);
+        .ok()?;
+
+    let started = Arc::new(OnceLock::new());
+    let marker = started.clone();
+    let chunk_count = UPLOAD_SIZE.div_ceil(UPLOAD_CHUNK_SIZE);
+    let chunks = (0..chunk_count).map(move |i| {
+        marker.get_or_init(Instant::now);
+        // 请求体被读取的进度即上传进度
+        let percent = 75 + (i * 25 / chunk_count) as u8;
+        if i == 0 || percent != 75 + ((i - 1) * 25 / chunk_count) as u8 {
+            on_progress(percent);
+        }
+        let len = UPLOAD_CHUNK_SIZE.min(UPLOAD_SIZE - i * UPLOAD_CHUNK_SIZE);
+        Ok::<_, std::io::Error>(vec![0u8; len])
+    });
+
+    let resp = client
+        .post(url)
+        .header(reqwest::header::CONTENT_LENGTH, UPLOAD_SIZE)
+        // 5MB 在慢速上行链路上可能超过客户端默认的 30 秒
+        .timeout(Duration::from_secs(90))
+        .body(reqwest::Body::wrap_stream(stream::iter(chunks)))
+        .send()
+        .await
+        .ok()?;
+    let finished = Instant::now();
+    if !resp.status().is_success() {
+        return None;
     }
 
-    result
+    let secs = finished
+        .duration_since(*started.get()?)
+        .as_secs_f64()
+        .max(0.001);
+    Some((UPLOAD_SIZE as f64 * 8.0) / 1_000_000.0 / secs)
 }
 
 fn main() {
     tauri::Builder::default()
+        .manage(state::SysState::default())
+        .manage(metrics::MetricsStream::default())
+        .manage(state::NetTestState::default())
+        .manage(bench::StabilityTest::default())
+        .manage(metrics::MetricsHistory::default())
+        .manage(disk::DiskUsageHistory::default())
+        .manage(audio::MicMonitor::default())
+        .manage(audio::AudioWatch::default())
+        .manage(GpuCache::default())
+        .manage(StaticInfoCache::default())
+        .setup(|app| {
+            // 后台预先探测显卡，首次打开系统信息时不必等待
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                let _ = get_gpu_info(&handle.state::<GpuCache>(), false);
+            });
+            metrics::start_history_sampler(app.handle().clone());
+            disk::start_usage_sampler(app.handle().clone());
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // 窗口关闭后停止推送，避免后台任务继续运行
+            if let tauri::WindowEvent::Destroyed = event {
+                window.state::<metrics::MetricsStream>().stop();
+                window.state::<audio::MicMonitor>().stop();
+                window.state::<audio::AudioWatch>().stop();
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_system_info,
+            get_uptime,
+            get_network_interfaces,
+            power::get_battery,
+            power::get_battery_time_formatted,
             list_audio_devices,
+            audio::list_audio_devices_detailed,
+            audio::start_mic_monitor,
+            audio::stop_mic_monitor,
+            audio::test_microphone,
+            audio::start_audio_watch,
+            audio::stop_audio_watch,
             list_cameras,
-            run_network_test
+            camera::list_cameras_detailed,
+            run_network_test,
+            cancel_network_test,
+            get_public_ip,
+            get_total_vram,
+            refresh_gpu_info,
+            gpu::get_gpu_metrics,
+            gpu::get_gpu_processes,
+            process::list_processes,
+            process::get_processes,
+            process::get_process_detail,
+            process::get_self_usage,
+            process::kill_process,
+            network::get_vpn_status,
+            network::ping_host,
+            network::get_routes,
+            network::get_name_resolution_config,
+            disk::list_disks,
+            disk::get_disk_io,
+            disk::get_nvme_temperatures,
+            disk::get_disk_usage_trend,
+            displays::get_displays,
+            displays::list_displays,
+            screenshot::capture_screenshot,
+            hardware::get_hardware_info,
+            memory::get_fs_cache_stats,
+            metrics::start_metrics_stream,
+            metrics::stop_metrics_stream,
+            metrics::get_cpu_history,
+            metrics::get_metrics_history,
+            metrics::set_metrics_history_config,
+            cpu::get_cstate_residency,
+            cpu::get_load_average,
+            cpu::get_cpu_security_features,
+            sensors::get_temperatures,
+            sensors::get_sensors,
+            sensors::get_thermal_status,
+            sensors::get_fans,
+            power::get_throttle_status,
+            power::set_app_nap_disabled,
+            runtimes::list_runtimes,
+            desktop::get_do_not_disturb,
+            desktop::set_do_not_disturb,
+            desktop::get_foreground_app,
+            bench::run_stability_test,
+            bench::cancel_stability_test,
+            bench::benchmark_memory_latency,
+            bench::run_cpu_benchmark,
+            users::get_users,
+            sockets::get_process_socket_summary,
+            locale::get_timezone_info,
+            locale::get_locale_info,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedCpus(Vec<CpuReading>);
+
+    impl CpuSource for FixedCpus {
+        fn readings(&self) -> Vec<CpuReading> {
+            self.0
+                .iter()
+                .map(|cpu| CpuReading {
+                    name: cpu.name.clone(),
+                    brand: cpu.brand.clone(),
+                    usage: cpu.usage,
+                    frequency: cpu.frequency,
+                })
+                .collect()
+        }
+
+        fn physical_cores(&self) -> Option<usize> {
+            None
+        }
+    }
+
+    fn reading(name: &str, usage: f32) -> CpuReading {
+        CpuReading {
+            name: name.to_string(),
+            brand: "Test CPU".to_string(),
+            usage,
+            frequency: 2400,
+        }
+    }
+
+    #[test]
+    fn empty_cpu_list_leaves_core_fields_unset() {
+        let mut info = SystemInfo::default();
+        fill_cpu_info(&mut info, &FixedCpus(Vec::new()));
+
+        assert_eq!(info.cpu_brand.as_deref(), Some("Unknown"));
+        assert_eq!(info.cpu_usage, Some(0.0));
+        assert!(info.cpu_logical_cores.is_none());
+        assert!(info.cpu_cores.is_none());
+    }
+
+    #[test]
+    fn logical_cores_match_core_list() {
+        let mut info = SystemInfo::default();
+        fill_cpu_info(
+            &mut info,
+            &FixedCpus(vec![reading("cpu0", 20.0), reading("cpu1", 40.0)]),
+        );
+
+        assert_eq!(info.cpu_brand.as_deref(), Some("Test CPU"));
+        assert_eq!(info.cpu_usage, Some(30.0));
+        assert_eq!(info.cpu_logical_cores, Some(2));
+        assert_eq!(info.cpu_cores.map(|cores| cores.len()), Some(2));
+    }
+
+    // 读完请求体后返回 200 的本地 HTTP 服务，同一连接上可以有多个请求
+    fn spawn_sink_server() -> reqwest::Url {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                std::thread::spawn(move || loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((key, value)) = line.split_once(':') {
+                            if key.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    if reader.read_exact(&mut body).is_err() {
+                        return;
+                    }
+                    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+                });
+            }
+        });
+        format!("http://{}/post", addr).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn upload_mbps_is_finite_and_non_negative() {
+        let url = spawn_sink_server();
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let progress = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = progress.clone();
+
+        let mbps = measure_upload(&client, url, move |percent| {
+            sink.lock().unwrap().push(percent)
+        })
+        .await
+        .expect("upload to the local server succeeds");
+        assert!(mbps.is_finite() && mbps >= 0.0, "{}", mbps);
+
+        let progress = progress.lock().unwrap();
+        assert_eq!(progress.first(), Some(&75));
+        assert!(progress.iter().all(|p| (75..100).contains(p)));
+    }
+
+    #[test]
+    fn boot_time_plus_uptime_is_now() {
+        let (boot_time, uptime) = boot_time_and_uptime();
+        let now = unix_now();
+        assert!(boot_time > 0 && boot_time <= now);
+        assert!(now.abs_diff(boot_time + uptime) <= 1);
+
+        let info = get_uptime();
+        assert!(unix_now().abs_diff(info.boot_time + info.uptime_secs) <= 1);
+    }
+
+    // 双显卡笔记本上 lspci -mm -nn -D -d ::0300 与 -d ::0302 的输出
+    #[cfg(target_os = "linux")]
+    const LSPCI_OUTPUT: &str = "\
+0000:00:02.0 \"VGA compatible controller [0300]\" \"Intel Corporation [8086]\" \"TigerLake-LP GT2 [Iris Xe Graphics] [9a49]\" -r01 -p00 \"Lenovo [17aa]\" \"Device [22d8]\"
+0000:01:00.0 \"3D controller [0302]\" \"NVIDIA Corporation [10de]\" \"GA107M [GeForce RTX 3050 Mobile] [25a2]\" -ra1 -p00 \"Lenovo [17aa]\" \"Device [22d8]\"
+";
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn lspci_output_parses_slot_vendor_and_name() {
+        let gpus = parse_lspci_gpus(LSPCI_OUTPUT);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].slot, "0000:00:02.0");
+        assert_eq!(gpus[0].vendor_id, "8086");
+        assert_eq!(gpus[0].name, "TigerLake-LP GT2 [Iris Xe Graphics]");
+        assert_eq!(gpus[1].slot, "0000:01:00.0");
+        assert_eq!(gpus[1].vendor_id, "10DE");
+        assert_eq!(gpus[1].name, "GA107M [GeForce RTX 3050 Mobile]");
+
+        assert!(parse_lspci_gpus("").is_empty());
+        assert!(parse_lspci_gpus("0000:00:02.0 garbage").is_empty());
+    }
+
+    // 在临时目录中仿造 /sys/class/drm：card0 为已加载驱动的 AMD 独显，card1 为 simpledrm，
+    // card0-DP-1 为显示接口；NVIDIA 卡没有加载驱动，只出现在 lspci 中
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sysfs_fixture_enumerates_cards() {
+        use std::fs;
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join(format!("hisen-drm-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let amd = root.join("devices/pci0000:00/0000:03:00.0");
+        let simple = root.join("devices/platform/simple-framebuffer.0");
+        let drm = root.join("drm");
+        for dir in [
+            &amd,
+            &simple,
+            &drm.join("card0"),
+            &drm.join("card1"),
+            &drm.join("card0-DP-1"),
+        ] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        for (file, value) in [
+            ("vendor", "0x1002\n"),
+            ("device", "0x73bf\n"),
+            ("mem_info_vram_total", "17163091968\n"),
+            ("mem_info_vram_used", "1073741824\n"),
+            ("boot_vga", "1\n"),
+        ] {
+            fs::write(amd.join(file), value).unwrap();
+        }
+        symlink(&amd, drm.join("card0/device")).unwrap();
+        symlink(&simple, drm.join("card1/device")).unwrap();
+
+        let lspci = vec![
+            LspciGpu {
+                slot: "0000:03:00.0".to_string(),
+                vendor_id: "1002".to_string(),
+                name: "Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]".to_string(),
+            },
+            LspciGpu {
+                slot: "0000:01:00.0".to_string(),
+                vendor_id: "10DE".to_string(),
+                name: "GA104 [GeForce RTX 3070]".to_string(),
+            },
+        ];
+        let nvidia_memory = [("0000:01:00.0".to_string(), (8u64 << 30, 512u64 << 20))]
+            .into_iter()
+            .collect();
+        let gpus = linux_gpus(&drm, lspci, &nvidia_memory);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(gpus.len(), 2);
+        let amd = &gpus[0];
+        assert_eq!(amd.name, "Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]");
+        assert_eq!(amd.vendor, "AMD");
+        assert_eq!(amd.vram_bytes, Some(17163091968));
+        assert_eq!(amd.vram_used, Some(1 << 30));
+        assert_eq!(amd.kind, "discrete");
+        assert!(amd.is_primary);
+        assert_eq!(amd.pci_bus_id.as_deref(), Some("0000:03:00.0"));
+
+        let nvidia = &gpus[1];
+        assert_eq!(nvidia.name, "GA104 [GeForce RTX 3070]");
+        assert_eq!(nvidia.vendor, "NVIDIA");
+        assert_eq!(nvidia.vram_bytes, Some(8 << 30));
+        assert_eq!(nvidia.vram_used, Some(512 << 20));
+        assert!(!nvidia.is_primary);
+    }
+
+    fn section_names(names: &[&str]) -> Option<Vec<String>> {
+        Some(names.iter().map(|n| n.to_string()).collect())
+    }
+
+    #[test]
+    fn sections_default_to_all() {
+        let sections = Sections::from_names(None).unwrap();
+        assert!(sections.os && sections.cpu && sections.memory && sections.network && sections.gpu);
+    }
+
+    #[test]
+    fn sections_select_only_requested_names() {
+        let sections = Sections::from_names(section_names(&["CPU", "gpu", "cpu"])).unwrap();
+        assert!(sections.cpu && sections.gpu);
+        assert!(!sections.os && !sections.memory && !sections.network);
+
+        // 空列表表示不采集任何分区，只返回 collected_at
+        let sections = Sections::from_names(section_names(&[])).unwrap();
+        assert!(!sections.os && !sections.cpu && !sections.memory);
+        assert!(!sections.network && !sections.gpu);
+    }
+
+    #[test]
+    fn unknown_section_is_invalid_argument() {
+        match Sections::from_names(section_names(&["cpu", "gpus"])) {
+            Err(AppError::InvalidArgument(msg)) => assert!(msg.contains("\"gpus\"")),
+            Err(e) => panic!("expected InvalidArgument, got {:?}", e),
+            Ok(_) => panic!("unknown section accepted"),
+        }
+    }
+
+    #[test]
+    fn bad_system_profiler_output_is_a_parse_error() {
+        // system_profiler 被中断时输出不完整
+        let truncated = r#"{"SPDisplaysDataType" : [{"_name" : "kHW_AppleM1Item", "#;
+        assert!(matches!(
+            parse_macos_gpu_json(truncated),
+            Err(AppError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_macos_gpu_json(r#"{"SPDisplaysDataType" : {}}"#),
+            Err(AppError::Parse(_))
+        ));
+        // 没有显卡是空列表，不是错误
+        let gpus = parse_macos_gpu_json(r#"{"SPDisplaysDataType" : []}"#).unwrap();
+        assert!(gpus.is_empty());
+    }
+
+    fn ip_url(url: &str) -> reqwest::Url {
+        reqwest::Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn ip_responses_parse_per_service() {
+        let ipip = r#"{"ret":"ok","data":{"ip":"123.118.75.9","location":["中国","北京","北京","","联通"]}}"#;
+        assert_eq!(
+            parse_ip_response(&ip_url("https://myip.ipip.net/json"), ipip).as_deref(),
+            Some("123.118.75.9")
+        );
+
+        let pconline = "\r\n\r\nif(window.IPCallBack) {IPCallBack({\"ip\":\"223.104.3.186\",\"pro\":\"广东省\",\"proCode\":\"440000\",\"city\":\"深圳市\",\"addr\":\"广东省深圳市 移动\",\"err\":\"\"});}\r\n\r\n";
+        let url = ip_url("https://whois.pconline.com.cn/ipJson.jsp");
+        assert_eq!(
+            parse_ip_response(&url, pconline).as_deref(),
+            Some("223.104.3.186")
+        );
+        let url = ip_url("https://whois.pconline.com.cn/ipJson.jsp?json=true");
+        assert_eq!(
+            parse_ip_response(&url, r#"{"ip":"223.104.3.186","pro":"广东省"}"#).as_deref(),
+            Some("223.104.3.186")
+        );
+
+        let useragentinfo = r#"{"ip":"2408:8207:2471:d1f0::1","country":"中国","short_name":"CN","isp":"联通","net":"","desc":"","code":200}"#;
+        assert_eq!(
+            parse_ip_response(&ip_url("https://ip.useragentinfo.com/json"), useragentinfo)
+                .as_deref(),
+            Some("2408:8207:2471:d1f0::1")
+        );
+
+        // ipify 等只返回纯文本 IP
+        let ipify = ip_url("https://api.ipify.org");
+        assert_eq!(
+            parse_ip_response(&ipify, "203.0.113.7\n").as_deref(),
+            Some("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn ip_responses_reject_non_ip_values() {
+        let url = ip_url("https://myip.ipip.net/json");
+        assert!(parse_ip_response(&url, r#"{"ret":"err","msg":"limited"}"#).is_none());
+        assert!(parse_ip_response(&url, "当前 IP：1.2.3.4").is_none());
+
+        let url = ip_url("https://whois.pconline.com.cn/ipJson.jsp");
+        assert!(parse_ip_response(&url, "<html>502 Bad Gateway</html>").is_none());
+
+        // 网关或登录页返回的 HTML 不能当作 IP
+        let url = ip_url("https://api.ipify.org");
+        assert!(parse_ip_response(&url, "<html><body>login</body></html>").is_none());
+        assert!(parse_ip_response(&url, r#"{"ip":"not-an-ip"}"#).is_none());
+        assert!(parse_ip_response(&url, "").is_none());
+    }
+
+    fn wmi_row(name: &str, pnp_device_id: &str, adapter_ram: Option<u32>) -> Win32VideoController {
+        Win32VideoController {
+            name: Some(name.to_string()),
+            adapter_compatibility: None,
+            driver_version: None,
+            adapter_ram,
+            pnp_device_id: Some(pnp_device_id.to_string()),
+            current_horizontal_resolution: None,
+        }
+    }
+
+    #[test]
+    fn wmi_row_maps_to_gpu_info() {
+        let mut row = wmi_row(
+            "NVIDIA GeForce RTX 3060 Laptop GPU",
+            r"PCI\VEN_10DE&DEV_2520&SUBSYS_0A7E1028&REV_A1\4&1F0C5A9B&0&0008",
+            Some(2 << 30),
+        );
+        row.driver_version = Some(" 31.0.15.3623 ".to_string());
+        row.current_horizontal_resolution = Some(2560);
+        let gpu = gpu_from_wmi_row(row, &Default::default(), false);
+
+        assert_eq!(gpu.name, "NVIDIA GeForce RTX 3060 Laptop GPU");
+        assert_eq!(gpu.vendor, "NVIDIA");
+        assert_eq!(gpu.vram_bytes, Some(2 << 30));
+        assert_eq!(gpu.driver_version.as_deref(), Some("31.0.15.3623"));
+        assert_eq!(gpu.kind, "discrete");
+        assert!(gpu.is_primary);
+    }
+
+    #[test]
+    fn wmi_row_vendor_falls_back_to_adapter_compatibility() {
+        // 远程桌面等虚拟显卡没有 PCI 厂商 ID
+        let mut row = wmi_row(
+            "AMD Radeon(TM) Graphics",
+            r"ROOT\DISPLAY\0000",
+            Some(512 << 20),
+        );
+        row.adapter_compatibility = Some("Advanced Micro Devices, Inc.".to_string());
+        row.current_horizontal_resolution = Some(1920);
+        // 前面已有主显卡
+        let gpu = gpu_from_wmi_row(row, &Default::default(), true);
+        assert_eq!(gpu.vendor, "AMD");
+        assert_eq!(gpu.kind, "integrated");
+        assert!(!gpu.is_primary);
+
+        let mut row = wmi_row("  ", "", Some(0));
+        row.adapter_compatibility = Some("(Standard display types)".to_string());
+        let gpu = gpu_from_wmi_row(row, &Default::default(), false);
+        assert_eq!(gpu.name, "Unknown GPU");
+        assert_eq!(gpu.vendor, "Unknown");
+        assert_eq!(gpu.vram_bytes, None);
+        assert_eq!(gpu.kind, "unknown");
+        assert!(!gpu.is_primary);
+    }
+
+    #[test]
+    fn registry_vram_overrides_capped_adapter_ram() {
+        // 12GB 显卡的 AdapterRAM 被截断为 DWORD 上限
+        let name = "NVIDIA GeForce RTX 4070";
+        let pnp = r"PCI\VEN_10DE&DEV_2786&SUBSYS_51011458&REV_A1\4&2283F625&0&0019";
+        let registry = std::collections::HashMap::from([(name.to_string(), 12u64 << 30)]);
+
+        let gpu = gpu_from_wmi_row(wmi_row(name, pnp, Some(u32::MAX)), &registry, false);
+        assert_eq!(gpu.vram_bytes, Some(12 << 30));
+
+        // 注册表中没有该显卡时只能退回到截断后的值
+        let gpu = gpu_from_wmi_row(
+            wmi_row(name, pnp, Some(u32::MAX)),
+            &Default::default(),
+            false,
+        );
+        assert_eq!(gpu.vram_bytes, Some(u64::from(u32::MAX)));
+
+        // 注册表值为 0 时同样不采用
+        let registry = std::collections::HashMap::from([(name.to_string(), 0)]);
+        let gpu = gpu_from_wmi_row(wmi_row(name, pnp, Some(2 << 30)), &registry, false);
+        assert_eq!(gpu.vram_bytes, Some(2 << 30));
+    }
+
+    #[test]
+    fn qw_memory_size_reads_qword_and_binary() {
+        use serde_json::json;
+
+        assert_eq!(parse_qw_memory_size(&json!(8589934592u64)), Some(8 << 30));
+        // REG_BINARY 经 ConvertTo-Json 后为小端字节数组
+        assert_eq!(
+            parse_qw_memory_size(&json!([0, 0, 0, 0, 3, 0, 0, 0])),
+            Some(12 << 30)
+        );
+        assert_eq!(parse_qw_memory_size(&json!([0, 0, 0, 128])), Some(2 << 30));
+        assert_eq!(parse_qw_memory_size(&json!([])), None);
+        assert_eq!(
+            parse_qw_memory_size(&json!([0, 0, 0, 0, 0, 0, 0, 0, 1])),
+            None
+        );
+        assert_eq!(parse_qw_memory_size(&json!([0, 256])), None);
+        assert_eq!(parse_qw_memory_size(&json!("8589934592")), None);
+        assert_eq!(parse_qw_memory_size(&json!(null)), None);
+    }
+
+    // 双显卡笔记本上 WINDOWS_GPU_QUERY 的输出（ConvertTo-Json 输出数组）
+    const POWERSHELL_DUAL_GPU: &str = r#"[
+    {
+        "Name":  "Intel(R) UHD Graphics 630",
+        "AdapterRAM":  1073741824,
+        "DriverVersion":  "31.0.101.2111",
+        "PNPDeviceID":  "PCI\\VEN_8086\u0026DEV_3E9B\u0026SUBSYS_09261028\u0026REV_02\\3\u002611583659\u00260\u002610",
+        "CurrentHorizontalResolution":  1920,
+        "QwMemorySize":  null
+    },
+    {
+        "Name":  "NVIDIA GeForce GTX 1650",
+        "AdapterRAM":  4293918720,
+        "DriverVersion":  "31.0.15.3623",
+        "PNPDeviceID":  "PCI\\VEN_10DE\u0026DEV_1F91\u0026SUBSYS_09261028\u0026REV_A1\\4\u00262B6A7E2B\u00260\u00260008",
+        "CurrentHorizontalResolution":  null,
+        "QwMemorySize":  4294967296
+    }
+]
+"#;
+
+    #[test]
+    fn powershell_gpu_json_parses_dual_gpu_array() {
+        let gpus = parse_windows_gpu_powershell(POWERSHELL_DUAL_GPU).unwrap();
+        assert_eq!(gpus.len(), 2);
+
+        assert_eq!(gpus[0].name, "Intel(R) UHD Graphics 630");
+        assert_eq!(gpus[0].vendor, "Intel");
+        assert_eq!(gpus[0].vram_bytes, Some(1 << 30));
+        assert_eq!(gpus[0].driver_version.as_deref(), Some("31.0.101.2111"));
+        assert_eq!(gpus[0].kind, "integrated");
+        assert!(gpus[0].is_primary);
+
+        assert_eq!(gpus[1].name, "NVIDIA GeForce GTX 1650");
+        assert_eq!(gpus[1].vendor, "NVIDIA");
+        // QwMemorySize 优先于被截断的 AdapterRAM
+        assert_eq!(gpus[1].vram_bytes, Some(4 << 30));
+        assert_eq!(gpus[1].kind, "discrete");
+        assert!(!gpus[1].is_primary);
+    }
+
+    #[test]
+    fn powershell_gpu_json_parses_single_object() {
+        // 只有一块显卡时 ConvertTo-Json 输出单个对象；名称中的逗号不影响解析，
+        // QwMemorySize 为 REG_BINARY 时是字节数组
+        let json = concat!(
+            "\u{feff}",
+            r#"{
+    "Name":  "AMD Radeon(TM) RX 6600, Sapphire Pulse",
+    "AdapterRAM":  4293918720,
+    "DriverVersion":  "31.0.21921.1000 ",
+    "PNPDeviceID":  "PCI\\VEN_1002\u0026DEV_73FF\u0026SUBSYS_E4471DA2\u0026REV_C7\\6\u002631D5A4E\u00260\u00260019",
+    "CurrentHorizontalResolution":  2560,
+    "QwMemorySize":  [0, 0, 0, 0, 2, 0, 0, 0]
+}
+"#
+        );
+        let gpus = parse_windows_gpu_powershell(json).unwrap();
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].name, "AMD Radeon(TM) RX 6600, Sapphire Pulse");
+        assert_eq!(gpus[0].vendor, "AMD");
+        assert_eq!(gpus[0].vram_bytes, Some(8 << 30));
+        assert_eq!(gpus[0].driver_version.as_deref(), Some("31.0.21921.1000"));
+        assert_eq!(gpus[0].kind, "discrete");
+        assert!(gpus[0].is_primary);
+    }
+
+    #[test]
+    fn powershell_gpu_json_handles_empty_and_invalid_output() {
+        assert!(parse_windows_gpu_powershell("").unwrap().is_empty());
+        assert!(parse_windows_gpu_powershell("\u{feff}\r\n")
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            parse_windows_gpu_powershell("Get-CimInstance : Access denied"),
+            Err(AppError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn vram_strings_parse_to_bytes() {
+        assert_eq!(parse_vram_bytes("8 GB"), Some(8 << 30));
+        assert_eq!(parse_vram_bytes("512 MB"), Some(512 << 20));
+        assert_eq!(parse_vram_bytes(" 1536 MB "), Some(1536 << 20));
+        assert_eq!(parse_vram_bytes("1.5 GB"), Some(3 << 29));
+        assert_eq!(parse_vram_bytes("8GB"), Some(8 << 30));
+    }
+
+    #[test]
+    fn malformed_vram_strings_are_none() {
+        assert_eq!(parse_vram_bytes(""), None);
+        assert_eq!(parse_vram_bytes("8"), None);
+        assert_eq!(parse_vram_bytes("GB"), None);
+        assert_eq!(parse_vram_bytes("8 parsecs"), None);
+        assert_eq!(parse_vram_bytes("shared"), None);
+    }
+
+    #[test]
+    fn format_vram_prefers_whole_gigabytes() {
+        assert_eq!(format_vram(8 << 30), "8 GB");
+        assert_eq!(format_vram(512 << 20), "512 MB");
+        assert_eq!(format_vram(1536 << 20), "1536 MB");
+        assert_eq!(format_vram(0), "0 MB");
+    }
+}
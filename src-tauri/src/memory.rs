@@ -0,0 +1,345 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct FsCacheStats {
+    // 页缓存命中率（0~100），只有 macOS 旧版 vm_stat 提供
+    cache_hit_rate: Option<f32>,
+    cached_bytes: u64,
+    // 等待写回磁盘的脏页，macOS 不提供
+    dirty_bytes: Option<u64>,
+}
+
+#[tauri::command]
+pub fn get_fs_cache_stats() -> Option<FsCacheStats> {
+    #[cfg(target_os = "linux")]
+    {
+        get_fs_cache_stats_linux()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        get_fs_cache_stats_macos()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        None
+    }
+}
+
+// /proc/meminfo 中的值形如 "Cached:          1234567 kB"，返回字节数
+#[cfg(target_os = "linux")]
+fn meminfo_field(content: &str, key: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            (k == key).then(|| v.split_whitespace().next()?.parse::<u64>().ok())?
+        })
+        .map(|kb| kb * 1024)
+}
+
+#[cfg(target_os = "linux")]
+fn get_fs_cache_stats_linux() -> Option<FsCacheStats> {
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    Some(FsCacheStats {
+        cache_hit_rate: None,
+        cached_bytes: meminfo_field(&content, "Cached")?,
+        dirty_bytes: meminfo_field(&content, "Dirty"),
+    })
+}
+
+// vm_stat 输出形如：
+// Mach Virtual Memory Statistics: (page size of 16384 bytes)
+// File-backed pages:                      123456.
+// Object cache: 15 hits of 2,000 lookups (0% hit rate)
+// 解析部分不依赖系统调用，测试时在所有平台上编译
+#[cfg(any(target_os = "macos", test))]
+struct VmStat {
+    text: String,
+    page_size: u64,
+}
+
+#[cfg(any(target_os = "macos", test))]
+impl VmStat {
+    #[cfg(target_os = "macos")]
+    fn read() -> Option<Self> {
+        let out = std::process::Command::new("vm_stat").output().ok()?;
+        Some(Self::parse(
+            String::from_utf8_lossy(&out.stdout).into_owned(),
+        ))
+    }
+
+    // 首行没有页大小时按 4KB 计算
+    fn parse(text: String) -> Self {
+        let page_size = text
+            .lines()
+            .next()
+            .and_then(|l| l.split("page size of ").nth(1))
+            .and_then(|s| s.split_whitespace().next())
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(4096);
+        VmStat { text, page_size }
+    }
+
+    // 指定项的原始数值（页数或次数）
+    fn count(&self, key: &str) -> Option<u64> {
+        self.text.lines().find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            (k.trim() == key).then(|| v.trim().trim_end_matches('.').parse::<u64>().ok())?
+        })
+    }
+
+    // 指定项的页数换算为字节
+    fn bytes(&self, key: &str) -> Option<u64> {
+        self.count(key).map(|pages| pages * self.page_size)
+    }
+
+    // 旧版系统的 "Object cache" 行给出命中与查询次数，新版没有这一行
+    fn cache_hit_rate(&self) -> Option<f32> {
+        let line = self.text.lines().find(|l| l.starts_with("Object cache:"))?;
+        let numbers: Vec<f64> = line
+            .split_whitespace()
+            .filter_map(|w| w.replace(',', "").parse().ok())
+            .collect();
+        let (hits, lookups) = (*numbers.first()?, *numbers.get(1)?);
+        (lookups > 0.0).then(|| (hits / lookups * 100.0) as f32)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_fs_cache_stats_macos() -> Option<FsCacheStats> {
+    let vm = VmStat::read()?;
+    Some(FsCacheStats {
+        cache_hit_rate: vm.cache_hit_rate(),
+        cached_bytes: vm.bytes("File-backed pages")?,
+        dirty_bytes: None,
+    })
+}
+
+// 各平台的内存细分（字节），用于显示与活动监视器 / 任务管理器一致的数值，
+// 平台不提供的项为 None
+#[derive(Serialize, Default)]
+pub struct MemoryBreakdown {
+    // 文件缓存（Linux Cached，macOS 文件缓存，Windows 系统缓存）
+    cached: Option<u64>,
+    // 仅 Linux
+    buffers: Option<u64>,
+    // 以下仅 macOS
+    wired: Option<u64>,
+    compressed: Option<u64>,
+    app_memory: Option<u64>,
+    // 仅 Windows：备用列表（可随时回收的缓存页）与已修改列表
+    standby: Option<u64>,
+    modified: Option<u64>,
+}
+
+pub fn memory_breakdown() -> MemoryBreakdown {
+    #[cfg(target_os = "linux")]
+    {
+        memory_breakdown_linux().unwrap_or_default()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        memory_breakdown_macos().unwrap_or_default()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        memory_breakdown_windows().unwrap_or_default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn memory_breakdown_linux() -> Option<MemoryBreakdown> {
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    // 可回收的 slab 与 free 的 buff/cache 口径一致
+    let cached = meminfo_field(&content, "Cached")
+        .map(|c| c + meminfo_field(&content, "SReclaimable").unwrap_or(0));
+    Some(MemoryBreakdown {
+        cached,
+        buffers: meminfo_field(&content, "Buffers"),
+        ..Default::default()
+    })
+}
+
+// 与活动监视器的口径一致：App 内存 = 匿名页 - 可清除页，缓存文件 = 文件页 + 可清除页
+#[cfg(target_os = "macos")]
+fn memory_breakdown_macos() -> Option<MemoryBreakdown> {
+    let vm = VmStat::read()?;
+    let purgeable = vm.bytes("Pages purgeable").unwrap_or(0);
+    Some(MemoryBreakdown {
+        cached: vm.bytes("File-backed pages").map(|b| b + purgeable),
+        wired: vm.bytes("Pages wired down"),
+        compressed: vm.bytes("Pages occupied by compressor"),
+        app_memory: vm
+            .bytes("Anonymous pages")
+            .map(|b| b.saturating_sub(purgeable)),
+        ..Default::default()
+    })
+}
+
+// 通过 PDH 读取内存性能计数器，无需启动 PowerShell。使用英文计数器路径，不受系统语言影响；
+// 这些计数器都是瞬时值，采集一次即可
+#[cfg(target_os = "windows")]
+fn memory_breakdown_windows() -> Option<MemoryBreakdown> {
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::System::Performance::{
+        PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterValue,
+        PdhOpenQueryW, PDH_FMT_COUNTERVALUE, PDH_FMT_LARGE, PDH_HCOUNTER, PDH_HQUERY,
+    };
+
+    let paths = [
+        w!(r"\Memory\Cache Bytes"),
+        w!(r"\Memory\Modified Page List Bytes"),
+        w!(r"\Memory\Standby Cache Core Bytes"),
+        w!(r"\Memory\Standby Cache Normal Priority Bytes"),
+        w!(r"\Memory\Standby Cache Reserve Bytes"),
+    ];
+    let mut query = PDH_HQUERY(std::ptr::null_mut());
+    // SAFETY: 计数器句柄属于 query，只在关闭 query 之前使用；union 的 largeValue 与 PDH_FMT_LARGE 对应
+    let values: Vec<Option<u64>> = unsafe {
+        if PdhOpenQueryW(PCWSTR::null(), 0, &mut query) != 0 {
+            return None;
+        }
+        let counters: Vec<Option<PDH_HCOUNTER>> = paths
+            .iter()
+            .map(|path| {
+                let mut counter = PDH_HCOUNTER::default();
+                (PdhAddEnglishCounterW(query, *path, 0, &mut counter) == 0).then_some(counter)
+            })
+            .collect();
+        let values = if PdhCollectQueryData(query) == 0 {
+            counters
+                .iter()
+                .map(|counter| {
+                    let mut value = PDH_FMT_COUNTERVALUE::default();
+                    let status =
+                        PdhGetFormattedCounterValue((*counter)?, PDH_FMT_LARGE, None, &mut value);
+                    (status == 0).then_some(value.Anonymous.largeValue as u64)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        PdhCloseQuery(query);
+        values
+    };
+    let [cached, modified, standby @ ..] = values.as_slice() else {
+        return None;
+    };
+    Some(MemoryBreakdown {
+        cached: *cached,
+        standby: standby.iter().copied().sum::<Option<u64>>(),
+        modified: *modified,
+        ..Default::default()
+    })
+}
+
+// macOS 内核的内存压力状态，比已用内存更能反映内存是否紧张
+#[derive(Serialize)]
+pub struct MemoryPressure {
+    // "normal" / "warn" / "critical"，无法读取时为 "unknown"
+    level: String,
+    compressed_bytes: Option<u64>,
+    // 以下为开机以来的累计次数
+    swapins: Option<u64>,
+    swapouts: Option<u64>,
+    compressions: Option<u64>,
+    decompressions: Option<u64>,
+}
+
+// 其他平台返回 None
+pub fn memory_pressure() -> Option<MemoryPressure> {
+    #[cfg(target_os = "macos")]
+    {
+        memory_pressure_macos()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
+
+// kern.memorystatus_vm_pressure_level：1 正常、2 警告、4 严重
+#[cfg(target_os = "macos")]
+fn memory_pressure_macos() -> Option<MemoryPressure> {
+    let level = crate::util::run_checked("sysctl", &["-n", "kern.memorystatus_vm_pressure_level"])
+        .ok()
+        .map(|v| match v.trim() {
+            "1" => "normal",
+            "2" => "warn",
+            "4" => "critical",
+            _ => "unknown",
+        })
+        .unwrap_or("unknown");
+    let vm = VmStat::read()?;
+    Some(MemoryPressure {
+        level: level.to_string(),
+        compressed_bytes: vm.bytes("Pages occupied by compressor"),
+        swapins: vm.count("Swapins"),
+        swapouts: vm.count("Swapouts"),
+        compressions: vm.count("Compressions"),
+        decompressions: vm.count("Decompressions"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Apple Silicon 上 macOS 14 的 vm_stat 输出
+    const VM_STAT_ARM64: &str = "\
+Mach Virtual Memory Statistics: (page size of 16384 bytes)
+Pages free:                                3530.
+Pages active:                            230114.
+Pages inactive:                          227352.
+Pages speculative:                         2049.
+Pages throttled:                              0.
+Pages wired down:                         93125.
+Pages purgeable:                           9162.
+\"Translation faults\":                4184125617.
+Pages copy-on-write:                   142867434.
+Pages zero filled:                    1660958806.
+Pages reactivated:                      24567895.
+Pages purged:                           12876412.
+File-backed pages:                        155211.
+Anonymous pages:                          304304.
+Pages stored in compressor:               657214.
+Pages occupied by compressor:             176032.
+Decompressions:                         32598456.
+Compressions:                           44816532.
+Pageins:                                71254365.
+Pageouts:                                 603121.
+Swapins:                                 1243565.
+Swapouts:                                1823541.
+";
+
+    #[test]
+    fn vm_stat_reads_page_size_and_counts() {
+        let vm = VmStat::parse(VM_STAT_ARM64.to_string());
+        assert_eq!(vm.page_size, 16384);
+        // 数值末尾的 "." 被去掉
+        assert_eq!(vm.count("Swapins"), Some(1243565));
+        assert_eq!(vm.count("Pages throttled"), Some(0));
+        assert_eq!(vm.bytes("File-backed pages"), Some(155211 * 16384));
+        assert_eq!(
+            vm.bytes("Pages occupied by compressor"),
+            Some(176032 * 16384)
+        );
+        // 新版系统没有 Object cache 行
+        assert_eq!(vm.cache_hit_rate(), None);
+    }
+
+    #[test]
+    fn vm_stat_missing_keys_and_header() {
+        let vm = VmStat::parse(
+            "Pages free:                               12345.\n\
+             Object cache: 15 hits of 2,000 lookups (0% hit rate)\n"
+                .to_string(),
+        );
+        // 缺少页大小时按 4KB 计算
+        assert_eq!(vm.page_size, 4096);
+        assert_eq!(vm.bytes("Pages free"), Some(12345 * 4096));
+        assert_eq!(vm.count("File-backed pages"), None);
+        assert_eq!(vm.count("Swapins"), None);
+        assert_eq!(vm.cache_hit_rate(), Some(0.75));
+    }
+}
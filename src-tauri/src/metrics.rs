@@ -0,0 +1,301 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Networks, System};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// 推送间隔下限，避免前端传入过小的值把 CPU 打满
+const MIN_INTERVAL_MS: u64 = 200;
+// 历史记录默认每秒采样一次，保留 10 分钟
+const DEFAULT_HISTORY_INTERVAL_MS: u64 = 1000;
+const DEFAULT_HISTORY_RETENTION_SECS: u64 = 600;
+// 保留时长上限，避免缓冲区占用过多内存
+const MAX_HISTORY_RETENTION_SECS: u64 = 24 * 3600;
+// 推送任务中每个核心保留的样本数，默认 60 个，上限 3600 个
+const DEFAULT_CPU_HISTORY_SIZE: usize = 60;
+const MAX_CPU_HISTORY_SIZE: usize = 3600;
+
+// 当前运行中的推送任务，stop 或重新 start 时中止
+#[derive(Default)]
+pub struct MetricsStream {
+    task: Mutex<Option<JoinHandle<()>>>,
+    cpu_history: Mutex<CpuHistory>,
+}
+
+// 每个逻辑核心最近 capacity 次推送的使用率与频率，用于绘制各核心的趋势图
+#[derive(Default)]
+struct CpuHistory {
+    capacity: usize,
+    cores: Vec<VecDeque<CoreSample>>,
+}
+
+#[derive(Clone, Copy)]
+struct CoreSample {
+    usage: f32,
+    frequency: u64,
+}
+
+impl CpuHistory {
+    fn push(&mut self, samples: impl ExactSizeIterator<Item = CoreSample>) {
+        // 核心数变化（如热插拔）时重新开始记录
+        if self.cores.len() != samples.len() {
+            self.cores = vec![VecDeque::with_capacity(self.capacity); samples.len()];
+        }
+        for (core, sample) in self.cores.iter_mut().zip(samples) {
+            if core.len() >= self.capacity {
+                core.pop_front();
+            }
+            core.push_back(sample);
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CoreHistory {
+    index: usize,
+    // 按时间先后排列，最后一个为最新样本
+    usage: Vec<f32>,
+    // MHz
+    frequency: Vec<u64>,
+}
+
+impl MetricsStream {
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            task.abort();
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct IfaceDelta {
+    name: String,
+    received: u64,
+    transmitted: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct MetricsPayload {
+    cpu_usage: Vec<f32>,
+    used_memory: u64,
+    network: Vec<IfaceDelta>,
+}
+
+// history_size 为每个核心保留的样本数，重新 start 时清空已有历史
+#[tauri::command]
+pub fn start_metrics_stream(
+    app: AppHandle,
+    stream: State<'_, MetricsStream>,
+    interval_ms: u64,
+    history_size: Option<usize>,
+) {
+    let interval = Duration::from_millis(interval_ms.max(MIN_INTERVAL_MS));
+    *stream.cpu_history.lock().unwrap_or_else(|e| e.into_inner()) = CpuHistory {
+        capacity: history_size
+            .unwrap_or(DEFAULT_CPU_HISTORY_SIZE)
+            .clamp(1, MAX_CPU_HISTORY_SIZE),
+        cores: Vec::new(),
+    };
+    let task = tauri::async_runtime::spawn(run_metrics_stream(app, interval));
+    // 已有任务时用新的间隔替换
+    let previous = stream
+        .task
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(task);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+pub fn stop_metrics_stream(stream: State<'_, MetricsStream>) {
+    stream.stop();
+}
+
+// 推送任务未启动时返回空列表
+#[tauri::command]
+pub fn get_cpu_history(stream: State<'_, MetricsStream>) -> Vec<CoreHistory> {
+    let history = stream.cpu_history.lock().unwrap_or_else(|e| e.into_inner());
+    history
+        .cores
+        .iter()
+        .enumerate()
+        .map(|(index, samples)| CoreHistory {
+            index,
+            usage: samples.iter().map(|s| s.usage).collect(),
+            frequency: samples.iter().map(|s| s.frequency).collect(),
+        })
+        .collect()
+}
+
+// 推送与后台采样各自持有 System，刷新 CPU 不会缩短其他命令读取使用率时的采样窗口
+fn new_cpu_system() -> System {
+    let mut system = System::new();
+    system.refresh_cpu_all();
+    system
+}
+
+async fn run_metrics_stream(app: AppHandle, interval: Duration) {
+    let mut system = new_cpu_system();
+    let mut networks = Networks::new_with_refreshed_list();
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        let (cpu_usage, used_memory) = {
+            system.refresh_cpu_all();
+            system.refresh_memory();
+            let cpus = system.cpus();
+            app.state::<MetricsStream>()
+                .cpu_history
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(cpus.iter().map(|c| CoreSample {
+                    usage: c.cpu_usage(),
+                    frequency: c.frequency(),
+                }));
+            let cpu_usage = cpus.iter().map(|c| c.cpu_usage()).collect();
+            (cpu_usage, system.used_memory())
+        };
+
+        // received / transmitted 为距上次 refresh 的增量
+        networks.refresh();
+        let network = networks
+            .iter()
+            .map(|(name, data)| IfaceDelta {
+                name: name.clone(),
+                received: data.received(),
+                transmitted: data.transmitted(),
+            })
+            .collect();
+
+        let payload = MetricsPayload {
+            cpu_usage,
+            used_memory,
+            network,
+        };
+        if app.emit("metrics", payload).is_err() {
+            break;
+        }
+    }
+}
+
+// 应用启动后在后台持续采样的 CPU / 内存历史，前端刷新页面后仍可取回
+pub struct MetricsHistory {
+    samples: Mutex<VecDeque<MetricsSample>>,
+    config: Mutex<HistoryConfig>,
+}
+
+#[derive(Clone, Copy)]
+struct HistoryConfig {
+    interval_ms: u64,
+    retention_secs: u64,
+}
+
+impl HistoryConfig {
+    // 缓冲区容量 = 保留时长 / 采样间隔
+    fn capacity(&self) -> usize {
+        (self.retention_secs * 1000 / self.interval_ms).max(1) as usize
+    }
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        MetricsHistory {
+            samples: Mutex::new(VecDeque::new()),
+            config: Mutex::new(HistoryConfig {
+                interval_ms: DEFAULT_HISTORY_INTERVAL_MS,
+                retention_secs: DEFAULT_HISTORY_RETENTION_SECS,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct MetricsSample {
+    // 采样时间（Unix 时间戳，毫秒）
+    timestamp_ms: u64,
+    cpu_total: f32,
+    cpu_per_core: Vec<f32>,
+    used_memory: u64,
+}
+
+// 在 setup 中调用，随应用一直运行
+pub fn start_history_sampler(app: AppHandle) {
+    tauri::async_runtime::spawn(run_history_sampler(app));
+}
+
+async fn run_history_sampler(app: AppHandle) {
+    let mut system = new_cpu_system();
+    loop {
+        // 每次循环重新读取配置，修改间隔后立即生效
+        let config = *app
+            .state::<MetricsHistory>()
+            .config
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        tokio::time::sleep(Duration::from_millis(config.interval_ms)).await;
+
+        let sample = {
+            system.refresh_cpu_all();
+            system.refresh_memory();
+            MetricsSample {
+                timestamp_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+                cpu_total: system.global_cpu_usage(),
+                cpu_per_core: system.cpus().iter().map(|c| c.cpu_usage()).collect(),
+                used_memory: system.used_memory(),
+            }
+        };
+
+        let history = app.state::<MetricsHistory>();
+        let mut samples = history.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.push_back(sample);
+        // 超出容量时丢弃最旧的样本
+        let capacity = config.capacity();
+        while samples.len() > capacity {
+            samples.pop_front();
+        }
+    }
+}
+
+// 返回最近 seconds 秒内的样本，按时间先后排列
+#[tauri::command]
+pub fn get_metrics_history(history: State<'_, MetricsHistory>, seconds: u64) -> Vec<MetricsSample> {
+    let samples = history.samples.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(latest) = samples.back().map(|s| s.timestamp_ms) else {
+        return Vec::new();
+    };
+    let since = latest.saturating_sub(seconds.saturating_mul(1000));
+    samples
+        .iter()
+        .filter(|s| s.timestamp_ms >= since)
+        .cloned()
+        .collect()
+}
+
+// 修改采样间隔与保留时长，缩短保留时长时立即丢弃多出的旧样本
+#[tauri::command]
+pub fn set_metrics_history_config(
+    history: State<'_, MetricsHistory>,
+    interval_ms: u64,
+    retention_secs: u64,
+) {
+    let config = HistoryConfig {
+        interval_ms: interval_ms.max(MIN_INTERVAL_MS),
+        retention_secs: retention_secs.clamp(1, MAX_HISTORY_RETENTION_SECS),
+    };
+    *history.config.lock().unwrap_or_else(|e| e.into_inner()) = config;
+
+    let mut samples = history.samples.lock().unwrap_or_else(|e| e.into_inner());
+    let capacity = config.capacity();
+    while samples.len() > capacity {
+        samples.pop_front();
+    }
+}
@@ -0,0 +1,1017 @@
+use crate::error::AppError;
+use crate::util::output_with_timeout;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Serialize, Default)]
+pub struct VpnStatus {
+    connected: bool,
+    interface: Option<String>,
+    #[serde(rename = "type")]
+    vpn_type: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_vpn_status() -> VpnStatus {
+    #[cfg(target_os = "macos")]
+    {
+        get_vpn_status_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_vpn_status_windows()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        get_vpn_status_unix()
+    }
+}
+
+// 根据接口名前缀判断是否为 VPN 隧道接口
+fn vpn_type_from_iface(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    let kinds = [
+        ("wg", "WireGuard"),
+        ("tailscale", "WireGuard"),
+        ("utun", "tun"),
+        ("tun", "tun"),
+        ("tap", "tap"),
+        ("ppp", "PPP"),
+        ("ipsec", "IPsec"),
+    ];
+    kinds
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, kind)| *kind)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_vpn_status_unix() -> VpnStatus {
+    use sysinfo::Networks;
+
+    // 只考虑已分配地址的隧道接口
+    let networks = Networks::new_with_refreshed_list();
+    let mut tunnels: Vec<(String, &'static str)> = networks
+        .iter()
+        .filter(|(_, data)| !data.ip_networks().is_empty())
+        .filter_map(|(name, _)| vpn_type_from_iface(name).map(|kind| (name.clone(), kind)))
+        .collect();
+    tunnels.sort();
+
+    // 默认路由走隧道接口时优先报告该接口（全局 VPN）
+    let default_ifaces = linux_default_route_ifaces();
+    let chosen = tunnels
+        .iter()
+        .find(|(name, _)| default_ifaces.contains(name))
+        .or_else(|| tunnels.first());
+
+    match chosen {
+        Some((name, kind)) => VpnStatus {
+            connected: true,
+            interface: Some(name.clone()),
+            vpn_type: Some(kind.to_string()),
+        },
+        None => VpnStatus::default(),
+    }
+}
+
+// 读取 /proc/net/route 中目标为 0.0.0.0 的默认路由接口
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn linux_default_route_ifaces() -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string("/proc/net/route") else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let iface = fields.next()?;
+            let destination = fields.next()?;
+            (destination == "00000000").then(|| iface.to_string())
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn get_vpn_status_macos() -> VpnStatus {
+    use std::process::Command;
+
+    // 系统 VPN / NetworkExtension VPN 会出现在 scutil --nc list 中
+    // 形如: * (Connected)      XXXX-XXXX IPSec "Office" [IPSec]
+    let mut vpn_type = None;
+    if let Ok(out) = Command::new("scutil").args(["--nc", "list"]).output() {
+        let text = String::from_utf8_lossy(&out.stdout);
+        if let Some(line) = text.lines().find(|l| l.contains("(Connected)")) {
+            let kind = line
+                .rsplit_once('[')
+                .and_then(|(_, rest)| rest.split(']').next())
+                .unwrap_or("VPN");
+            vpn_type = Some(kind.to_string());
+        }
+    }
+
+    // utun0~3 通常被 iCloud 等系统服务占用，只有默认路由走 utun 才视为 VPN
+    let default_iface = Command::new("route")
+        .args(["-n", "get", "default"])
+        .output()
+        .ok()
+        .and_then(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("interface:").map(|s| s.trim().to_string()))
+        });
+    let tunnel = default_iface.filter(|name| vpn_type_from_iface(name).is_some());
+
+    if vpn_type.is_none() && tunnel.is_none() {
+        return VpnStatus::default();
+    }
+    let vpn_type = vpn_type.or_else(|| {
+        tunnel
+            .as_deref()
+            .and_then(vpn_type_from_iface)
+            .map(|s| s.to_string())
+    });
+    VpnStatus {
+        connected: true,
+        interface: tunnel,
+        vpn_type,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn get_vpn_status_windows() -> VpnStatus {
+    // 通过适配器描述与接口类型识别 VPN 适配器（23 = PPP，131 = 隧道）
+    let output = crate::util::command("powershell")
+        .args([
+            "-Command",
+            "Get-NetAdapter | Where-Object Status -eq 'Up' | Select-Object Name, InterfaceDescription, InterfaceType | ConvertTo-Json",
+        ])
+        .output();
+    let Ok(out) = output else {
+        return VpnStatus::default();
+    };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&out.stdout) else {
+        return VpnStatus::default();
+    };
+    let adapters = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+
+    let keywords = [
+        ("wireguard", "WireGuard"),
+        ("wintun", "WireGuard"),
+        ("tap-windows", "tap"),
+        ("openvpn", "OpenVPN"),
+        ("anyconnect", "AnyConnect"),
+        ("pangp", "GlobalProtect"),
+        ("fortinet", "FortiClient"),
+        ("vpn", "VPN"),
+    ];
+    for adapter in adapters {
+        let name = adapter.get("Name").and_then(|v| v.as_str()).unwrap_or_default();
+        let desc = adapter
+            .get("InterfaceDescription")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let if_type = adapter.get("InterfaceType").and_then(|v| v.as_u64());
+
+        let kind = keywords
+            .iter()
+            .find(|(k, _)| desc.contains(k))
+            .map(|(_, kind)| *kind)
+            .or(match if_type {
+                Some(23) => Some("PPP"),
+                Some(131) => Some("tunnel"),
+                _ => None,
+            });
+        if let Some(kind) = kind {
+            return VpnStatus {
+                connected: true,
+                interface: Some(name.to_string()),
+                vpn_type: Some(kind.to_string()),
+            };
+        }
+    }
+    VpnStatus::default()
+}
+
+// 单次 ping 最多发送的回显请求数
+const MAX_PING_COUNT: u32 = 20;
+
+#[derive(Serialize)]
+pub struct PingResult {
+    host: String,
+    transmitted: u32,
+    received: u32,
+    // 没有收到任何回复时为 None
+    min_ms: Option<f64>,
+    avg_ms: Option<f64>,
+    max_ms: Option<f64>,
+    // 丢包率（百分比）
+    packet_loss: f64,
+}
+
+// 调用系统 ping（已具备发送 ICMP 所需的权限），避免本进程需要原始套接字权限
+#[tauri::command]
+pub async fn ping_host(host: String, count: u32) -> Result<PingResult, AppError> {
+    let host = host.trim().to_string();
+    // 以 '-' 开头会被当作 ping 的参数
+    if host.is_empty() || host.starts_with('-') || host.contains(char::is_whitespace) {
+        return Err(AppError::Network(format!("invalid host: {:?}", host)));
+    }
+    let count = count.clamp(1, MAX_PING_COUNT);
+
+    tauri::async_runtime::spawn_blocking(move || run_ping(host, count))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("ping task: {}", e)))?
+}
+
+fn run_ping(host: String, count: u32) -> Result<PingResult, AppError> {
+    let count_arg = count.to_string();
+    let mut cmd = std::process::Command::new("ping");
+    #[cfg(target_os = "windows")]
+    cmd.args(["-n", &count_arg, "-w", "2000", &host]);
+    #[cfg(target_os = "macos")]
+    cmd.args(["-c", &count_arg, "-W", "2000", &host]);
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    cmd.args(["-c", &count_arg, "-W", "2", &host]);
+
+    // 每个请求最多等待 2 秒，另外留出域名解析的时间
+    let timeout = Duration::from_secs(u64::from(count) * 3 + 5);
+    let out = output_with_timeout(&mut cmd, timeout).map_err(|e| match e.kind() {
+        std::io::ErrorKind::TimedOut => AppError::Timeout(format!("ping {}: {}", host, e)),
+        _ => AppError::CommandFailed(format!("ping: {}", e)),
+    })?;
+    let stdout = String::from_utf8_lossy(&out.stdout);
+
+    // 全部丢包时 ping 以非零状态退出，但仍会输出统计信息
+    if let Some(result) = parse_ping_output(&host, &stdout) {
+        return Ok(result);
+    }
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let message = format!("ping {}: {}", host, stderr.trim());
+    if stderr.contains("Operation not permitted") || stderr.contains("Permission denied") {
+        return Err(AppError::PermissionDenied(message));
+    }
+    Err(AppError::CommandFailed(message))
+}
+
+// 解析 ping 的统计输出：
+// Linux:   4 packets transmitted, 4 received, 0% packet loss, time 3004ms
+//          rtt min/avg/max/mdev = 9.8/10.1/10.5/0.2 ms
+// macOS:   4 packets transmitted, 4 packets received, 0.0% packet loss
+//          round-trip min/avg/max/stddev = 9.8/10.1/10.5/0.2 ms
+// Windows: Packets: Sent = 4, Received = 4, Lost = 0 (0% loss),
+//          Minimum = 9ms, Maximum = 10ms, Average = 10ms
+// 本地化的 Windows 输出措辞不同（中文版用全角逗号），但字段顺序不变
+fn parse_ping_output(host: &str, text: &str) -> Option<PingResult> {
+    let (transmitted, received) = text.lines().find_map(parse_ping_counts)?;
+    let lost = transmitted.saturating_sub(received);
+    let packet_loss = if transmitted == 0 {
+        100.0
+    } else {
+        (f64::from(lost) * 1000.0 / f64::from(transmitted)).round() / 10.0
+    };
+
+    let mut rtt = None;
+    if let Some(line) = text.lines().find(|l| l.contains("min/avg/max")) {
+        let values: Vec<f64> = line
+            .split('=')
+            .nth(1)?
+            .split_whitespace()
+            .next()?
+            .split('/')
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if values.len() >= 3 {
+            rtt = Some((values[0], values[1], values[2]));
+        }
+    } else if let Some(line) = text.lines().rev().find(|l| l.matches("ms").count() >= 3) {
+        // Windows 依次为最小、最大、平均
+        let values: Vec<f64> = line
+            .split([',', '，'])
+            .filter_map(|part| part.split('=').nth(1))
+            .filter_map(|v| v.trim().trim_end_matches("ms").trim().parse().ok())
+            .collect();
+        if values.len() >= 3 {
+            rtt = Some((values[0], values[2], values[1]));
+        }
+    }
+
+    Some(PingResult {
+        host: host.to_string(),
+        transmitted,
+        received,
+        min_ms: rtt.map(|r| r.0),
+        avg_ms: rtt.map(|r| r.1),
+        max_ms: rtt.map(|r| r.2),
+        packet_loss,
+    })
+}
+
+// 统计行的前两个字段依次为发送数与接收数：Unix 为字段开头的数字，Windows 为 '=' 后的数字。
+// 两者都必须是整数，以免把 Windows 的耗时行（9ms）或带 '%' 的 IPv6 地址行当成统计行
+fn parse_ping_counts(line: &str) -> Option<(u32, u32)> {
+    let mut fields = line.split([',', '，']).map(|field| {
+        let value = field.split_once('=').map_or(field, |(_, value)| value);
+        value.split_whitespace().next()?.parse::<u32>().ok()
+    });
+    Some((fields.next()??, fields.next()??))
+}
+
+#[derive(Serialize)]
+pub struct RouteInfo {
+    // CIDR 形式，默认路由为 0.0.0.0/0
+    destination: String,
+    // 直连路由没有网关
+    gateway: Option<String>,
+    interface: String,
+    // macOS 的路由表不提供跃点数
+    metric: Option<u32>,
+    is_default: bool,
+    // 多条默认路由中实际生效的那一条
+    is_active: bool,
+}
+
+// macOS / Windows 要启动 netstat / PowerShell，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_routes() -> Result<Vec<RouteInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(collect_routes)
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("route task: {}", e)))
+}
+
+fn collect_routes() -> Vec<RouteInfo> {
+    #[cfg(target_os = "macos")]
+    let mut routes = get_routes_macos();
+    #[cfg(target_os = "windows")]
+    let mut routes = get_routes_windows();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut routes = get_routes_linux();
+
+    // 非 macOS 平台上跃点数最小的默认路由生效；macOS 在解析时已标记
+    if !cfg!(target_os = "macos") {
+        if let Some(active) = routes
+            .iter_mut()
+            .filter(|r| r.is_default)
+            .min_by_key(|r| r.metric.unwrap_or(u32::MAX))
+        {
+            active.is_active = true;
+        }
+    }
+    // 默认路由排在最前，其余按目标排序
+    routes.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then(a.metric.cmp(&b.metric))
+            .then(a.destination.cmp(&b.destination))
+    });
+    routes
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_routes_linux() -> Vec<RouteInfo> {
+    std::fs::read_to_string("/proc/net/route")
+        .map(|content| parse_proc_net_route(&content))
+        .unwrap_or_default()
+}
+
+// /proc/net/route（IPv4）各列：Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+// 地址为小端序十六进制，Flags 的 0x1 位表示路由可用
+#[cfg(any(not(any(target_os = "macos", target_os = "windows")), test))]
+fn parse_proc_net_route(content: &str) -> Vec<RouteInfo> {
+    let hex_ip = |hex: &str| -> Option<std::net::Ipv4Addr> {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        Some(std::net::Ipv4Addr::from(value.to_le_bytes()))
+    };
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+            if flags & 0x1 == 0 {
+                return None;
+            }
+            let destination = hex_ip(fields.get(1)?)?;
+            let gateway = hex_ip(fields.get(2)?)?;
+            let prefix = u32::from(hex_ip(fields.get(7)?)?).count_ones();
+            Some(RouteInfo {
+                destination: format!("{}/{}", destination, prefix),
+                gateway: (!gateway.is_unspecified()).then(|| gateway.to_string()),
+                interface: fields[0].to_string(),
+                metric: fields.get(6)?.parse().ok(),
+                is_default: destination.is_unspecified() && prefix == 0,
+                is_active: false,
+            })
+        })
+        .collect()
+}
+
+// netstat -rn -f inet 输出形如：
+// Destination        Gateway            Flags           Netif Expire
+// default            192.168.1.1        UGScg             en0
+// 127                127.0.0.1          UCS               lo0
+#[cfg(target_os = "macos")]
+fn get_routes_macos() -> Vec<RouteInfo> {
+    use std::process::Command;
+
+    let Ok(out) = Command::new("netstat").args(["-rn", "-f", "inet"]).output() else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+
+    // 默认路由走哪个接口由 route get 决定
+    let active_iface = Command::new("route")
+        .args(["-n", "get", "default"])
+        .output()
+        .ok()
+        .and_then(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("interface:").map(|s| s.trim().to_string()))
+        });
+
+    let mut active_marked = false;
+    text.lines()
+        .skip_while(|l| !l.starts_with("Destination"))
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let destination = *fields.first()?;
+            let gateway = *fields.get(1)?;
+            let interface = *fields.get(3)?;
+            let is_default = destination == "default";
+            let is_active = is_default
+                && !active_marked
+                && active_iface.as_deref() == Some(interface);
+            active_marked |= is_active;
+            let destination = if is_default { "0.0.0.0/0" } else { destination };
+            Some(RouteInfo {
+                destination: destination.to_string(),
+                // 直连路由的网关是 link#N 或 MAC 地址
+                gateway: gateway
+                    .parse::<std::net::Ipv4Addr>()
+                    .ok()
+                    .map(|ip| ip.to_string()),
+                interface: interface.to_string(),
+                metric: None,
+                is_default,
+                is_active,
+            })
+        })
+        .collect()
+}
+
+// Windows 上实际生效的跃点数为路由跃点数与接口跃点数之和
+#[cfg(target_os = "windows")]
+fn get_routes_windows() -> Vec<RouteInfo> {
+    let output = crate::util::command("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-NetRoute -AddressFamily IPv4 | Select-Object DestinationPrefix, NextHop, InterfaceAlias, RouteMetric, InterfaceMetric | ConvertTo-Json",
+        ])
+        .output();
+    let Ok(out) = output else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&out.stdout) else {
+        return Vec::new();
+    };
+    let items = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let destination = item.get("DestinationPrefix")?.as_str()?.to_string();
+            let next_hop = item.get("NextHop").and_then(|v| v.as_str()).unwrap_or_default();
+            let metric = |key: &str| item.get(key).and_then(|v| v.as_u64());
+            let metric = match (metric("RouteMetric"), metric("InterfaceMetric")) {
+                (Some(route), Some(iface)) => Some((route + iface) as u32),
+                (route, iface) => route.or(iface).map(|m| m as u32),
+            };
+            Some(RouteInfo {
+                is_default: destination == "0.0.0.0/0",
+                destination,
+                gateway: (next_hop != "0.0.0.0" && !next_hop.is_empty()).then(|| next_hop.to_string()),
+                interface: item
+                    .get("InterfaceAlias")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                metric,
+                is_active: false,
+            })
+        })
+        .collect()
+}
+
+// hosts 文件条目数上限
+const MAX_HOSTS_ENTRIES: usize = 500;
+
+#[derive(Serialize)]
+pub struct NameResolutionConfig {
+    // (地址, 主机名)，一行有多个别名时拆成多条
+    hosts_file_entries: Vec<(String, String)>,
+    // 查询顺序，如 ["files", "mdns4_minimal", "dns"]
+    resolution_order: Vec<String>,
+    search_domains: Vec<String>,
+}
+
+#[tauri::command]
+pub fn get_name_resolution_config() -> NameResolutionConfig {
+    #[cfg(unix)]
+    {
+        get_name_resolution_config_unix()
+    }
+    #[cfg(windows)]
+    {
+        get_name_resolution_config_windows()
+    }
+}
+
+// hosts 文件格式：地址后跟一个或多个主机名，# 之后为注释
+fn parse_hosts_file(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let address = tokens.next().unwrap_or_default().to_string();
+            tokens.map(move |name| (address.clone(), name.to_string()))
+        })
+        .take(MAX_HOSTS_ENTRIES)
+        .collect()
+}
+
+#[cfg(unix)]
+fn get_name_resolution_config_unix() -> NameResolutionConfig {
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+
+    let mut resolution_order = parse_nsswitch_hosts(&read("/etc/nsswitch.conf"));
+    // macOS 没有 nsswitch.conf，系统解析器先查 hosts 文件再查 DNS
+    if resolution_order.is_empty() && cfg!(target_os = "macos") {
+        resolution_order = vec!["files".to_string(), "dns".to_string()];
+    }
+
+    NameResolutionConfig {
+        hosts_file_entries: parse_hosts_file(&read("/etc/hosts")),
+        resolution_order,
+        search_domains: parse_resolv_search(&read("/etc/resolv.conf")),
+    }
+}
+
+// nsswitch.conf 的 hosts 行形如 "hosts: files mdns4_minimal [NOTFOUND=return] dns"，
+// 方括号内为动作而不是数据源
+#[cfg(any(unix, test))]
+fn parse_nsswitch_hosts(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("hosts:"))
+        .map(|sources| {
+            sources
+                .split_whitespace()
+                .filter(|s| !s.starts_with('[') && !s.ends_with(']'))
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// resolv.conf 中 search 与 domain 互斥，以最后出现的为准
+#[cfg(any(unix, test))]
+fn parse_resolv_search(content: &str) -> Vec<String> {
+    let mut search_domains = Vec::new();
+    for line in content.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("search") | Some("domain") => {
+                search_domains = tokens.map(|s| s.to_string()).collect();
+            }
+            _ => {}
+        }
+    }
+    search_domains
+}
+
+#[cfg(windows)]
+fn get_name_resolution_config_windows() -> NameResolutionConfig {
+    let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let hosts = std::fs::read_to_string(format!(r"{}\System32\drivers\etc\hosts", system_root))
+        .unwrap_or_default();
+
+    // 未配置后缀搜索列表时输出为空
+    let search_domains = crate::util::run_checked(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "(Get-DnsClientGlobalSetting).SuffixSearchList",
+        ],
+    )
+    .map(|out| {
+        out.lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect()
+    })
+    .unwrap_or_default();
+
+    NameResolutionConfig {
+        hosts_file_entries: parse_hosts_file(&hosts),
+        // Windows 的查询顺序固定：hosts 文件（预加载到 DNS 缓存）、DNS、LLMNR、NetBIOS
+        resolution_order: ["files", "dns", "llmnr", "netbios"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        search_domains,
+    }
+}
+
+// 系统提供的接口链路信息，sysinfo 不包含这些字段
+#[derive(Default)]
+pub struct LinkDetails {
+    pub mtu: Option<u32>,
+    pub is_up: Option<bool>,
+    pub speed_mbps: Option<u64>,
+}
+
+// 按接口名返回链路信息，平台不提供的项为 None
+pub fn link_details() -> HashMap<String, LinkDetails> {
+    #[cfg(target_os = "macos")]
+    {
+        link_details_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        link_details_windows()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        link_details_linux()
+    }
+}
+
+// 按接口名推断类型，供前端分组："loopback" / "vpn" / "virtual" / "wireless" / "physical"
+pub fn iface_kind(name: &str) -> &'static str {
+    let lower = name.to_ascii_lowercase();
+    if lower == "lo" || lower.starts_with("lo0") || lower.starts_with("loopback") {
+        return "loopback";
+    }
+    if vpn_type_from_iface(name).is_some() {
+        return "vpn";
+    }
+    let virtual_prefixes = [
+        "docker", "veth", "br-", "virbr", "vmnet", "vboxnet", "awdl", "llw", "bridge",
+        "anpi", "gif", "stf", "ap1", "cni", "flannel", "zt",
+    ];
+    if virtual_prefixes.iter().any(|p| lower.starts_with(p)) {
+        return "virtual";
+    }
+    let wireless = lower.starts_with("wl")
+        || lower.starts_with("wi-fi")
+        || lower.contains("wireless")
+        || (cfg!(target_os = "linux")
+            && std::path::Path::new(&format!("/sys/class/net/{}/wireless", name)).exists());
+    if wireless {
+        return "wireless";
+    }
+    "physical"
+}
+
+// /sys/class/net/<name>/{mtu,operstate,flags,speed}；lo 的 operstate 为 unknown，
+// 此时以 flags 的 IFF_UP 位为准；未连接的网卡读取 speed 会出错或为 -1
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn link_details_linux() -> HashMap<String, LinkDetails> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let dir = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(dir.join(file))
+                    .ok()
+                    .map(|v| v.trim().to_string())
+            };
+            let flags_up = read("flags")
+                .and_then(|f| u32::from_str_radix(f.trim_start_matches("0x"), 16).ok())
+                .map(|f| f & 0x1 != 0);
+            let is_up = match read("operstate").as_deref() {
+                Some("up") => Some(true),
+                Some("down") | Some("lowerlayerdown") | Some("notpresent") => Some(false),
+                _ => flags_up,
+            };
+            let details = LinkDetails {
+                mtu: read("mtu").and_then(|v| v.parse().ok()),
+                is_up,
+                speed_mbps: read("speed")
+                    .and_then(|v| v.parse::<i64>().ok())
+                    .filter(|&v| v > 0)
+                    .map(|v| v as u64),
+            };
+            (entry.file_name().to_string_lossy().into_owned(), details)
+        })
+        .collect()
+}
+
+// ifconfig 输出形如：
+// en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+// 	status: active
+// macOS 不便获取链路速率
+#[cfg(target_os = "macos")]
+fn link_details_macos() -> HashMap<String, LinkDetails> {
+    let Ok(text) = crate::util::run_checked("ifconfig", &[]) else {
+        return HashMap::new();
+    };
+    let mut details: HashMap<String, LinkDetails> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let Some((name, rest)) = line.split_once(": ") else {
+                continue;
+            };
+            let mtu = rest
+                .split_once("mtu ")
+                .and_then(|(_, v)| v.split_whitespace().next())
+                .and_then(|v| v.parse().ok());
+            let flags: Vec<&str> = rest
+                .split_once('<')
+                .and_then(|(_, f)| f.split_once('>'))
+                .map(|(f, _)| f.split(',').collect())
+                .unwrap_or_default();
+            details.insert(
+                name.to_string(),
+                LinkDetails {
+                    mtu,
+                    is_up: Some(flags.contains(&"UP") && flags.contains(&"RUNNING")),
+                    speed_mbps: None,
+                },
+            );
+            current = Some(name.to_string());
+        } else if let Some(status) = line.trim().strip_prefix("status: ") {
+            // 有 status 行的接口以其为准（网线拔出时 flags 仍为 UP）
+            if let Some(entry) = current.as_ref().and_then(|name| details.get_mut(name)) {
+                entry.is_up = Some(status == "active");
+            }
+        }
+    }
+    details
+}
+
+// GetIfTable2 直接返回各接口的别名（即 sysinfo 与 Get-NetAdapter 使用的名称）、MTU、状态与速率，
+// 无需启动 PowerShell
+#[cfg(target_os = "windows")]
+fn link_details_windows() -> HashMap<String, LinkDetails> {
+    use windows::Win32::NetworkManagement::IpHelper::{FreeMibTable, GetIfTable2, MIB_IF_TABLE2};
+    use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+
+    let mut table: *mut MIB_IF_TABLE2 = std::ptr::null_mut();
+    // SAFETY: 调用成功后 table 指向 NumEntries 行的表，读取完毕后由 FreeMibTable 释放
+    unsafe {
+        if GetIfTable2(&mut table).is_err() || table.is_null() {
+            return HashMap::new();
+        }
+        let rows =
+            std::slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize);
+        let details = rows
+            .iter()
+            .filter_map(|row| {
+                let len = row
+                    .Alias
+                    .iter()
+                    .position(|&c| c == 0)
+                    .unwrap_or(row.Alias.len());
+                let name = String::from_utf16_lossy(&row.Alias[..len]);
+                if name.is_empty() {
+                    return None;
+                }
+                // 速率单位为 bit/s，未连接时为 0 或 u64::MAX
+                let speed = row.ReceiveLinkSpeed.max(row.TransmitLinkSpeed);
+                let details = LinkDetails {
+                    mtu: Some(row.Mtu).filter(|&mtu| mtu > 0 && mtu != u32::MAX),
+                    is_up: Some(row.OperStatus == IfOperStatusUp),
+                    speed_mbps: Some(speed)
+                        .filter(|&v| v > 0 && v != u64::MAX)
+                        .map(|v| v / 1_000_000),
+                };
+                Some((name, details))
+            })
+            .collect();
+        FreeMibTable(table as *const _);
+        details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proc_net_route() {
+        let content = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlp2s0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+enp0s31f6\t00000000\t0100000A\t0003\t0\t0\t100\t00000000\t0\t0\t0
+wlp2s0\t0001A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+docker0\t000011AC\t00000000\t0000\t0\t0\t0\t0000FFFF\t0\t0\t0
+";
+        let routes = parse_proc_net_route(content);
+        let summary: Vec<_> = routes
+            .iter()
+            .map(|r| {
+                (
+                    r.destination.as_str(),
+                    r.gateway.as_deref(),
+                    r.interface.as_str(),
+                    r.metric,
+                    r.is_default,
+                )
+            })
+            .collect();
+        // 未设置 RTF_UP 的 docker0 路由被跳过
+        assert_eq!(
+            summary,
+            [
+                ("0.0.0.0/0", Some("192.168.1.1"), "wlp2s0", Some(600), true),
+                ("0.0.0.0/0", Some("10.0.0.1"), "enp0s31f6", Some(100), true),
+                ("192.168.1.0/24", None, "wlp2s0", Some(600), false),
+            ]
+        );
+        assert!(routes.iter().all(|r| !r.is_active));
+        assert!(parse_proc_net_route("Iface\tDestination\n").is_empty());
+    }
+
+    #[test]
+    fn hosts_file_entries() {
+        let content = "\
+# Static table lookup for hostnames.
+127.0.0.1\tlocalhost
+::1     localhost ip6-localhost ip6-loopback   # IPv6
+192.168.1.10  nas.lan nas
+
+   # indented comment
+10.0.0.5 build
+";
+        let entries = parse_hosts_file(content);
+        let pairs: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|(address, name)| (address.as_str(), name.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("127.0.0.1", "localhost"),
+                ("::1", "localhost"),
+                ("::1", "ip6-localhost"),
+                ("::1", "ip6-loopback"),
+                ("192.168.1.10", "nas.lan"),
+                ("192.168.1.10", "nas"),
+                ("10.0.0.5", "build"),
+            ]
+        );
+
+        let many: String = (0..MAX_HOSTS_ENTRIES + 10)
+            .map(|i| format!("10.1.{}.{} host{}\n", i / 256, i % 256, i))
+            .collect();
+        assert_eq!(parse_hosts_file(&many).len(), MAX_HOSTS_ENTRIES);
+    }
+
+    #[test]
+    fn nsswitch_and_resolv_conf() {
+        let nsswitch = "\
+passwd:         files systemd
+# hosts: files dns
+hosts:          files mdns4_minimal [NOTFOUND=return] dns myhostname
+networks:       files
+";
+        assert_eq!(
+            parse_nsswitch_hosts(nsswitch),
+            ["files", "mdns4_minimal", "dns", "myhostname"]
+        );
+        assert!(parse_nsswitch_hosts("passwd: files\n").is_empty());
+
+        let resolv = "\
+# Generated by NetworkManager
+domain corp.example
+nameserver 192.168.1.1
+search lan example.com
+options edns0
+";
+        assert_eq!(parse_resolv_search(resolv), ["lan", "example.com"]);
+        assert!(parse_resolv_search("nameserver 1.1.1.1\n").is_empty());
+    }
+
+    #[test]
+    fn ping_linux() {
+        let output = "PING 8.8.8.8 (8.8.8.8) 56(84) bytes of data.
+64 bytes from 8.8.8.8: icmp_seq=1 ttl=117 time=9.81 ms
+64 bytes from 8.8.8.8: icmp_seq=3 ttl=117 time=10.5 ms
+
+--- 8.8.8.8 ping statistics ---
+3 packets transmitted, 2 received, 33.3333% packet loss, time 2003ms
+rtt min/avg/max/mdev = 9.810/10.155/10.500/0.345 ms
+";
+        let result = parse_ping_output("8.8.8.8", output).unwrap();
+        assert_eq!((result.transmitted, result.received), (3, 2));
+        assert_eq!(result.packet_loss, 33.3);
+        assert_eq!(result.min_ms, Some(9.81));
+        assert_eq!(result.avg_ms, Some(10.155));
+        assert_eq!(result.max_ms, Some(10.5));
+    }
+
+    #[test]
+    fn ping_linux_ipv6_scope_and_no_reply() {
+        // 地址中的 '%' 不能被当成丢包率
+        let output = "PING fe80::1%eth0 (fe80::1%eth0) 56 data bytes
+
+--- fe80::1%eth0 ping statistics ---
+4 packets transmitted, 0 received, +4 errors, 100% packet loss, time 3051ms
+";
+        let result = parse_ping_output("fe80::1%eth0", output).unwrap();
+        assert_eq!((result.transmitted, result.received), (4, 0));
+        assert_eq!(result.packet_loss, 100.0);
+        assert_eq!(result.avg_ms, None);
+    }
+
+    #[test]
+    fn ping_macos() {
+        let output = "PING example.com (93.184.216.34): 56 data bytes
+64 bytes from 93.184.216.34: icmp_seq=0 ttl=56 time=88.214 ms
+
+--- example.com ping statistics ---
+4 packets transmitted, 4 packets received, 0.0% packet loss
+round-trip min/avg/max/stddev = 87.902/88.150/88.412/0.190 ms
+";
+        let result = parse_ping_output("example.com", output).unwrap();
+        assert_eq!((result.transmitted, result.received), (4, 4));
+        assert_eq!(result.packet_loss, 0.0);
+        assert_eq!(result.min_ms, Some(87.902));
+        assert_eq!(result.max_ms, Some(88.412));
+    }
+
+    #[test]
+    fn ping_windows() {
+        let output = "
+Pinging 8.8.8.8 with 32 bytes of data:
+Reply from 8.8.8.8: bytes=32 time=10ms TTL=117
+Request timed out.
+
+Ping statistics for 8.8.8.8:
+    Packets: Sent = 4, Received = 3, Lost = 1 (25% loss),
+Approximate round trip times in milli-seconds:
+    Minimum = 9ms, Maximum = 12ms, Average = 10ms
+";
+        let result = parse_ping_output("8.8.8.8", output).unwrap();
+        assert_eq!((result.transmitted, result.received), (4, 3));
+        assert_eq!(result.packet_loss, 25.0);
+        assert_eq!(result.min_ms, Some(9.0));
+        assert_eq!(result.avg_ms, Some(10.0));
+        assert_eq!(result.max_ms, Some(12.0));
+    }
+
+    #[test]
+    fn ping_windows_localized() {
+        let chinese = "
+正在 Ping 8.8.8.8 具有 32 字节的数据:
+来自 8.8.8.8 的回复: 字节=32 时间=10ms TTL=117
+
+8.8.8.8 的 Ping 统计信息:
+    数据包: 已发送 = 2，已接收 = 2，丢失 = 0 (0% 丢失)，
+往返行程的估计时间(以毫秒为单位):
+    最短 = 9ms，最长 = 11ms，平均 = 10ms
+";
+        let result = parse_ping_output("8.8.8.8", chinese).unwrap();
+        assert_eq!((result.transmitted, result.received), (2, 2));
+        assert_eq!(result.packet_loss, 0.0);
+        assert_eq!(result.min_ms, Some(9.0));
+        assert_eq!(result.avg_ms, Some(10.0));
+        assert_eq!(result.max_ms, Some(11.0));
+
+        // 德语版把丢包率放在下一行
+        let german = "
+Ping-Statistik für 8.8.8.8:
+    Pakete: Gesendet = 4, Empfangen = 0, Verloren = 4
+    (100% Verlust),
+";
+        let result = parse_ping_output("8.8.8.8", german).unwrap();
+        assert_eq!((result.transmitted, result.received), (4, 0));
+        assert_eq!(result.packet_loss, 100.0);
+        assert_eq!(result.min_ms, None);
+    }
+
+    #[test]
+    fn ping_without_statistics() {
+        let output = "ping: nowhere: Name or service not known\n";
+        assert!(parse_ping_output("nowhere", output).is_none());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::gpu::{GpuMetrics, GpuProcess};
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use nvml_wrapper::Nvml;
+use std::process::Command;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// 驱动异常时 nvidia-smi 可能长时间无响应
+const NVIDIA_SMI_TIMEOUT_SECS: u64 = 5;
+
+// 优先使用 NVML，加载失败时调用 nvidia-smi；没有 NVIDIA 显卡时返回空列表
+pub fn nvidia_metrics() -> Vec<GpuMetrics> {
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    {
+        let gpus = nvml_gpus();
+        if !gpus.is_empty() {
+            return gpus
+                .into_iter()
+                .enumerate()
+                .map(|(index, gpu)| GpuMetrics {
+                    index: index as u32,
+                    name: gpu.name.unwrap_or_else(|| "NVIDIA GPU".to_string()),
+                    vendor: "NVIDIA".to_string(),
+                    utilization_pct: gpu.utilization,
+                    memory_used: gpu.memory_used,
+                    memory_total: gpu.memory_total,
+                    temperature: gpu.temperature,
+                    power_draw: gpu.power_draw,
+                })
+                .collect();
+        }
+    }
+    nvidia_smi_metrics()
+}
+
+fn nvidia_smi_metrics() -> Vec<GpuMetrics> {
+    let mut cmd = Command::new("nvidia-smi");
+    cmd.args([
+        "--query-gpu=index,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw,name",
+        "--format=csv,noheader,nounits",
+    ]);
+    match crate::util::output_with_timeout(&mut cmd, Duration::from_secs(NVIDIA_SMI_TIMEOUT_SECS)) {
+        Ok(out) if out.status.success() => {
+            parse_nvidia_smi_csv(&String::from_utf8_lossy(&out.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+// 每行形如 "0, 35, 1024, 8192, 45, 30.52, NVIDIA GeForce RTX 3070"；
+// 不支持的项为 "[N/A]" / "N/A" / "[Not Supported]"。名称放在最后，避免名称中的逗号打乱列
+fn parse_nvidia_smi_csv(output: &str) -> Vec<GpuMetrics> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.splitn(7, ',').map(str::trim).collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let number = |i: usize| fields[i].parse::<f32>().ok();
+            let mib = |i: usize| fields[i].parse::<u64>().ok().map(|v| v << 20);
+            Some(GpuMetrics {
+                index: fields[0].parse().ok()?,
+                name: fields[6].to_string(),
+                vendor: "NVIDIA".to_string(),
+                utilization_pct: number(1),
+                memory_used: mib(2),
+                memory_total: mib(3),
+                temperature: number(4),
+                power_draw: number(5),
+            })
+        })
+        .collect()
+}
+
+// 使用 NVIDIA 显卡的进程，优先 NVML（含使用率），失败时调用 nvidia-smi（只有计算进程与显存）。
+// 名称可能为空，由调用方补齐；两者都不可用时为 None
+pub fn nvidia_processes() -> Option<Vec<GpuProcess>> {
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    if let Some(processes) = nvml_processes() {
+        return Some(processes);
+    }
+    nvidia_smi_processes()
+}
+
+// 同一进程可能同时出现在计算与图形进程列表中，每块显卡内按 PID 去重，跨显卡累加
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn nvml_processes() -> Option<Vec<GpuProcess>> {
+    use nvml_wrapper::enums::device::UsedGpuMemory;
+    use std::collections::HashMap;
+
+    let nvml = nvml()?;
+    let mut processes: HashMap<u32, GpuProcess> = HashMap::new();
+    for index in 0..nvml.device_count().ok()? {
+        let Ok(device) = nvml.device_by_index(index) else {
+            continue;
+        };
+        // 驱动缓存的最近若干个采样，同一进程取时间最新的 SM 使用率
+        let mut utilization: HashMap<u32, (u64, u32)> = HashMap::new();
+        for sample in device.process_utilization_stats(0u64).unwrap_or_default() {
+            let latest = utilization.entry(sample.pid).or_insert((0, 0));
+            if sample.timestamp >= latest.0 {
+                *latest = (sample.timestamp, sample.sm_util);
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        let running = device
+            .running_compute_processes()
+            .unwrap_or_default()
+            .into_iter()
+            .chain(device.running_graphics_processes().unwrap_or_default());
+        for info in running.filter(|info| seen.insert(info.pid)) {
+            let entry = processes.entry(info.pid).or_insert_with(|| GpuProcess {
+                pid: info.pid,
+                name: String::new(),
+                gpu_percent: None,
+                vram_bytes: None,
+            });
+            // Windows（WDDM）下显存由系统管理，NVML 不报告单个进程的用量
+            if let UsedGpuMemory::Used(bytes) = info.used_gpu_memory {
+                entry.vram_bytes = Some(entry.vram_bytes.unwrap_or(0) + bytes);
+            }
+            if let Some(&(_, util)) = utilization.get(&info.pid) {
+                entry.gpu_percent = Some(entry.gpu_percent.unwrap_or(0.0) + util as f32);
+            }
+        }
+    }
+    Some(processes.into_values().collect())
+}
+
+// 每行形如 "1234, 512, python"，显存单位为 MiB，Windows 上可能为 "[N/A]"；名称放在最后
+fn nvidia_smi_processes() -> Option<Vec<GpuProcess>> {
+    let mut cmd = Command::new("nvidia-smi");
+    cmd.args([
+        "--query-compute-apps=pid,used_memory,process_name",
+        "--format=csv,noheader,nounits",
+    ]);
+    let out =
+        crate::util::output_with_timeout(&mut cmd, Duration::from_secs(NVIDIA_SMI_TIMEOUT_SECS))
+            .ok()
+            .filter(|out| out.status.success())?;
+    Some(
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.splitn(3, ',').map(str::trim).collect();
+                Some(GpuProcess {
+                    pid: fields.first()?.parse().ok()?,
+                    name: fields.get(2).map(|n| n.to_string()).unwrap_or_default(),
+                    gpu_percent: None,
+                    vram_bytes: fields.get(1)?.parse::<u64>().ok().map(|mib| mib << 20),
+                })
+            })
+            .collect(),
+    )
+}
+
+// 单块 NVIDIA 显卡的实时状态，单项读取失败时为 None
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub struct NvmlGpu {
+    // 与 sysfs 一致的 PCI 地址，如 "0000:01:00.0"
+    pub bus_id: Option<String>,
+    pub name: Option<String>,
+    // GPU 使用率（百分比）
+    pub utilization: Option<f32>,
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+    // 摄氏度
+    pub temperature: Option<f32>,
+    // 瓦
+    pub power_draw: Option<f32>,
+}
+
+// NVML 在运行时加载驱动自带的 nvml.dll / libnvidia-ml.so，只初始化一次；
+// 没有 NVIDIA 驱动时为 None
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn nvml() -> Option<&'static Nvml> {
+    static NVML: OnceLock<Option<Nvml>> = OnceLock::new();
+    NVML.get_or_init(|| Nvml::init().ok()).as_ref()
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub fn nvml_gpus() -> Vec<NvmlGpu> {
+    let Some(nvml) = nvml() else {
+        return Vec::new();
+    };
+    let count = nvml.device_count().unwrap_or(0);
+    (0..count)
+        .filter_map(|index| nvml.device_by_index(index).ok())
+        .map(|device| {
+            let memory = device.memory_info().ok();
+            NvmlGpu {
+                bus_id: device
+                    .pci_info()
+                    .ok()
+                    .and_then(|pci| normalize_bus_id(&pci.bus_id)),
+                name: device.name().ok(),
+                utilization: device.utilization_rates().ok().map(|u| u.gpu as f32),
+                memory_used: memory.as_ref().map(|m| m.used),
+                memory_total: memory.as_ref().map(|m| m.total),
+                temperature: device
+                    .temperature(TemperatureSensor::Gpu)
+                    .ok()
+                    .map(|t| t as f32),
+                // power_usage 单位为毫瓦
+                power_draw: device.power_usage().ok().map(|mw| mw as f32 / 1000.0),
+            }
+        })
+        .collect()
+}
+
+// NVIDIA 工具使用 8 位域号（00000000:01:00.0），转成 sysfs 的 0000:01:00.0 形式
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub fn normalize_bus_id(bus_id: &str) -> Option<String> {
+    let (domain, bus) = bus_id.trim().split_once(':')?;
+    let domain = u32::from_str_radix(domain, 16).ok()?;
+    Some(format!("{:04x}:{}", domain, bus.to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_multiple_gpus() {
+        let output = "0, 35, 1024, 8192, 45, 30.52, NVIDIA GeForce RTX 3070\n\
+                      1, 100, 40000, 81920, 71, 298.11, NVIDIA A100-SXM4-80GB\n";
+        let gpus = parse_nvidia_smi_csv(output);
+        assert_eq!(gpus.len(), 2);
+
+        assert_eq!(gpus[0].index, 0);
+        assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 3070");
+        assert_eq!(gpus[0].vendor, "NVIDIA");
+        assert_eq!(gpus[0].utilization_pct, Some(35.0));
+        assert_eq!(gpus[0].memory_used, Some(1024 << 20));
+        assert_eq!(gpus[0].memory_total, Some(8192 << 20));
+        assert_eq!(gpus[0].temperature, Some(45.0));
+        assert_eq!(gpus[0].power_draw, Some(30.52));
+
+        assert_eq!(gpus[1].index, 1);
+        assert_eq!(gpus[1].name, "NVIDIA A100-SXM4-80GB");
+        assert_eq!(gpus[1].utilization_pct, Some(100.0));
+    }
+
+    #[test]
+    fn unsupported_fields_are_none() {
+        let output = "0, [N/A], 512, 4096, N/A, [Not Supported], Quadro P1000, Mobile\n";
+        let gpus = parse_nvidia_smi_csv(output);
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].utilization_pct, None);
+        assert_eq!(gpus[0].memory_used, Some(512 << 20));
+        assert_eq!(gpus[0].temperature, None);
+        assert_eq!(gpus[0].power_draw, None);
+        // 名称中的逗号不影响前面的列
+        assert_eq!(gpus[0].name, "Quadro P1000, Mobile");
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let output = "\nNVIDIA-SMI has failed\nx, 1, 2, 3, 4, 5, bad index\n";
+        assert!(parse_nvidia_smi_csv(output).is_empty());
+    }
+}
@@ -0,0 +1,562 @@
+use crate::error::AppError;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct ThrottleStatus {
+    // 系统当前是否在限制 CPU（热节流 / 节能模式 / 后台限流）
+    is_throttled: Option<bool>,
+    low_power_mode: Option<bool>,
+    // macOS: 本应用是否已关闭 App Nap
+    app_nap_disabled: Option<bool>,
+    // Windows: 系统是否启用了后台限流（Power Throttling）策略。默认即启用，
+    // 只说明系统可能限制后台进程，不代表本应用正在被限流
+    power_throttling_enabled: Option<bool>,
+}
+
+#[tauri::command]
+pub fn get_throttle_status(app: tauri::AppHandle) -> ThrottleStatus {
+    #[cfg(target_os = "macos")]
+    {
+        get_throttle_status_macos(&app.config().identifier)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = app;
+        get_throttle_status_windows()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = app;
+        get_throttle_status_linux()
+    }
+}
+
+// macOS: 为本应用关闭 / 恢复 App Nap，下次启动生效
+#[tauri::command]
+pub fn set_app_nap_disabled(app: tauri::AppHandle, disabled: bool) -> Result<(), AppError> {
+    #[cfg(target_os = "macos")]
+    {
+        let identifier = &app.config().identifier;
+        let status = std::process::Command::new("defaults")
+            .args([
+                "write",
+                identifier,
+                "NSAppSleepDisabled",
+                "-bool",
+                if disabled { "YES" } else { "NO" },
+            ])
+            .status()
+            .map_err(|e| AppError::CommandFailed(format!("defaults: {}", e)))?;
+        if !status.success() {
+            return Err(AppError::CommandFailed(format!("defaults exited with {}", status)));
+        }
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, disabled);
+        Err(AppError::Unsupported("App Nap only exists on macOS".to_string()))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_throttle_status_macos(identifier: &str) -> ThrottleStatus {
+    use std::process::Command;
+
+    let run = |args: &[&str]| -> Option<String> {
+        let out = Command::new(args[0]).args(&args[1..]).output().ok()?;
+        out.status
+            .success()
+            .then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+    };
+    // pmset 输出形如 " lowpowermode         1"，新系统上字段名为 powermode
+    let pmset_value = |text: &str, key: &str| -> Option<u32> {
+        text.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next()? == key).then(|| fields.next()?.parse().ok())?
+        })
+    };
+
+    let low_power_mode = run(&["pmset", "-g"]).and_then(|text| {
+        pmset_value(&text, "lowpowermode")
+            .or_else(|| pmset_value(&text, "powermode"))
+            .map(|v| v == 1)
+    });
+
+    let is_throttled = match (cpu_speed_limited(), low_power_mode) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(false) || b.unwrap_or(false)),
+    };
+
+    let app_nap_disabled = Some(
+        run(&["defaults", "read", identifier, "NSAppSleepDisabled"])
+            .is_some_and(|v| v.trim() == "1"),
+    );
+
+    ThrottleStatus {
+        is_throttled,
+        low_power_mode,
+        app_nap_disabled,
+        power_throttling_enabled: None,
+    }
+}
+
+// CPU 是否因温度被降频，只看时钟限制，不考虑节能模式等策略；平台无法得知时为 None
+pub fn cpu_speed_limited() -> Option<bool> {
+    // pmset -g therm 中 CPU_Speed_Limit 小于 100 表示正在降频
+    #[cfg(target_os = "macos")]
+    {
+        let out = std::process::Command::new("pmset")
+            .args(["-g", "therm"])
+            .output()
+            .ok()
+            .filter(|out| out.status.success())?;
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "CPU_Speed_Limit")
+                    .then(|| value.trim().parse::<u32>().ok().map(|v| v < 100))?
+            })
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn get_throttle_status_windows() -> ThrottleStatus {
+    // Power Throttling 默认开启，只有设置了 PowerThrottlingOff=1 才会全局关闭
+    let output = crate::util::command("reg")
+        .args([
+            "query",
+            r"HKLM\SYSTEM\CurrentControlSet\Control\Power\PowerThrottling",
+            "/v",
+            "PowerThrottlingOff",
+        ])
+        .output();
+    let power_throttling_enabled = match output {
+        Ok(out) if out.status.success() => {
+            let text = String::from_utf8_lossy(&out.stdout);
+            Some(!text.contains("0x1"))
+        }
+        // 值不存在说明未关闭（系统默认）
+        Ok(_) => Some(true),
+        Err(_) => None,
+    };
+
+    // 策略是否启用不能说明本应用是否正被限流，Windows 不提供后者，is_throttled 为 None
+    ThrottleStatus {
+        power_throttling_enabled,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_throttle_status_linux() -> ThrottleStatus {
+    // power-profiles-daemon 的 power-saver 模式视为低功耗模式
+    let low_power_mode = std::process::Command::new("powerprofilesctl")
+        .arg("get")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim() == "power-saver");
+
+    ThrottleStatus {
+        low_power_mode,
+        ..Default::default()
+    }
+}
+
+#[derive(Serialize)]
+pub struct BatteryInfo {
+    name: String,
+    percentage: Option<f32>,
+    // "charging" / "discharging" / "full" / "not_charging" / "unknown"
+    state: String,
+    time_to_empty_secs: Option<u64>,
+    time_to_full_secs: Option<u64>,
+    // 容量单位见 capacity_unit："mWh" 或 "mAh"（取决于平台 / 电池控制器）
+    design_capacity: Option<u64>,
+    full_charge_capacity: Option<u64>,
+    capacity_unit: String,
+    // 当前满充容量占设计容量的百分比
+    health_pct: Option<f32>,
+    cycle_count: Option<u32>,
+    // 当前充放电功率（瓦）
+    power_watts: Option<f32>,
+}
+
+impl BatteryInfo {
+    fn new(name: String, capacity_unit: &str) -> Self {
+        BatteryInfo {
+            name,
+            percentage: None,
+            state: "unknown".to_string(),
+            time_to_empty_secs: None,
+            time_to_full_secs: None,
+            design_capacity: None,
+            full_charge_capacity: None,
+            capacity_unit: capacity_unit.to_string(),
+            health_pct: None,
+            cycle_count: None,
+            power_watts: None,
+        }
+    }
+
+    fn update_health(&mut self) {
+        if let (Some(full), Some(design)) = (self.full_charge_capacity, self.design_capacity) {
+            if design > 0 {
+                self.health_pct = Some((full as f64 / design as f64 * 100.0) as f32);
+            }
+        }
+    }
+}
+
+// 台式机没有电池时返回 None；部分笔记本有两块电池，因此逐块返回列表而不做合并，
+// 只需要一个数值时由前端取第一块。各平台直接读取系统接口（未使用 battery crate），
+// 接口不可用时返回错误而不会 panic
+#[tauri::command]
+pub fn get_battery() -> Result<Option<Vec<BatteryInfo>>, AppError> {
+    #[cfg(target_os = "macos")]
+    let mut batteries = get_batteries_macos()?;
+    #[cfg(target_os = "windows")]
+    let mut batteries = get_batteries_windows()?;
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut batteries = get_batteries_linux();
+
+    if batteries.is_empty() {
+        return Ok(None);
+    }
+    for battery in &mut batteries {
+        battery.update_health();
+    }
+    Ok(Some(batteries))
+}
+
+// 第一块电池的剩余 / 充满时间，如 "2h 34m until full"、"1h 12m remaining"；
+// 系统尚未估算出时间时为 "Estimating…"，没有电池或不在充放电时为 None
+#[tauri::command]
+pub fn get_battery_time_formatted() -> Option<String> {
+    let batteries = get_battery().ok().flatten()?;
+    battery_time_text(batteries.first()?)
+}
+
+fn battery_time_text(battery: &BatteryInfo) -> Option<String> {
+    let format = |secs: Option<u64>, suffix: &str| match secs {
+        Some(secs) => format!("{} {}", crate::util::format_duration(secs, 2), suffix),
+        None => "Estimating…".to_string(),
+    };
+    match battery.state.as_str() {
+        "charging" => Some(format(battery.time_to_full_secs, "until full")),
+        "discharging" => Some(format(battery.time_to_empty_secs, "remaining")),
+        _ => None,
+    }
+}
+
+// 是否正在使用电池供电（任一电池处于放电状态）
+pub fn is_on_battery() -> bool {
+    matches!(
+        get_battery(),
+        Ok(Some(batteries)) if batteries.iter().any(|b| b.state == "discharging")
+    )
+}
+
+// /sys/class/power_supply/BAT*：能量类属性单位为 µWh / µW，电荷类属性单位为 µAh / µA
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn get_batteries_linux() -> Vec<BatteryInfo> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return Vec::new();
+    };
+    let mut batteries: Vec<BatteryInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let read = |name: &str| {
+                std::fs::read_to_string(dir.join(name))
+                    .ok()
+                    .map(|v| v.trim().to_string())
+            };
+            let read_u64 = |name: &str| read(name).and_then(|v| v.parse::<u64>().ok());
+            if read("type")? != "Battery" {
+                return None;
+            }
+
+            // 优先使用能量（µWh），否则退回电荷（µAh）
+            let energy = read_u64("energy_full").is_some();
+            let (now, full, design, rate, unit) = if energy {
+                (
+                    read_u64("energy_now"),
+                    read_u64("energy_full"),
+                    read_u64("energy_full_design"),
+                    read_u64("power_now"),
+                    "mWh",
+                )
+            } else {
+                (
+                    read_u64("charge_now"),
+                    read_u64("charge_full"),
+                    read_u64("charge_full_design"),
+                    read_u64("current_now"),
+                    "mAh",
+                )
+            };
+
+            let mut battery = BatteryInfo::new(entry.file_name().to_string_lossy().into_owned(), unit);
+            battery.percentage = read_u64("capacity").map(|v| v as f32);
+            battery.state = match read("status").as_deref() {
+                Some("Charging") => "charging",
+                Some("Discharging") => "discharging",
+                Some("Full") => "full",
+                Some("Not charging") => "not_charging",
+                _ => "unknown",
+            }
+            .to_string();
+            battery.design_capacity = design.map(|v| v / 1000);
+            battery.full_charge_capacity = full.map(|v| v / 1000);
+            battery.cycle_count = read_u64("cycle_count")
+                .filter(|&v| v > 0)
+                .map(|v| v as u32);
+
+            // 功率：power_now 直接为 µW，电荷模式下用 current_now * voltage_now
+            battery.power_watts = if energy {
+                rate.map(|uw| uw as f32 / 1_000_000.0)
+            } else {
+                rate.zip(read_u64("voltage_now"))
+                    .map(|(ua, uv)| (ua as f64 * uv as f64 / 1e12) as f32)
+            };
+
+            // 剩余时间 = 剩余（或待充）容量 / 当前速率
+            if let (Some(now), Some(rate)) = (now, rate.filter(|&r| r > 0)) {
+                let hours_to_secs = |amount: u64| (amount as f64 / rate as f64 * 3600.0) as u64;
+                match battery.state.as_str() {
+                    "discharging" => battery.time_to_empty_secs = Some(hours_to_secs(now)),
+                    "charging" => {
+                        battery.time_to_full_secs =
+                            full.map(|full| hours_to_secs(full.saturating_sub(now)))
+                    }
+                    _ => {}
+                }
+            }
+            Some(battery)
+        })
+        .collect();
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    batteries
+}
+
+// ioreg -rn AppleSmartBattery 输出形如 `    "CurrentCapacity" = 85`
+// Apple Silicon 上 CurrentCapacity / MaxCapacity 是百分比，实际容量在 AppleRawMaxCapacity（mAh）
+#[cfg(target_os = "macos")]
+fn get_batteries_macos() -> Result<Vec<BatteryInfo>, AppError> {
+    let out = std::process::Command::new("ioreg")
+        .args(["-rn", "AppleSmartBattery"])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("ioreg: {}", e)))?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    if !text.contains("AppleSmartBattery") {
+        return Ok(Vec::new());
+    }
+
+    let value = |key: &str| -> Option<String> {
+        let prefix = format!("\"{}\" = ", key);
+        text.lines()
+            .find_map(|line| line.trim_start().strip_prefix(prefix.as_str()))
+            .map(|v| v.trim().to_string())
+    };
+    // Amperage 为有符号数，但 ioreg 可能以无符号形式输出负值
+    let number = |key: &str| -> Option<i64> {
+        let v = value(key)?;
+        v.parse::<i64>().ok().or_else(|| v.parse::<u64>().ok().map(|u| u as i64))
+    };
+    let flag = |key: &str| value(key).map(|v| v == "Yes");
+
+    let mut battery = BatteryInfo::new("InternalBattery".to_string(), "mAh");
+    if let (Some(current), Some(max)) = (number("CurrentCapacity"), number("MaxCapacity")) {
+        if max > 0 {
+            battery.percentage = Some((current as f64 / max as f64 * 100.0) as f32);
+        }
+    }
+    battery.state = match (flag("FullyCharged"), flag("IsCharging"), flag("ExternalConnected")) {
+        (Some(true), _, _) => "full",
+        (_, Some(true), _) => "charging",
+        (_, _, Some(true)) => "not_charging",
+        (_, _, Some(false)) => "discharging",
+        _ => "unknown",
+    }
+    .to_string();
+
+    // 65535 表示仍在计算
+    let minutes = |key: &str| {
+        number(key)
+            .filter(|&m| m > 0 && m < 65535)
+            .map(|m| m as u64 * 60)
+    };
+    match battery.state.as_str() {
+        "discharging" => battery.time_to_empty_secs = minutes("AvgTimeToEmpty"),
+        "charging" => battery.time_to_full_secs = minutes("AvgTimeToFull"),
+        _ => {}
+    }
+
+    battery.design_capacity = number("DesignCapacity").map(|v| v as u64);
+    battery.full_charge_capacity = number("AppleRawMaxCapacity")
+        .or_else(|| number("MaxCapacity").filter(|&v| v > 100))
+        .map(|v| v as u64);
+    battery.cycle_count = number("CycleCount").map(|v| v as u32);
+    // Voltage 单位 mV，Amperage 单位 mA
+    battery.power_watts = number("Voltage")
+        .zip(number("Amperage"))
+        .map(|(mv, ma)| (mv as f64 * ma.unsigned_abs() as f64 / 1_000_000.0) as f32);
+
+    Ok(vec![battery])
+}
+
+// Win32_Battery 只有电量和状态，容量、循环次数和充放电功率来自 root\wmi 下的电池类（单位 mWh / mW）
+#[cfg(target_os = "windows")]
+const WINDOWS_BATTERY_QUERY: &str = r#"
+$b = @(Get-CimInstance Win32_Battery)
+if ($b.Count -eq 0) { return }
+$static = @(Get-CimInstance -Namespace root\wmi BatteryStaticData -ErrorAction SilentlyContinue)
+$full = @(Get-CimInstance -Namespace root\wmi BatteryFullChargedCapacity -ErrorAction SilentlyContinue)
+$cycle = @(Get-CimInstance -Namespace root\wmi BatteryCycleCount -ErrorAction SilentlyContinue)
+$status = @(Get-CimInstance -Namespace root\wmi BatteryStatus -ErrorAction SilentlyContinue)
+@(for ($i = 0; $i -lt $b.Count; $i++) {
+    [PSCustomObject]@{
+        Name = $b[$i].Name
+        Charge = $b[$i].EstimatedChargeRemaining
+        Status = $b[$i].BatteryStatus
+        RunTime = $b[$i].EstimatedRunTime
+        DesignCapacity = $static[$i].DesignedCapacity
+        FullChargedCapacity = $full[$i].FullChargedCapacity
+        CycleCount = $cycle[$i].CycleCount
+        RemainingCapacity = $status[$i].RemainingCapacity
+        ChargeRate = $status[$i].ChargeRate
+        DischargeRate = $status[$i].DischargeRate
+    }
+}) | ConvertTo-Json
+"#;
+
+#[cfg(target_os = "windows")]
+fn get_batteries_windows() -> Result<Vec<BatteryInfo>, AppError> {
+    let out = crate::util::command("powershell")
+        .args(["-NoProfile", "-Command", WINDOWS_BATTERY_QUERY])
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("powershell: {}", e)))?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    // 没有电池时脚本不输出任何内容
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let json: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| AppError::Parse(format!("Win32_Battery JSON: {}", e)))?;
+    let items = match json {
+        serde_json::Value::Array(items) => items,
+        item => vec![item],
+    };
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            let number = |key: &str| item.get(key).and_then(|v| v.as_u64());
+            let name = item
+                .get("Name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Battery")
+                .to_string();
+            let mut battery = BatteryInfo::new(name, "mWh");
+            battery.percentage = number("Charge").map(|v| v as f32);
+            // BatteryStatus：1 放电，2 接通电源，3 充满，6~9 充电
+            battery.state = match number("Status") {
+                Some(1) => "discharging",
+                Some(2) => "not_charging",
+                Some(3) => "full",
+                Some(6..=9) => "charging",
+                _ => "unknown",
+            }
+            .to_string();
+            battery.design_capacity = number("DesignCapacity").filter(|&v| v > 0);
+            battery.full_charge_capacity = number("FullChargedCapacity").filter(|&v| v > 0);
+            battery.cycle_count = number("CycleCount").filter(|&v| v > 0).map(|v| v as u32);
+
+            let charge_rate = number("ChargeRate").filter(|&v| v > 0);
+            let discharge_rate = number("DischargeRate").filter(|&v| v > 0);
+            battery.power_watts = charge_rate
+                .or(discharge_rate)
+                .map(|mw| mw as f32 / 1000.0);
+
+            match battery.state.as_str() {
+                // EstimatedRunTime 单位为分钟，71582788 表示接通电源 / 未知
+                "discharging" => {
+                    battery.time_to_empty_secs = number("RunTime")
+                        .filter(|&m| m < 71_582_788)
+                        .map(|m| m * 60)
+                }
+                "charging" => {
+                    battery.time_to_full_secs = battery
+                        .full_charge_capacity
+                        .zip(number("RemainingCapacity"))
+                        .zip(charge_rate)
+                        .map(|((full, now), rate)| {
+                            (full.saturating_sub(now) as f64 / rate as f64 * 3600.0) as u64
+                        })
+                }
+                _ => {}
+            }
+            battery
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn battery(state: &str, to_full: Option<u64>, to_empty: Option<u64>) -> BatteryInfo {
+        let mut battery = BatteryInfo::new("BAT0".to_string(), "mWh");
+        battery.state = state.to_string();
+        battery.time_to_full_secs = to_full;
+        battery.time_to_empty_secs = to_empty;
+        battery
+    }
+
+    #[test]
+    fn battery_time_zero_minutes() {
+        assert_eq!(
+            battery_time_text(&battery("discharging", None, Some(0))).as_deref(),
+            Some("<1m remaining")
+        );
+        assert_eq!(
+            battery_time_text(&battery("charging", Some(45), None)).as_deref(),
+            Some("<1m until full")
+        );
+    }
+
+    #[test]
+    fn battery_time_over_a_day() {
+        let secs = 27 * 3600 + 20 * 60;
+        assert_eq!(
+            battery_time_text(&battery("discharging", None, Some(secs))).as_deref(),
+            Some("1d 3h remaining")
+        );
+        assert_eq!(
+            battery_time_text(&battery("charging", Some(2 * 3600 + 34 * 60), None)).as_deref(),
+            Some("2h 34m until full")
+        );
+    }
+
+    #[test]
+    fn battery_time_unknown() {
+        // 系统尚未估算出时间
+        assert_eq!(
+            battery_time_text(&battery("charging", None, None)).as_deref(),
+            Some("Estimating…")
+        );
+        // 不在充放电时没有剩余时间
+        assert_eq!(battery_time_text(&battery("full", None, None)), None);
+        assert_eq!(
+            battery_time_text(&battery("unknown", Some(60), Some(60))),
+            None
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use crate::error::AppError;
+use crate::state::SysState;
+use serde::Serialize;
+use sysinfo::{CpuRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, Users};
+use tauri::{AppHandle, Manager, State};
+
+#[derive(Serialize)]
+pub struct ProcessInfo {
+    pid: u32,
+    parent_pid: Option<u32>,
+    name: String,
+    exe: Option<String>,
+    // 以单核为 100%，多线程进程可能超过 100%
+    cpu_usage: f32,
+    // 以整机为 100%（cpu_usage / 逻辑核心数）
+    cpu_usage_normalized: f32,
+    memory: u64,
+    virtual_memory: u64,
+    status: String,
+    // 进程启动时间（Unix 时间戳，秒）
+    start_time: u64,
+}
+
+// 首次调用时要等待一个 CPU 采样间隔，在阻塞线程中执行
+#[tauri::command]
+pub async fn list_processes(
+    app: AppHandle,
+    sort_by: String,
+    limit: usize,
+) -> Result<Vec<ProcessInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_processes(&app.state::<SysState>(), &sort_by, limit)
+    })
+    .await
+    .map_err(|e| AppError::CommandFailed(format!("process list task: {}", e)))
+}
+
+// list_processes 的别名
+#[tauri::command]
+pub async fn get_processes(
+    app: AppHandle,
+    sort_by: String,
+    limit: usize,
+) -> Result<Vec<ProcessInfo>, AppError> {
+    list_processes(app, sort_by, limit).await
+}
+
+fn collect_processes(state: &SysState, sort_by: &str, limit: usize) -> Vec<ProcessInfo> {
+    // CPU 使用率需要两次采样之间的差值，共享状态里没有上一次刷新时先建立基线
+    {
+        let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+        if sys.processes_refreshed_at.is_none() {
+            if sys.refresh_processes() == 0 {
+                return Vec::new();
+            }
+            drop(sys);
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        }
+    }
+
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    if sys.refresh_processes() == 0 {
+        return Vec::new();
+    }
+
+    if sys.system.cpus().is_empty() {
+        sys.system.refresh_cpu_list(CpuRefreshKind::new());
+    }
+    let cores = sys.system.cpus().len().max(1) as f32;
+
+    let mut processes: Vec<ProcessInfo> = sys
+        .system
+        .processes()
+        .values()
+        .map(|p| ProcessInfo {
+            pid: p.pid().as_u32(),
+            parent_pid: p.parent().map(|pid| pid.as_u32()),
+            name: p.name().to_string_lossy().into_owned(),
+            exe: p.exe().map(|path| path.to_string_lossy().into_owned()),
+            cpu_usage: p.cpu_usage(),
+            cpu_usage_normalized: p.cpu_usage() / cores,
+            memory: p.memory(),
+            virtual_memory: p.virtual_memory(),
+            status: p.status().to_string(),
+            start_time: p.start_time(),
+        })
+        .collect();
+
+    sort_processes(&mut processes, sort_by);
+
+    if limit > 0 {
+        processes.truncate(limit);
+    }
+    processes
+}
+
+// 本应用自身的资源占用，供界面显示监控工具本身的开销
+#[derive(Serialize)]
+pub struct SelfUsage {
+    pid: u32,
+    // 以单核为 100%，为与上一次刷新之间的平均值
+    cpu_usage: f32,
+    cpu_usage_normalized: f32,
+    // 常驻内存（RSS）
+    memory: u64,
+    // 仅 Linux 提供（/proc/<pid>/task），其他平台为 None
+    threads: Option<usize>,
+}
+
+// 只刷新本进程，不触碰进程表中的其他条目
+#[tauri::command]
+pub fn get_self_usage(state: State<'_, SysState>) -> Result<SelfUsage, AppError> {
+    let pid = Pid::from_u32(std::process::id());
+    let refresh_kind = ProcessRefreshKind::new().with_cpu().with_memory();
+
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    // 进程表里还没有本进程时先建立 CPU 基线
+    if sys.system.process(pid).is_none() {
+        sys.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            refresh_kind,
+        );
+        drop(sys);
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    }
+    sys.system
+        .refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), false, refresh_kind);
+
+    if sys.system.cpus().is_empty() {
+        sys.system.refresh_cpu_list(CpuRefreshKind::new());
+    }
+    let cores = sys.system.cpus().len().max(1) as f32;
+    let process = sys
+        .system
+        .process(pid)
+        .ok_or(AppError::ProcessNotFound(pid.as_u32()))?;
+    Ok(SelfUsage {
+        pid: pid.as_u32(),
+        cpu_usage: process.cpu_usage(),
+        cpu_usage_normalized: process.cpu_usage() / cores,
+        memory: process.memory(),
+        threads: process.tasks().map(|tasks| tasks.len()),
+    })
+}
+
+// 支持 "cpu" / "memory" / "name"，未知的排序方式按 pid 排序
+fn sort_processes(processes: &mut [ProcessInfo], sort_by: &str) {
+    match sort_by {
+        "cpu" => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+        "memory" => processes.sort_by_key(|p| std::cmp::Reverse(p.memory)),
+        "name" => processes.sort_by_cached_key(|p| p.name.to_lowercase()),
+        _ => processes.sort_by_key(|p| p.pid),
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProcessDetail {
+    pid: u32,
+    parent_pid: Option<u32>,
+    name: String,
+    exe: Option<String>,
+    cmd: Vec<String>,
+    // 环境变量条数（不返回内容，避免泄露敏感信息）
+    environ_count: usize,
+    // 无权限读取时为 None（macOS / Windows 上的其他用户进程）
+    cwd: Option<String>,
+    user_id: Option<String>,
+    user_name: Option<String>,
+    // 仅 Linux 可获取线程数
+    threads: Option<usize>,
+    memory: ProcessMemory,
+    // 累计 CPU 时间（毫秒），仅 Linux 可获取
+    cpu_time_ms: Option<u64>,
+    status: String,
+    start_time: u64,
+    run_time: u64,
+    children: Vec<u32>,
+}
+
+#[derive(Serialize)]
+pub struct ProcessMemory {
+    resident: u64,
+    virtual_memory: u64,
+    // 以下字段来自 /proc/<pid>/status，其他平台为 None
+    anonymous: Option<u64>,
+    file_backed: Option<u64>,
+    swap: Option<u64>,
+}
+
+#[tauri::command]
+pub fn get_process_detail(pid: u32) -> Result<ProcessDetail, AppError> {
+    let target = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[target]),
+        true,
+        ProcessRefreshKind::everything(),
+    );
+    // 列表与详情之间进程可能已经退出
+    let Some(process) = sys.process(target) else {
+        return Err(AppError::ProcessNotFound(pid));
+    };
+
+    let user_id = process.user_id().cloned();
+    let user_name = user_id.as_ref().and_then(|uid| {
+        Users::new_with_refreshed_list()
+            .get_user_by_id(uid)
+            .map(|user| user.name().to_string())
+    });
+    #[cfg(target_os = "linux")]
+    let proc_stats = read_proc_stats(pid);
+
+    let mut detail = ProcessDetail {
+        pid,
+        parent_pid: process.parent().map(|p| p.as_u32()),
+        name: process.name().to_string_lossy().into_owned(),
+        exe: process.exe().map(|path| path.to_string_lossy().into_owned()),
+        cmd: process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect(),
+        environ_count: process.environ().len(),
+        cwd: process.cwd().map(|path| path.to_string_lossy().into_owned()),
+        user_id: user_id.map(|uid| (*uid).to_string()),
+        user_name,
+        threads: process.tasks().map(|tasks| tasks.len()),
+        memory: ProcessMemory {
+            resident: process.memory(),
+            virtual_memory: process.virtual_memory(),
+            anonymous: None,
+            file_backed: None,
+            swap: None,
+        },
+        cpu_time_ms: None,
+        status: process.status().to_string(),
+        start_time: process.start_time(),
+        run_time: process.run_time(),
+        children: Vec::new(),
+    };
+    #[cfg(target_os = "linux")]
+    {
+        detail.memory.anonymous = proc_stats.anonymous;
+        detail.memory.file_backed = proc_stats.file_backed;
+        detail.memory.swap = proc_stats.swap;
+        detail.cpu_time_ms = proc_stats.cpu_time_ms;
+    }
+
+    // 子进程需要其他进程的父子关系，只刷新最基本的信息
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    detail.children = sys
+        .processes()
+        .values()
+        .filter(|p| p.parent() == Some(target))
+        .map(|p| p.pid().as_u32())
+        .collect();
+    detail.children.sort_unstable();
+
+    Ok(detail)
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct ProcStats {
+    anonymous: Option<u64>,
+    file_backed: Option<u64>,
+    swap: Option<u64>,
+    cpu_time_ms: Option<u64>,
+}
+
+// 读取 /proc/<pid>/status 的内存细分与 /proc/<pid>/stat 的 utime + stime
+#[cfg(target_os = "linux")]
+fn read_proc_stats(pid: u32) -> ProcStats {
+    let mut stats = ProcStats::default();
+
+    if let Ok(status) = std::fs::read_to_string(format!("/proc/{}/status", pid)) {
+        for line in status.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            // 形如 "RssAnon:	    1234 kB"
+            let kb = value
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|v| v * 1024);
+            match key {
+                "RssAnon" => stats.anonymous = kb,
+                "RssFile" => stats.file_backed = kb,
+                "VmSwap" => stats.swap = kb,
+                _ => {}
+            }
+        }
+    }
+
+    // 节拍频率由内核配置决定，不能假定为 100Hz
+    // SAFETY: sysconf 只查询系统配置，没有指针参数
+    let clock_ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if clock_ticks > 0 {
+        stats.cpu_time_ms = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .ok()
+            .and_then(|stat| stat_cpu_ticks(&stat))
+            .map(|ticks| ticks * 1000 / clock_ticks as u64);
+    }
+
+    stats
+}
+
+// /proc/<pid>/stat 中 utime + stime 之和（单位为时钟节拍）。
+// 进程名可能包含空格和括号，从最后一个 ')' 之后开始按空格切分，之后第 12、13 个字段为 utime、stime
+#[cfg(target_os = "linux")]
+fn stat_cpu_ticks(stat: &str) -> Option<u64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let ticks = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok());
+    Some(ticks(11)? + ticks(12)?)
+}
+
+#[tauri::command]
+pub fn kill_process(pid: u32, force: bool) -> Result<bool, AppError> {
+    // 不允许结束自身以及 0 / 1 号进程（idle / init）
+    if pid == 0 || pid == 1 || pid == std::process::id() {
+        return Err(AppError::PermissionDenied(format!(
+            "refusing to kill protected process {}",
+            pid
+        )));
+    }
+
+    let target = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[target]),
+        true,
+        ProcessRefreshKind::new(),
+    );
+    let Some(process) = sys.process(target) else {
+        return Err(AppError::ProcessNotFound(pid));
+    };
+
+    // Unix 上 force 发送 SIGKILL，否则发送 SIGTERM 让进程自行退出
+    #[cfg(unix)]
+    {
+        let signal = if force { Signal::Kill } else { Signal::Term };
+        if process.kill_with(signal).unwrap_or(false) {
+            return Ok(true);
+        }
+        // sysinfo 只返回 bool，失败原因保留在 errno 中
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EPERM) => Err(AppError::PermissionDenied(format!("kill {}: {}", pid, err))),
+            // 刷新之后进程已经退出
+            Some(libc::ESRCH) => Err(AppError::ProcessNotFound(pid)),
+            _ => Ok(false),
+        }
+    }
+    // Windows 没有信号：force 时 sysinfo 调用 taskkill /F，否则用 taskkill 请求窗口关闭
+    #[cfg(not(unix))]
+    {
+        if force {
+            if process.kill_with(Signal::Kill).unwrap_or(false) {
+                return Ok(true);
+            }
+            // 进程仍存在时强制结束失败基本是权限不足
+            return Err(AppError::PermissionDenied(format!(
+                "failed to terminate process {}, administrator rights may be required",
+                pid
+            )));
+        }
+        let out = crate::util::command("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .output()
+            .map_err(|e| AppError::CommandFailed(format!("taskkill: {}", e)))?;
+        // 没有窗口的进程无法被正常关闭，此时返回 false 由调用方决定是否强制结束
+        Ok(out.status.success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn stat_cpu_ticks_skips_process_name() {
+        let stat = "1234 (Web Content (x)) S 1 1234 1234 0 -1 4194560 100 0 0 0 \
+                    250 50 0 0 20 0 30 0 1000 0 0";
+        assert_eq!(stat_cpu_ticks(stat), Some(300));
+        assert_eq!(stat_cpu_ticks("1234 (truncated) S 1"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kill_process_terminates_child() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+
+        assert!(matches!(kill_process(pid, false), Ok(true)));
+        let status = child.wait().expect("wait for child");
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+        // 已回收的进程不再存在
+        assert!(matches!(
+            kill_process(pid, true),
+            Err(AppError::ProcessNotFound(p)) if p == pid
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn kill_process_terminates_child() {
+        let mut child = std::process::Command::new("ping")
+            .args(["-n", "30", "127.0.0.1"])
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .expect("spawn ping");
+        let pid = child.id();
+
+        // 控制台程序没有窗口，只能强制结束
+        assert!(matches!(kill_process(pid, true), Ok(true)));
+        assert!(!child.wait().expect("wait for child").success());
+        assert!(matches!(
+            kill_process(pid, true),
+            Err(AppError::ProcessNotFound(p)) if p == pid
+        ));
+    }
+}
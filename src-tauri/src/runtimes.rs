@@ -0,0 +1,173 @@
+use crate::error::AppError;
+use crate::util::output_with_timeout;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+// 单个探测命令的超时时间
+const PROBE_TIMEOUT_MS: u64 = 2000;
+
+#[derive(Serialize)]
+pub struct RuntimeInfo {
+    name: String,
+    version: String,
+    path: Option<String>,
+}
+
+// 每个探测最多等待 PROBE_TIMEOUT_MS，在阻塞线程中执行
+#[tauri::command]
+pub async fn list_runtimes() -> Result<Vec<RuntimeInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(collect_runtimes)
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("runtime probe task: {}", e)))
+}
+
+fn collect_runtimes() -> Vec<RuntimeInfo> {
+    // 各探测互不依赖，并行执行以免多个慢命令叠加
+    std::thread::scope(|s| {
+        let python = s.spawn(probe_python);
+        let java = s.spawn(|| probe_simple("Java", "java", &["-version"]));
+        let node = s.spawn(|| probe_simple("Node.js", "node", &["--version"]));
+        let dotnet = s.spawn(probe_dotnet);
+
+        let mut runtimes = Vec::new();
+        for handle in [python, java, node] {
+            if let Ok(Some(info)) = handle.join() {
+                runtimes.push(info);
+            }
+        }
+        if let Ok(list) = dotnet.join() {
+            runtimes.extend(list);
+        }
+        runtimes
+    })
+}
+
+// 部分系统只有 python3，部分（如 Windows）只有 python
+fn probe_python() -> Option<RuntimeInfo> {
+    probe_simple("Python", "python3", &["--version"])
+        .or_else(|| probe_simple("Python", "python", &["--version"]))
+}
+
+fn probe_simple(name: &str, program: &str, args: &[&str]) -> Option<RuntimeInfo> {
+    let path = find_in_path(program)?;
+    let text = run_probe(&path, args)?;
+    Some(RuntimeInfo {
+        name: name.to_string(),
+        version: parse_version(&text)?,
+        path: Some(path.to_string_lossy().into_owned()),
+    })
+}
+
+// dotnet --list-runtimes 每行一个运行时：
+// Microsoft.NETCore.App 8.0.1 [/usr/share/dotnet/shared/Microsoft.NETCore.App]
+fn probe_dotnet() -> Vec<RuntimeInfo> {
+    let Some(path) = find_in_path("dotnet") else {
+        return Vec::new();
+    };
+    let Some(text) = run_probe(&path, &["--list-runtimes"]) else {
+        return Vec::new();
+    };
+    parse_dotnet_runtimes(&text)
+}
+
+fn parse_dotnet_runtimes(text: &str) -> Vec<RuntimeInfo> {
+    text.lines()
+        .filter_map(|line| {
+            let (head, dir) = match line.split_once('[') {
+                Some((head, rest)) => (head, Some(rest.trim_end_matches(']').trim())),
+                None => (line, None),
+            };
+            let mut parts = head.split_whitespace();
+            let framework = parts.next()?;
+            let version = parts.next()?;
+            Some(RuntimeInfo {
+                name: format!(".NET ({})", framework),
+                version: version.to_string(),
+                path: dir.map(|d| d.to_string()),
+            })
+        })
+        .collect()
+}
+
+// 合并 stdout 与 stderr：java -version 和旧版 python --version 输出到 stderr
+fn run_probe(path: &Path, args: &[&str]) -> Option<String> {
+    let out = output_with_timeout(
+        Command::new(path).args(args),
+        Duration::from_millis(PROBE_TIMEOUT_MS),
+    )
+    .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+    text.push('\n');
+    text.push_str(&String::from_utf8_lossy(&out.stderr));
+    Some(text)
+}
+
+// 取第一个以数字开头的词作为版本号，兼容以下格式：
+// Python 3.11.4 / v20.11.0 / openjdk version "17.0.2" 2022-01-18
+fn parse_version(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches('"').trim_start_matches('v'))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| word.to_string())
+}
+
+// 在 PATH 中查找可执行文件，找不到则视为未安装
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    let exe = if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    };
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(&exe))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_formats() {
+        assert_eq!(parse_version("Python 3.11.4\n").as_deref(), Some("3.11.4"));
+        assert_eq!(parse_version("v20.11.0\n").as_deref(), Some("20.11.0"));
+        // run_probe 先拼接 stdout 再拼接 stderr，java -version 只写 stderr
+        let java = "\nopenjdk version \"17.0.2\" 2022-01-18\n\
+                    OpenJDK Runtime Environment (build 17.0.2+8-86)\n\
+                    OpenJDK 64-Bit Server VM (build 17.0.2+8-86, mixed mode, sharing)\n";
+        assert_eq!(parse_version(java).as_deref(), Some("17.0.2"));
+        let java8 =
+            "\njava version \"1.8.0_392\"\nJava(TM) SE Runtime Environment (build 1.8.0_392-b08)\n";
+        assert_eq!(parse_version(java8).as_deref(), Some("1.8.0_392"));
+        assert_eq!(parse_version("\n"), None);
+    }
+
+    #[test]
+    fn parse_dotnet_list_runtimes() {
+        let output = "Microsoft.AspNetCore.App 8.0.1 [/usr/share/dotnet/shared/Microsoft.AspNetCore.App]\n\
+                      Microsoft.NETCore.App 6.0.26 [C:\\Program Files\\dotnet\\shared\\Microsoft.NETCore.App]\n\
+                      Microsoft.WindowsDesktop.App 8.0.1\n\
+                      \n";
+        let runtimes = parse_dotnet_runtimes(output);
+        assert_eq!(runtimes.len(), 3);
+        assert_eq!(runtimes[0].name, ".NET (Microsoft.AspNetCore.App)");
+        assert_eq!(runtimes[0].version, "8.0.1");
+        assert_eq!(
+            runtimes[0].path.as_deref(),
+            Some("/usr/share/dotnet/shared/Microsoft.AspNetCore.App")
+        );
+        assert_eq!(runtimes[1].version, "6.0.26");
+        assert_eq!(
+            runtimes[1].path.as_deref(),
+            Some("C:\\Program Files\\dotnet\\shared\\Microsoft.NETCore.App")
+        );
+        assert_eq!(runtimes[2].name, ".NET (Microsoft.WindowsDesktop.App)");
+        assert_eq!(runtimes[2].path, None);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::AppError;
+use base64::Engine;
+
+// 截取一个显示器，返回 base64 编码的 PNG（不含 data: 前缀）。
+// display_index 为系统枚举显示器的顺序（Linux 上与 get_displays 一致），缺省为主显示器。
+// 截图可能耗时数秒，在阻塞线程中执行，不占用主线程
+#[tauri::command]
+pub async fn capture_screenshot(display_index: Option<usize>) -> Result<String, AppError> {
+    let png = tauri::async_runtime::spawn_blocking(move || capture_png(display_index))
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("screenshot task: {}", e)))??;
+    Ok(base64::engine::general_purpose::STANDARD.encode(png))
+}
+
+// 没有指定下标时取主显示器，无法判断主显示器时取第一个
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+fn select_display<T>(
+    displays: Vec<T>,
+    index: Option<usize>,
+    is_primary: impl Fn(&T) -> bool,
+) -> Result<T, AppError> {
+    let count = displays.len();
+    let selected = match index {
+        Some(i) => displays.into_iter().nth(i),
+        None => {
+            let primary = displays.iter().position(&is_primary).unwrap_or(0);
+            displays.into_iter().nth(primary)
+        }
+    };
+    selected.ok_or_else(|| match index {
+        Some(i) => AppError::Unsupported(format!(
+            "display index {} out of range ({} displays)",
+            i, count
+        )),
+        None => AppError::Unsupported("no display to capture".to_string()),
+    })
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn capture_png(display_index: Option<usize>) -> Result<Vec<u8>, AppError> {
+    use xcap::image::ImageFormat;
+
+    #[cfg(target_os = "macos")]
+    ensure_screen_capture_access()?;
+
+    let monitors = xcap::Monitor::all()
+        .map_err(|e| AppError::CommandFailed(format!("list displays: {}", e)))?;
+    let monitor = select_display(monitors, display_index, |m| m.is_primary().unwrap_or(false))?;
+    let image = monitor
+        .capture_image()
+        .map_err(|e| AppError::CommandFailed(format!("capture display: {}", e)))?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| AppError::Io(format!("encode png: {}", e)))?;
+    Ok(png.into_inner())
+}
+
+// 没有“屏幕录制”权限时系统不会报错，只返回桌面壁纸，因此先检查权限。
+// 首次请求会弹出系统对话框，授权后需要重启应用才生效
+#[cfg(target_os = "macos")]
+fn ensure_screen_capture_access() -> Result<(), AppError> {
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    // SAFETY: 两个函数都没有参数，只查询 / 请求当前进程的权限
+    if unsafe { CGPreflightScreenCaptureAccess() } {
+        return Ok(());
+    }
+    unsafe { CGRequestScreenCaptureAccess() };
+    Err(AppError::PermissionDenied(
+        "screen recording is not allowed; enable this app in System Settings > \
+         Privacy & Security > Screen Recording, then restart it"
+            .to_string(),
+    ))
+}
+
+// 截图工具的执行时间上限，Wayland 门户可能等待用户确认
+#[cfg(target_os = "linux")]
+const CAPTURE_TIMEOUT_SECS: u64 = 10;
+
+// Linux 调用系统截图工具：Wayland 使用 grim（wlroots 系合成器），X11 使用 ImageMagick 的 import
+#[cfg(target_os = "linux")]
+fn capture_png(display_index: Option<usize>) -> Result<Vec<u8>, AppError> {
+    let png = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let displays = crate::displays::wayland_outputs();
+        // 没有 wayland-info 时无法区分输出，只能截取整个桌面
+        if displays.is_empty() && display_index.is_none() {
+            run_capture("grim", &["-"])?
+        } else {
+            let display = select_display(displays, display_index, |d| d.is_primary)?;
+            run_capture("grim", &["-o", &display.id, "-"])?
+        }
+    } else {
+        // 与 get_displays 使用同一份 xrandr --query 输出，下标一致
+        let displays = crate::displays::xrandr_displays();
+        let display = select_display(displays, display_index, |d| d.is_primary)?;
+        let geometry = display.x11_geometry().ok_or_else(|| {
+            AppError::Unsupported(format!(
+                "display {} is connected but turned off",
+                display.id
+            ))
+        })?;
+        run_capture("import", &["-window", "root", "-crop", &geometry, "png:-"])?
+    };
+    if !png.starts_with(b"\x89PNG") {
+        return Err(AppError::Parse(
+            "screenshot tool did not return a PNG".to_string(),
+        ));
+    }
+    Ok(png)
+}
+
+#[cfg(target_os = "linux")]
+fn run_capture(program: &str, args: &[&str]) -> Result<Vec<u8>, AppError> {
+    use std::io::ErrorKind;
+    use std::time::Duration;
+
+    let mut cmd = std::process::Command::new(program);
+    cmd.args(args);
+    let out = crate::util::output_with_timeout(&mut cmd, Duration::from_secs(CAPTURE_TIMEOUT_SECS))
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::Unsupported(format!(
+                "{} is not installed; screenshots need grim (Wayland) or ImageMagick (X11)",
+                program
+            )),
+            _ => AppError::io(program, e),
+        })?;
+    if !out.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{}: {}",
+            program,
+            String::from_utf8_lossy(&out.stderr).trim()
+        )));
+    }
+    Ok(out.stdout)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn capture_png(display_index: Option<usize>) -> Result<Vec<u8>, AppError> {
+    let _ = display_index;
+    Err(AppError::Unsupported(
+        "screenshots are not supported on this platform".to_string(),
+    ))
+}
@@ -0,0 +1,404 @@
+use crate::error::AppError;
+use crate::state::SysState;
+use serde::Serialize;
+use tauri::State;
+
+// 传感器未报告临界温度时使用的默认值（℃）
+const DEFAULT_CRITICAL_TEMP: f32 = 100.0;
+// 距临界温度的余量低于该百分比即视为正在热节流
+const DEFAULT_THROTTLE_HEADROOM_PCT: f32 = 5.0;
+
+#[derive(Serialize)]
+pub struct ComponentTemp {
+    // 系统原始标签（Apple Silicon 上是 SMC / HID 传感器名）
+    label: String,
+    // 便于展示的名称，无法识别时与 label 相同
+    name: String,
+    temperature: f32,
+    max: Option<f32>,
+    critical: Option<f32>,
+}
+
+#[derive(Serialize)]
+pub struct SensorReport {
+    // 虚拟机等环境读不到任何传感器时为 false
+    supported: bool,
+    sensors: Vec<ComponentTemp>,
+}
+
+#[tauri::command]
+pub fn get_temperatures(state: State<'_, SysState>) -> Vec<ComponentTemp> {
+    read_temperatures(&state)
+}
+
+#[tauri::command]
+pub fn get_sensors(state: State<'_, SysState>) -> SensorReport {
+    let sensors = read_temperatures(&state);
+    SensorReport {
+        supported: !sensors.is_empty(),
+        sensors,
+    }
+}
+
+#[derive(Serialize)]
+pub struct ThermalStatus {
+    cpu_temp: Option<f32>,
+    gpu_temp: Option<f32>,
+    // (名称, 温度)
+    hottest_sensor: Option<(String, f32)>,
+    // 系统报告正在限流，或温度余量低于阈值
+    throttling: bool,
+    // 各传感器距其临界温度的余量（百分比）中的最小值
+    headroom_pct: Option<f32>,
+}
+
+// 汇总温度与限流状态，给出“离热节流还有多远”的单一指标
+// critical_temp：传感器未报告临界温度时使用的值；throttle_headroom_pct：判定为节流的余量阈值
+#[tauri::command]
+pub fn get_thermal_status(
+    state: State<'_, SysState>,
+    critical_temp: Option<f32>,
+    throttle_headroom_pct: Option<f32>,
+) -> ThermalStatus {
+    let critical_temp = critical_temp.unwrap_or(DEFAULT_CRITICAL_TEMP);
+    let throttle_headroom_pct = throttle_headroom_pct.unwrap_or(DEFAULT_THROTTLE_HEADROOM_PCT);
+    let temps = read_temperatures(&state);
+
+    let hottest_of = |keywords: &[&str]| {
+        temps
+            .iter()
+            .filter(|t| {
+                let label = format!("{} {}", t.label, t.name).to_ascii_lowercase();
+                keywords.iter().any(|k| label.contains(k))
+            })
+            .map(|t| t.temperature)
+            .reduce(f32::max)
+    };
+    let cpu_temp = hottest_of(&[
+        "cpu", "package", "core", "tctl", "tdie", "k10temp", "coretemp",
+    ]);
+    let gpu_temp = hottest_of(&["gpu", "amdgpu", "nouveau", "radeon", "junction"]);
+    let hottest_sensor = temps
+        .iter()
+        .max_by(|a, b| a.temperature.total_cmp(&b.temperature))
+        .map(|t| (t.name.clone(), t.temperature));
+
+    let headroom_pct = temps
+        .iter()
+        .filter_map(|t| {
+            let critical = t.critical.filter(|&c| c > 0.0).unwrap_or(critical_temp);
+            (critical > 0.0).then(|| ((critical - t.temperature) / critical * 100.0).max(0.0))
+        })
+        .reduce(f32::min);
+    // 只依据温度余量和实际的降频信号，不使用节能 / 后台限流等策略开关
+    let throttling = crate::power::cpu_speed_limited() == Some(true)
+        || headroom_pct.is_some_and(|h| h <= throttle_headroom_pct);
+
+    ThermalStatus {
+        cpu_temp,
+        gpu_temp,
+        hottest_sensor,
+        throttling,
+        headroom_pct,
+    }
+}
+
+// 当前所有传感器中的最高温度
+pub fn max_temperature(state: &SysState) -> Option<f32> {
+    read_temperatures(state)
+        .iter()
+        .map(|t| t.temperature)
+        .reduce(f32::max)
+}
+
+fn read_temperatures(state: &SysState) -> Vec<ComponentTemp> {
+    let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+    sys.refresh_components();
+    // 读不到数据的传感器会返回 NaN，直接过滤掉
+    let finite = |v: f32| Some(v).filter(|v| v.is_finite());
+    let temps: Vec<ComponentTemp> = sys
+        .components
+        .list()
+        .iter()
+        .filter(|c| c.temperature().is_finite())
+        .map(|c| ComponentTemp {
+            label: c.label().to_string(),
+            name: friendly_sensor_name(c.label()),
+            temperature: c.temperature(),
+            max: finite(c.max()),
+            critical: c.critical().and_then(finite),
+        })
+        .collect();
+    drop(sys);
+
+    #[cfg(target_os = "macos")]
+    if temps.is_empty() {
+        return get_temperatures_powermetrics();
+    }
+    temps
+}
+
+// sysinfo 在 macOS 上经常读不到 SMC 传感器，退而解析 powermetrics 输出
+// powermetrics 需要 root 权限，普通用户运行时返回空列表
+#[cfg(target_os = "macos")]
+fn get_temperatures_powermetrics() -> Vec<ComponentTemp> {
+    // 非 root 运行时 powermetrics 必然失败，不启动子进程
+    // SAFETY: geteuid 没有参数，总是成功
+    if unsafe { libc::geteuid() } != 0 {
+        return Vec::new();
+    }
+    let Ok(out) = std::process::Command::new("powermetrics")
+        .args(["--samplers", "smc", "-n", "1", "-i", "1"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !out.status.success() {
+        return Vec::new();
+    }
+
+    // 形如 "CPU die temperature: 45.12 C"
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (label, value) = line.split_once("temperature:")?;
+            let temperature: f32 = value.trim().trim_end_matches('C').trim().parse().ok()?;
+            temperature.is_finite().then(|| ComponentTemp {
+                label: label.trim().to_string(),
+                name: label.trim().to_string(),
+                temperature,
+                max: None,
+                critical: None,
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct FanInfo {
+    label: String,
+    rpm: u32,
+}
+
+// 风扇转速，平台不提供或读取失败时为空列表：
+// - Linux：hwmon 的 fan*_input
+// - macOS：powermetrics 的 SMC 采样（需要 root，Apple Silicon 上不报告风扇）
+// - Windows：没有通用接口（Win32_Fan 几乎都不填转速），不支持
+// powermetrics 要采样一秒，在阻塞线程中执行
+#[tauri::command]
+pub async fn get_fans() -> Result<Vec<FanInfo>, AppError> {
+    tauri::async_runtime::spawn_blocking(read_fans)
+        .await
+        .map_err(|e| AppError::CommandFailed(format!("fan task: {}", e)))
+}
+
+fn read_fans() -> Vec<FanInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        hwmon_fans(std::path::Path::new("/sys/class/hwmon"))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        get_fans_powermetrics()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        Vec::new()
+    }
+}
+
+// 标签优先取 fanN_label，否则为 "<驱动名> fan N"（如 "thinkpad fan 1"）；
+// 停转的风扇报告 0，照常列出。hwmon_dir 通常为 /sys/class/hwmon
+#[cfg(any(target_os = "linux", test))]
+fn hwmon_fans(hwmon_dir: &std::path::Path) -> Vec<FanInfo> {
+    use std::fs;
+
+    let read = |path: &std::path::Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let mut hwmons: Vec<_> = fs::read_dir(hwmon_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    hwmons.sort();
+
+    let mut fans = Vec::new();
+    for hwmon in hwmons {
+        let chip = read(&hwmon.join("name")).unwrap_or_else(|| "hwmon".to_string());
+        let mut inputs: Vec<u32> = fs::read_dir(&hwmon)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                name.strip_prefix("fan")?
+                    .strip_suffix("_input")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        inputs.sort_unstable();
+        for index in inputs {
+            let Some(rpm) = read(&hwmon.join(format!("fan{}_input", index)))
+                .and_then(|v| v.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            fans.push(FanInfo {
+                label: read(&hwmon.join(format!("fan{}_label", index)))
+                    .filter(|l| !l.is_empty())
+                    .unwrap_or_else(|| format!("{} fan {}", chip, index)),
+                rpm,
+            });
+        }
+    }
+    fans
+}
+
+// Intel Mac 上形如 "Fan: 1797.35 rpm"，多风扇机型依次输出多行
+#[cfg(target_os = "macos")]
+fn get_fans_powermetrics() -> Vec<FanInfo> {
+    // SAFETY: geteuid 没有参数，总是成功
+    if unsafe { libc::geteuid() } != 0 {
+        return Vec::new();
+    }
+    let Ok(out) = std::process::Command::new("powermetrics")
+        .args(["--samplers", "smc", "-n", "1", "-i", "1"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !out.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let value = line.trim().strip_prefix("Fan:")?;
+            let rpm: f32 = value.trim().trim_end_matches("rpm").trim().parse().ok()?;
+            rpm.is_finite().then_some(rpm.round() as u32)
+        })
+        .enumerate()
+        .map(|(i, rpm)| FanInfo {
+            label: format!("Fan {}", i + 1),
+            rpm,
+        })
+        .collect()
+}
+
+// Apple Silicon 的传感器名形如 "pACC MTR Temp Sensor3"、"PMU tdie1"，
+// Intel Mac 为 SMC 四字符键（TC0P、TG0D ...），转换为常见部件名，保留末尾编号
+fn friendly_sensor_name(label: &str) -> String {
+    let patterns = [
+        ("pACC", "CPU Performance Core"),
+        ("eACC", "CPU Efficiency Core"),
+        ("PMU tdie", "CPU Die"),
+        ("GPU MTR", "GPU"),
+        ("SOC MTR", "SoC"),
+        ("ANE MTR", "Neural Engine"),
+        ("ISP MTR", "Image Signal Processor"),
+        ("NAND", "SSD"),
+        ("gas gauge battery", "Battery"),
+        ("TC0", "CPU"),
+        ("TG0", "GPU"),
+        ("TB", "Battery"),
+        ("TH0", "SSD"),
+        ("TA0", "Ambient"),
+    ];
+    let Some((_, name)) = patterns.iter().find(|(prefix, _)| {
+        // SMC 键必须是恰好四个字符，避免误匹配普通标签
+        if prefix.len() <= 3 && prefix.starts_with('T') {
+            label.len() == 4 && label.starts_with(prefix)
+        } else {
+            label.contains(prefix)
+        }
+    }) else {
+        return label.to_string();
+    };
+
+    let index = &label[label.trim_end_matches(|c: char| c.is_ascii_digit()).len()..];
+    if index.is_empty() || label.len() == 4 {
+        name.to_string()
+    } else {
+        format!("{} {}", name, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn friendly_sensor_names() {
+        let cases = [
+            // Intel Mac 的 SMC 键
+            ("TC0P", "CPU"),
+            ("TC0D", "CPU"),
+            ("TG0D", "GPU"),
+            ("TB0T", "Battery"),
+            ("TB1T", "Battery"),
+            ("TH0a", "SSD"),
+            ("TA0P", "Ambient"),
+            // Apple Silicon 的 HID 传感器，保留末尾编号
+            ("pACC MTR Temp Sensor3", "CPU Performance Core 3"),
+            ("eACC MTR Temp Sensor0", "CPU Efficiency Core 0"),
+            ("PMU tdie1", "CPU Die 1"),
+            ("GPU MTR Temp Sensor1", "GPU 1"),
+            ("SOC MTR Temp Sensor2", "SoC 2"),
+            ("NAND CH0 temp", "SSD"),
+            ("gas gauge battery", "Battery"),
+            // 无法识别的标签原样返回；形似 SMC 键但长度不是 4 的不转换
+            ("Tctl", "Tctl"),
+            ("TC0", "TC0"),
+            ("TC0PX", "TC0PX"),
+            ("acpitz temp1", "acpitz temp1"),
+            ("coretemp Package id 0", "coretemp Package id 0"),
+            ("", ""),
+        ];
+        for (label, expected) in cases {
+            assert_eq!(friendly_sensor_name(label), expected, "label {:?}", label);
+        }
+    }
+
+    #[test]
+    fn hwmon_fixture_lists_fans() {
+        use std::fs;
+
+        let root = std::env::temp_dir().join(format!("hisen-hwmon-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let thinkpad = root.join("hwmon3");
+        let nct = root.join("hwmon2");
+        let coretemp = root.join("hwmon1");
+        for dir in [&thinkpad, &nct, &coretemp] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        for (dir, file, value) in [
+            (&thinkpad, "name", "thinkpad\n"),
+            (&thinkpad, "fan1_input", "2650\n"),
+            (&thinkpad, "fan2_input", "0\n"),
+            (&nct, "name", "nct6775\n"),
+            (&nct, "fan10_input", "1200\n"),
+            (&nct, "fan2_input", "845\n"),
+            (&nct, "fan2_label", "CPU Fan\n"),
+            (&nct, "fan3_input", "invalid\n"),
+            (&coretemp, "name", "coretemp\n"),
+            (&coretemp, "temp1_input", "45000\n"),
+        ] {
+            fs::write(dir.join(file), value).unwrap();
+        }
+        let fans = hwmon_fans(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        let fans: Vec<(&str, u32)> = fans.iter().map(|f| (f.label.as_str(), f.rpm)).collect();
+        assert_eq!(
+            fans,
+            [
+                ("CPU Fan", 845),
+                ("nct6775 fan 10", 1200),
+                ("thinkpad fan 1", 2650),
+                ("thinkpad fan 2", 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+#[derive(Serialize)]
+pub struct ProcessSocketSummary {
+    // 无法归属到进程的连接（没有权限查看其他用户的进程）汇总在 pid 为 None 的条目中
+    pid: Option<u32>,
+    name: String,
+    listening_count: usize,
+    established_count: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TcpState {
+    Listen,
+    Established,
+    Other,
+}
+
+// 一个 TCP 套接字及其所属进程
+struct TcpSocket {
+    pid: Option<u32>,
+    state: TcpState,
+}
+
+// 按进程统计监听与已建立的 TCP 连接数，按已建立连接数降序
+#[tauri::command]
+pub fn get_process_socket_summary() -> Vec<ProcessSocketSummary> {
+    let sockets = list_tcp_sockets();
+
+    let mut counts: HashMap<Option<u32>, (usize, usize)> = HashMap::new();
+    for socket in &sockets {
+        let entry = counts.entry(socket.pid).or_default();
+        match socket.state {
+            TcpState::Listen => entry.0 += 1,
+            TcpState::Established => entry.1 += 1,
+            TcpState::Other => {}
+        }
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    let mut summaries: Vec<ProcessSocketSummary> = counts
+        .into_iter()
+        .filter(|(_, (listening, established))| listening + established > 0)
+        .map(
+            |(pid, (listening_count, established_count))| ProcessSocketSummary {
+                pid,
+                name: match pid {
+                    Some(pid) => sys
+                        .process(sysinfo::Pid::from_u32(pid))
+                        .map(|p| p.name().to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    None => "(unattributed)".to_string(),
+                },
+                listening_count,
+                established_count,
+            },
+        )
+        .collect();
+    summaries.sort_by(|a, b| {
+        b.established_count
+            .cmp(&a.established_count)
+            .then(b.listening_count.cmp(&a.listening_count))
+    });
+    summaries
+}
+
+// /proc/net/tcp{,6} 中每行的第 4 列为状态（0A 监听、01 已建立），第 10 列为 inode；
+// 再通过 /proc/<pid>/fd 中的 socket:[inode] 链接找到所属进程
+#[cfg(target_os = "linux")]
+fn list_tcp_sockets() -> Vec<TcpSocket> {
+    let mut owners: HashMap<u64, u32> = HashMap::new();
+    if let Ok(procs) = std::fs::read_dir("/proc") {
+        for entry in procs.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|n| n.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            // 其他用户的进程没有权限读取 fd，对应的套接字无法归属
+            let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                let Ok(target) = std::fs::read_link(fd.path()) else {
+                    continue;
+                };
+                let inode = target
+                    .to_str()
+                    .and_then(|t| t.strip_prefix("socket:["))
+                    .and_then(|t| t.strip_suffix(']'))
+                    .and_then(|t| t.parse::<u64>().ok());
+                if let Some(inode) = inode {
+                    owners.insert(inode, pid);
+                }
+            }
+        }
+    }
+
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|content| parse_proc_net_tcp(&content, &owners))
+        .collect()
+}
+
+// owners 为 inode 到 pid 的映射，找不到的套接字 pid 为 None
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_net_tcp(content: &str, owners: &HashMap<u64, u32>) -> Vec<TcpSocket> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (Some(state), Some(inode)) = (fields.get(3), fields.get(9)) else {
+                return None;
+            };
+            let state = match *state {
+                "0A" => TcpState::Listen,
+                "01" => TcpState::Established,
+                _ => TcpState::Other,
+            };
+            let pid = inode
+                .parse::<u64>()
+                .ok()
+                .and_then(|inode| owners.get(&inode).copied());
+            Some(TcpSocket { pid, state })
+        })
+        .collect()
+}
+
+// lsof 只能看到有权限访问的进程，其余连接数由 netstat 的总数减去已归属的部分得到
+// lsof -F 输出每个字段一行：p<pid>、f<fd>、TST=<状态>
+#[cfg(target_os = "macos")]
+fn list_tcp_sockets() -> Vec<TcpSocket> {
+    let mut sockets = Vec::new();
+    // 有连接时 lsof 也可能因为部分进程无权限而返回非零，因此不检查退出状态
+    if let Ok(out) = std::process::Command::new("lsof")
+        .args(["-nP", "-iTCP", "-FpT"])
+        .output()
+    {
+        let mut pid = None;
+        for line in String::from_utf8_lossy(&out.stdout).lines() {
+            if let Some(p) = line.strip_prefix('p') {
+                pid = p.parse::<u32>().ok();
+            } else if let Some(state) = line.strip_prefix("TST=") {
+                sockets.push(TcpSocket {
+                    pid,
+                    state: macos_tcp_state(state),
+                });
+            }
+        }
+    }
+
+    // netstat -an -p tcp 的最后一列为状态，如 "tcp4  0  0  *.80  *.*  LISTEN"
+    if let Ok(text) = crate::util::run_checked("netstat", &["-an", "-p", "tcp"]) {
+        let count =
+            |sockets: &[TcpSocket], state| sockets.iter().filter(|s| s.state == state).count();
+        let mut totals = [(TcpState::Listen, 0), (TcpState::Established, 0)];
+        for line in text.lines().filter(|l| l.starts_with("tcp")) {
+            let state = macos_tcp_state(line.split_whitespace().last().unwrap_or_default());
+            if let Some(total) = totals.iter_mut().find(|(s, _)| *s == state) {
+                total.1 += 1;
+            }
+        }
+        for (state, total) in totals {
+            let missing = total.saturating_sub(count(&sockets, state));
+            sockets.extend((0..missing).map(|_| TcpSocket { pid: None, state }));
+        }
+    }
+    sockets
+}
+
+#[cfg(target_os = "macos")]
+fn macos_tcp_state(state: &str) -> TcpState {
+    match state {
+        "LISTEN" => TcpState::Listen,
+        "ESTABLISHED" => TcpState::Established,
+        _ => TcpState::Other,
+    }
+}
+
+// netstat -ano 的状态列会随系统语言本地化，Get-NetTCPConnection 的 State 是固定的英文枚举名
+#[cfg(target_os = "windows")]
+fn list_tcp_sockets() -> Vec<TcpSocket> {
+    let Ok(text) = crate::util::run_checked(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-NetTCPConnection | ForEach-Object { \"$($_.OwningProcess) $($_.State)\" }",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let (pid, state) = line.trim().split_once(' ')?;
+            Some(TcpSocket {
+                // PID 0 表示连接已没有所属进程（如 TIME_WAIT）
+                pid: pid.parse::<u32>().ok().filter(|&p| p != 0),
+                state: match state {
+                    "Listen" => TcpState::Listen,
+                    "Established" => TcpState::Established,
+                    _ => TcpState::Other,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proc_net_tcp_states_and_owners() {
+        let content = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A1B2 5DB8D822:01BB 01 00000000:00000000 02:000A3F2E 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+   2: 0F02000A:C3D4 5DB8D822:01BB 06 00000000:00000000 03:00000E1D 00000000     0        0 0 3 0000000000000000
+   3: 0F02000A:E5F6 0101A8C0:0035 01 00000000:00000000 00:00000000 00000000  1000        0 55555 1 0000000000000000 20 4 30 10 -1
+";
+        let owners: HashMap<u64, u32> = [(98765, 4242), (23456, 1)].into_iter().collect();
+        let sockets: Vec<_> = parse_proc_net_tcp(content, &owners)
+            .iter()
+            .map(|s| (s.pid, s.state))
+            .collect();
+        assert_eq!(
+            sockets,
+            [
+                (Some(1), TcpState::Listen),
+                (Some(4242), TcpState::Established),
+                // TIME_WAIT 的 inode 为 0，不属于任何进程
+                (None, TcpState::Other),
+                // 其他用户进程的套接字无法归属
+                (None, TcpState::Established),
+            ]
+        );
+        assert!(parse_proc_net_tcp("  sl  local_address\n", &owners).is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Components, Networks, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+// 跨命令共享的 sysinfo 实例，CPU 使用率等指标需要基于上一次刷新计算
+#[derive(Default)]
+pub struct SysState {
+    pub sys: Mutex<SharedSystem>,
+}
+
+// 网络测试的取消标志，每次开始测试时重置
+#[derive(Default)]
+pub struct NetTestState {
+    pub cancelled: AtomicBool,
+}
+
+#[derive(Default)]
+pub struct SharedSystem {
+    pub system: System,
+    // 最近一次刷新 CPU 的时间，以及它与再上一次刷新之间的间隔（即当前使用率的采样窗口）
+    pub cpu_refreshed_at: Option<Instant>,
+    pub cpu_window: Option<Duration>,
+    // 进程表最近一次刷新的时间，进程 CPU 使用率同样依赖上一次刷新
+    pub processes_refreshed_at: Option<Instant>,
+    // 网络速率由两次刷新之间的字节差值除以间隔得到
+    pub networks: Networks,
+    pub networks_refreshed_at: Option<Instant>,
+    // 上一次刷新时各接口的累计收发字节数（收, 发）
+    pub network_totals: HashMap<String, (u64, u64)>,
+    // 上一次读取的各磁盘累计读写字节数（读, 写），用于计算 I/O 速率；平台未报告的计数为 None
+    pub disk_io_prev: HashMap<String, (Option<u64>, Option<u64>)>,
+    pub disk_io_sampled_at: Option<Instant>,
+    // 温度传感器，首次使用时枚举
+    pub components: Components,
+}
+
+impl SharedSystem {
+    pub fn refresh_cpu(&mut self) {
+        self.system.refresh_cpu_all();
+        let now = Instant::now();
+        self.cpu_window = self.cpu_refreshed_at.map(|prev| now - prev);
+        self.cpu_refreshed_at = Some(now);
+    }
+
+    pub fn refresh_processes(&mut self) -> usize {
+        let refresh_kind = ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_exe(UpdateKind::OnlyIfNotSet);
+        let count = self
+            .system
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        self.processes_refreshed_at = Some(Instant::now());
+        count
+    }
+
+    // 列表为空时（首次调用或之前没读到）重新枚举，否则只刷新读数
+    pub fn refresh_components(&mut self) {
+        if self.components.is_empty() {
+            self.components.refresh_list();
+        } else {
+            self.components.refresh();
+        }
+    }
+
+    // 刷新接口列表及流量计数，返回与上一次刷新之间的间隔（首次刷新为 None）
+    pub fn refresh_networks(&mut self) -> Option<Duration> {
+        self.networks.refresh_list();
+        let now = Instant::now();
+        let elapsed = self.networks_refreshed_at.map(|prev| now - prev);
+        self.networks_refreshed_at = Some(now);
+        elapsed
+    }
+}
@@ -0,0 +1,249 @@
+use crate::util::run_checked;
+use serde::Serialize;
+use sysinfo::Users;
+
+#[derive(Serialize)]
+pub struct UserInfo {
+    name: String,
+    // Unix 上为 uid，Windows 上为 SID
+    uid: Option<String>,
+    groups: Vec<String>,
+    sessions: Vec<UserSession>,
+    // 当前运行本程序的用户
+    is_current: bool,
+}
+
+#[derive(Serialize)]
+pub struct UserSession {
+    // "console" / "ssh" / "rdp" / "unknown"
+    kind: String,
+    // 终端或会话名（tty7、pts/0、rdp-tcp#0）
+    terminal: Option<String>,
+    // 远程登录的来源地址
+    host: Option<String>,
+    // 系统工具给出的原始登录时间字符串，格式因平台和区域设置而异
+    login_time: Option<String>,
+}
+
+// 列出已登录的用户及其会话；无法获取会话信息时退化为系统用户列表（sessions 为空）
+#[tauri::command]
+pub fn get_users() -> Vec<UserInfo> {
+    let accounts = Users::new_with_refreshed_list();
+    let current = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok();
+    let to_info = |name: String, sessions: Vec<UserSession>| {
+        let account = accounts
+            .iter()
+            .find(|u| u.name().eq_ignore_ascii_case(&name));
+        let mut groups: Vec<String> = account
+            .map(|u| u.groups().iter().map(|g| g.name().to_string()).collect())
+            .unwrap_or_default();
+        groups.sort();
+        UserInfo {
+            uid: account.map(|u| u.id().to_string()),
+            groups,
+            sessions,
+            is_current: current
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(&name)),
+            name,
+        }
+    };
+
+    let Some(sessions) = list_sessions() else {
+        return accounts
+            .iter()
+            .map(|u| to_info(u.name().to_string(), Vec::new()))
+            .collect();
+    };
+
+    // 同一用户可能有多个会话，按出现顺序合并
+    let mut grouped: Vec<(String, Vec<UserSession>)> = Vec::new();
+    for (name, session) in sessions {
+        match grouped.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, list)) => list.push(session),
+            None => grouped.push((name, vec![session])),
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|(name, sessions)| to_info(name, sessions))
+        .collect()
+}
+
+#[cfg(unix)]
+fn list_sessions() -> Option<Vec<(String, UserSession)>> {
+    run_checked("who", &[]).ok().map(|out| parse_who(&out))
+}
+
+// who 输出形如：
+// alice    pts/0        2024-05-01 09:12 (192.168.1.5)
+// alice    console  May  1 09:12
+#[cfg(any(unix, test))]
+fn parse_who(out: &str) -> Vec<(String, UserSession)> {
+    out.lines()
+        .filter_map(|line| {
+            let mut tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.len() < 2 {
+                return None;
+            }
+            let host = tokens
+                .last()
+                .filter(|t| t.starts_with('('))
+                .map(|t| t.trim_matches(|c| c == '(' || c == ')').to_string());
+            if host.is_some() {
+                tokens.pop();
+            }
+            let terminal = tokens[1].to_string();
+            // 括号中是 X 显示编号（:0）或终端本身（GDM 会话为 "tty2 ... (tty2)"）时
+            // 为本地图形会话，不是远程地址
+            let host = host.filter(|h| !h.is_empty() && !h.starts_with(':') && *h != terminal);
+            let kind = match &host {
+                Some(_) => "ssh",
+                _ if terminal == "console"
+                    || terminal.starts_with("tty")
+                    || terminal.starts_with("pts/")
+                    || terminal.starts_with(':') =>
+                {
+                    "console"
+                }
+                _ => "unknown",
+            };
+            let login_time = (tokens.len() > 2).then(|| tokens[2..].join(" "));
+            Some((
+                tokens[0].to_string(),
+                UserSession {
+                    kind: kind.to_string(),
+                    terminal: Some(terminal),
+                    host,
+                    login_time,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn list_sessions() -> Option<Vec<(String, UserSession)>> {
+    run_checked("query", &["user"])
+        .ok()
+        .map(|out| parse_query_user(&out))
+}
+
+// query user 输出形如（当前会话以 > 开头，断开的会话没有会话名）：
+//  USERNAME              SESSIONNAME        ID  STATE   IDLE TIME  LOGON TIME
+// >alice                 console             1  Active      none   5/1/2024 9:12 AM
+//  bob                   rdp-tcp#0           2  Active         5   5/1/2024 10:03 AM
+//  carol                                     3  Disc           1   5/1/2024 8:00 AM
+#[cfg(any(windows, test))]
+fn parse_query_user(out: &str) -> Vec<(String, UserSession)> {
+    out.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line
+                .trim_start_matches(['>', ' '])
+                .split_whitespace()
+                .collect();
+            let name = tokens.first()?.to_string();
+            let has_session_name = tokens.get(1)?.parse::<u32>().is_err();
+            let session_name = has_session_name.then(|| tokens[1].to_string());
+            // 会话名之后依次为 ID、状态、空闲时间，其余为登录时间
+            let time_start = if has_session_name { 5 } else { 4 };
+            let login_time = (tokens.len() > time_start).then(|| tokens[time_start..].join(" "));
+            let kind = match session_name.as_deref().map(|s| s.to_ascii_lowercase()) {
+                Some(s) if s == "console" => "console",
+                Some(s) if s.starts_with("rdp") => "rdp",
+                _ => "unknown",
+            };
+            Some((
+                name,
+                UserSession {
+                    kind: kind.to_string(),
+                    terminal: session_name,
+                    host: None,
+                    login_time,
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(
+        sessions: &[(String, UserSession)],
+    ) -> Vec<(&str, &str, Option<&str>, Option<&str>)> {
+        sessions
+            .iter()
+            .map(|(name, s)| {
+                (
+                    name.as_str(),
+                    s.kind.as_str(),
+                    s.terminal.as_deref(),
+                    s.host.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn who_sessions() {
+        let out = "\
+alice    tty2         2024-05-01 09:10 (tty2)
+alice    :0           2024-05-01 09:12 (:0)
+alice    pts/1        2024-05-01 09:15 (:0)
+bob      pts/0        2024-05-01 10:03 (192.168.1.5)
+carol    console  May  1 08:00
+carol    ttys001  May  1 08:05 (2001:db8::7)
+dave     ?            2024-05-01 11:00
+";
+        let sessions = parse_who(out);
+        assert_eq!(
+            summary(&sessions),
+            [
+                ("alice", "console", Some("tty2"), None),
+                ("alice", "console", Some(":0"), None),
+                ("alice", "console", Some("pts/1"), None),
+                ("bob", "ssh", Some("pts/0"), Some("192.168.1.5")),
+                ("carol", "console", Some("console"), None),
+                ("carol", "ssh", Some("ttys001"), Some("2001:db8::7")),
+                ("dave", "unknown", Some("?"), None),
+            ]
+        );
+        assert_eq!(
+            sessions[3].1.login_time.as_deref(),
+            Some("2024-05-01 10:03")
+        );
+        assert_eq!(sessions[4].1.login_time.as_deref(), Some("May 1 08:00"));
+    }
+
+    #[test]
+    fn query_user_sessions() {
+        let out = " USERNAME              SESSIONNAME        ID  STATE   IDLE TIME  LOGON TIME
+>alice                 console             1  Active      none   5/1/2024 9:12 AM
+ bob                   rdp-tcp#0           2  Active         5   5/1/2024 10:03 AM
+ carol                                     3  Disc           1   5/1/2024 8:00 AM
+";
+        let sessions = parse_query_user(out);
+        assert_eq!(
+            summary(&sessions),
+            [
+                ("alice", "console", Some("console"), None),
+                ("bob", "rdp", Some("rdp-tcp#0"), None),
+                // 断开的会话没有会话名
+                ("carol", "unknown", None, None),
+            ]
+        );
+        assert_eq!(
+            sessions[0].1.login_time.as_deref(),
+            Some("5/1/2024 9:12 AM")
+        );
+        assert_eq!(
+            sessions[2].1.login_time.as_deref(),
+            Some("5/1/2024 8:00 AM")
+        );
+    }
+}
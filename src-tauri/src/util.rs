@@ -0,0 +1,136 @@
+use crate::error::AppError;
+use std::io;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+// Windows 上启动控制台程序时不弹出黑色窗口（CommandExt::creation_flags）
+#[cfg(target_os = "windows")]
+pub const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+// 运行外部命令并在超时后结束它，避免探测命令卡住整个调用。
+// stdout / stderr 在独立线程中持续读取，输出超过管道缓冲区（截图、大段 JSON）时子进程也不会阻塞
+pub fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = read_to_end_in_background(child.stdout.take());
+    let stderr = read_to_end_in_background(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {} ms", timeout.as_millis()),
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+// 管道关闭（子进程退出或被结束）时读取线程随之结束
+fn read_to_end_in_background<R: io::Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+// 创建外部命令；Windows 上不为控制台程序弹出窗口
+pub fn command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+// 运行命令并检查退出状态，返回 stdout
+pub fn run_checked(program: &str, args: &[&str]) -> Result<String, AppError> {
+    let out = command(program)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::CommandFailed(format!("{}: {}", program, e)))?;
+    if !out.status.success() {
+        return Err(AppError::CommandFailed(format!(
+            "{} exited with {}: {}",
+            program,
+            out.status,
+            String::from_utf8_lossy(&out.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+// 将秒数格式化为天、小时、分钟，从最大的非零单位开始最多保留 max_units 个：
+// max_units 为 2 时如 "1d 3h" / "2h 34m"，为 3 时如 "3d 4h 12m" / "4h 0m"；不足一分钟为 "<1m"
+pub fn format_duration(secs: u64, max_units: usize) -> String {
+    let units = [
+        (secs / 86_400, "d"),
+        (secs % 86_400 / 3600, "h"),
+        (secs % 3600 / 60, "m"),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .skip_while(|(value, _)| *value == 0)
+        .take(max_units.max(1))
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if parts.is_empty() {
+        "<1m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_duration_zero_and_sub_minute() {
+        assert_eq!(format_duration(0, 3), "<1m");
+        assert_eq!(format_duration(59, 3), "<1m");
+        assert_eq!(format_duration(60, 3), "1m");
+    }
+
+    #[test]
+    fn format_duration_multi_day() {
+        let secs = 3 * 86_400 + 4 * 3600 + 12 * 60 + 30;
+        assert_eq!(format_duration(secs, 3), "3d 4h 12m");
+        assert_eq!(format_duration(secs, 2), "3d 4h");
+        // 较大单位之后的 0 保留
+        assert_eq!(format_duration(2 * 86_400, 3), "2d 0h 0m");
+        assert_eq!(format_duration(4 * 3600, 3), "4h 0m");
+    }
+
+    #[test]
+    fn format_duration_keeps_at_least_one_unit() {
+        assert_eq!(format_duration(2 * 3600 + 34 * 60, 0), "2h");
+    }
+}
@@ -0,0 +1,274 @@
+use serde::Serialize;
+
+// 虚拟化检测结果。宁可返回未知也不要在物理机上误报，
+// 只有识别出具体的虚拟机或容器类型时才认为是虚拟环境
+#[derive(Serialize, Clone)]
+pub struct VirtInfo {
+    is_virtual: bool,
+    // 如 "VMware"、"VirtualBox"、"Hyper-V"、"KVM"、"QEMU"、"Parallels"、"Apple Virtualization"
+    hypervisor: Option<String>,
+    // 仅 Linux：如 "docker"、"podman"、"kubernetes"、"lxc"、"wsl"
+    container: Option<String>,
+}
+
+pub fn virtualization() -> VirtInfo {
+    let (hypervisor, container) = detect();
+    VirtInfo {
+        is_virtual: hypervisor.is_some() || container.is_some(),
+        hypervisor: hypervisor.map(str::to_string),
+        container: container.map(str::to_string),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn detect() -> (Option<&'static str>, Option<&'static str>) {
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+    let hypervisor = hypervisor_from_dmi(
+        &read("/sys/class/dmi/id/sys_vendor"),
+        &read("/sys/class/dmi/id/product_name"),
+    )
+    .or_else(hypervisor_from_cpuid);
+    let container = container_from_markers(
+        std::path::Path::new("/.dockerenv").exists(),
+        std::path::Path::new("/run/.containerenv").exists(),
+        &read("/proc/1/cgroup"),
+        &read("/proc/version"),
+    );
+    (hypervisor, container)
+}
+
+// Apple 虚拟化框架的客户机型号为 VirtualMac；kern.hv_vmm_present 只说明处于虚拟机中
+#[cfg(target_os = "macos")]
+fn detect() -> (Option<&'static str>, Option<&'static str>) {
+    let sysctl = |key: &str| {
+        crate::util::run_checked("sysctl", &["-n", key])
+            .map(|v| v.trim().to_string())
+            .unwrap_or_default()
+    };
+    let model = sysctl("hw.model");
+    let hypervisor = if model.starts_with("VirtualMac") {
+        Some("Apple Virtualization")
+    } else {
+        hypervisor_from_dmi("", &model).or_else(|| {
+            (sysctl("kern.hv_vmm_present") == "1")
+                .then(hypervisor_from_cpuid)
+                .flatten()
+        })
+    };
+    (hypervisor, None)
+}
+
+// 开启 VBS / WSL2 的物理机也运行在 Hyper-V 之上，CPUID 会报告 "Microsoft Hv"，
+// 所以 Windows 上以注册表中的 SMBIOS 厂商和型号为准
+#[cfg(target_os = "windows")]
+fn detect() -> (Option<&'static str>, Option<&'static str>) {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(r"HARDWARE\DESCRIPTION\System\BIOS");
+    let value = |name: &str| {
+        key.as_ref()
+            .ok()
+            .and_then(|k| k.get_value::<String, _>(name).ok())
+            .unwrap_or_default()
+    };
+    let hypervisor = hypervisor_from_dmi(&value("SystemManufacturer"), &value("SystemProductName"))
+        .or_else(hypervisor_from_cpuid);
+    (hypervisor, None)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn detect() -> (Option<&'static str>, Option<&'static str>) {
+    (None, None)
+}
+
+// 根据 SMBIOS 厂商 / 型号字符串判断；云厂商的裸金属实例同样带有云厂商名称，不据此判断
+fn hypervisor_from_dmi(vendor: &str, product: &str) -> Option<&'static str> {
+    let vendor = vendor.trim().to_ascii_lowercase();
+    let product = product.trim().to_ascii_lowercase();
+    if vendor.contains("vmware") || product.starts_with("vmware") {
+        Some("VMware")
+    } else if vendor.contains("innotek") || product == "virtualbox" {
+        Some("VirtualBox")
+    } else if vendor.contains("parallels") || product.starts_with("parallels") {
+        Some("Parallels")
+    } else if vendor == "qemu" || product.starts_with("standard pc (") {
+        Some("QEMU")
+    } else if product == "kvm" {
+        Some("KVM")
+    } else if vendor == "microsoft corporation" && product == "virtual machine" {
+        Some("Hyper-V")
+    } else if vendor == "xen" || product.starts_with("hvm domu") {
+        Some("Xen")
+    } else {
+        None
+    }
+}
+
+// CPUID 的 hypervisor 位（leaf 1 ECX bit 31）加上 0x40000000 叶的厂商签名。
+// 只认可明确的签名，"Microsoft Hv" 可能来自宿主机自身的 VBS，不计入
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn hypervisor_from_cpuid() -> Option<&'static str> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{__cpuid, CpuidResult};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{__cpuid, CpuidResult};
+
+    // 较新的编译器把 __cpuid 标为安全函数，旧版本（rust-version 以内）仍为 unsafe；
+    // 统一转成 unsafe 函数指针，两种版本下都不会产生多余或缺少 unsafe 的告警
+    let cpuid: unsafe fn(u32) -> CpuidResult = __cpuid;
+    // SAFETY: 支持 x86_64 的 CPU 都有 CPUID 指令，32 位 x86 上 486 之后的处理器同样支持
+    let (features, leaf) = unsafe { (cpuid(1), cpuid(0x4000_0000)) };
+    hypervisor_from_cpuid_regs(features.ecx, [leaf.ebx, leaf.ecx, leaf.edx])
+}
+
+// features_ecx 为 leaf 1 的 ECX，signature 为 0x40000000 叶的 EBX / ECX / EDX
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn hypervisor_from_cpuid_regs(features_ecx: u32, signature: [u32; 3]) -> Option<&'static str> {
+    if features_ecx & (1 << 31) == 0 {
+        return None;
+    }
+    let bytes: Vec<u8> = signature.iter().flat_map(|reg| reg.to_le_bytes()).collect();
+    hypervisor_from_signature(&String::from_utf8_lossy(&bytes))
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn hypervisor_from_cpuid() -> Option<&'static str> {
+    None
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn hypervisor_from_signature(signature: &str) -> Option<&'static str> {
+    match signature.trim_end_matches('\0').trim() {
+        "VMwareVMware" => Some("VMware"),
+        "VBoxVBoxVBox" => Some("VirtualBox"),
+        "KVMKVMKVM" => Some("KVM"),
+        "TCGTCGTCGTCG" => Some("QEMU"),
+        "prl hyperv" | "lrpepyh vr" => Some("Parallels"),
+        "XenVMMXenVMM" => Some("Xen"),
+        "bhyve bhyve" => Some("bhyve"),
+        _ => None,
+    }
+}
+
+// 容器标记：Docker 与 Podman 各自在根目录留下标记文件，
+// 其他运行时从 1 号进程的 cgroup 路径判断；WSL 的内核版本字符串带有 microsoft
+#[cfg(any(target_os = "linux", test))]
+fn container_from_markers(
+    dockerenv: bool,
+    containerenv: bool,
+    init_cgroup: &str,
+    proc_version: &str,
+) -> Option<&'static str> {
+    if dockerenv {
+        return Some("docker");
+    }
+    if containerenv {
+        return Some("podman");
+    }
+    let cgroup = init_cgroup.to_ascii_lowercase();
+    if cgroup.contains("kubepods") {
+        return Some("kubernetes");
+    }
+    if cgroup.contains("/docker") {
+        return Some("docker");
+    }
+    if cgroup.contains("/lxc") {
+        return Some("lxc");
+    }
+    if proc_version.to_ascii_lowercase().contains("microsoft") {
+        return Some("wsl");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dmi_vendor_strings() {
+        assert_eq!(
+            hypervisor_from_dmi("VMware, Inc.", "VMware Virtual Platform"),
+            Some("VMware")
+        );
+        assert_eq!(
+            hypervisor_from_dmi("innotek GmbH", "VirtualBox"),
+            Some("VirtualBox")
+        );
+        assert_eq!(
+            hypervisor_from_dmi("QEMU", "Standard PC (Q35 + ICH9, 2009)"),
+            Some("QEMU")
+        );
+        assert_eq!(hypervisor_from_dmi("Red Hat", "KVM"), Some("KVM"));
+        assert_eq!(
+            hypervisor_from_dmi("Microsoft Corporation", "Virtual Machine"),
+            Some("Hyper-V")
+        );
+        assert_eq!(hypervisor_from_dmi("Xen", "HVM domU"), Some("Xen"));
+        // Surface 等物理机同样由 Microsoft 生产
+        assert_eq!(
+            hypervisor_from_dmi("Microsoft Corporation", "Surface Laptop 5"),
+            None
+        );
+        assert_eq!(hypervisor_from_dmi("Dell Inc.", "XPS 15 9520"), None);
+        // 云厂商的裸金属实例
+        assert_eq!(hypervisor_from_dmi("Amazon EC2", "m5.metal"), None);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn cpuid_hypervisor_flag_and_signature() {
+        let regs = |signature: &[u8; 12]| {
+            let word = |i: usize| u32::from_le_bytes(signature[i..i + 4].try_into().unwrap());
+            [word(0), word(4), word(8)]
+        };
+        let present = 1 << 31;
+
+        assert_eq!(
+            hypervisor_from_cpuid_regs(present, regs(b"KVMKVMKVM\0\0\0")),
+            Some("KVM")
+        );
+        assert_eq!(
+            hypervisor_from_cpuid_regs(present, regs(b"VMwareVMware")),
+            Some("VMware")
+        );
+        // 没有 hypervisor 位时忽略 0x40000000 叶的内容
+        assert_eq!(hypervisor_from_cpuid_regs(0, regs(b"VMwareVMware")), None);
+        // 宿主机开启 VBS 时同样报告 "Microsoft Hv"，不计入
+        assert_eq!(
+            hypervisor_from_cpuid_regs(present, regs(b"Microsoft Hv")),
+            None
+        );
+    }
+
+    #[test]
+    fn wsl_kernel_string() {
+        let wsl = "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@941d701f84f1) \
+                   (gcc (GCC) 11.2.0, GNU ld (GNU Binutils) 2.37) #1 SMP";
+        assert_eq!(
+            container_from_markers(false, false, "0::/", wsl),
+            Some("wsl")
+        );
+
+        let native = "Linux version 6.8.0-45-generic (buildd@lcy02-amd64-075) #45-Ubuntu SMP";
+        assert_eq!(
+            container_from_markers(false, false, "0::/init.scope", native),
+            None
+        );
+    }
+
+    #[test]
+    fn container_markers() {
+        assert_eq!(container_from_markers(true, false, "", ""), Some("docker"));
+        assert_eq!(container_from_markers(false, true, "", ""), Some("podman"));
+        assert_eq!(
+            container_from_markers(false, false, "0::/kubepods/besteffort/pod1234/abcdef", ""),
+            Some("kubernetes")
+        );
+        assert_eq!(
+            container_from_markers(false, false, "0::/lxc/ct1", ""),
+            Some("lxc")
+        );
+    }
+}
@@ -1,79 +1,232 @@
 import React, { useEffect, useState } from 'react'
 
 // Tauri v2 API — dynamically import to handle browser context gracefully
-const invokeCmd = async <T,>(cmd: string): Promise<T> => {
+const invokeCmd = async <T,>(cmd: string, args?: Record<string, unknown>): Promise<T> => {
   const { invoke } = await import('@tauri-apps/api/core')
-  return invoke<T>(cmd)
+  return invoke<T>(cmd, args)
 }
 
-type NetworkIface = { name: string; received: number; transmitted: number }
+// 后端命令失败时 reject 的错误结构
+type AppError = { kind: string; message: string }
+
+const errorText = (e: unknown) => {
+  const err = e as Partial<AppError>
+  return err && err.kind ? `${err.kind}: ${err.message}` : String(e)
+}
+
+type NetworkIface = {
+  name: string
+  received: number
+  transmitted: number
+  rx_bytes_per_sec: number | null
+  tx_bytes_per_sec: number | null
+  mac_address: string | null
+  addresses: { address: string; prefix: number }[]
+  mtu: number | null
+  is_up: boolean | null
+  is_loopback: boolean
+  speed_mbps: number | null
+  kind: 'loopback' | 'vpn' | 'virtual' | 'wireless' | 'physical'
+}
 
 type GpuInfo = {
   name: string
   vendor: string
-  vram: string | null
+  vram_bytes: number | null
+  vram_display: string | null
+  driver_version: string | null
+  unified_memory: boolean
+  gpu_cores: number | null
+  metal_family: string | null
+  utilization: number | null
+  temperature: number | null
+  is_primary: boolean
+  kind: 'integrated' | 'discrete' | 'unknown'
+}
+
+type Resolution = { width: number; height: number }
+
+type DisplayInfo = {
+  id: string
+  name: string
+  native_resolution: Resolution | null
+  current_resolution: Resolution | null
+  refresh_rate_hz: number | null
+  scale_factor: number | null
+  bit_depth: number | null
+  is_primary: boolean
+  gpu: string | null
+}
+
+type CpuDetails = {
+  cache: { l1d: number | null; l1i: number | null; l2: number | null; l3: number | null }
+  features: string[]
+  cores: {
+    index: number
+    min_mhz: number | null
+    max_mhz: number | null
+    base_mhz: number | null
+    core_kind: 'performance' | 'efficiency' | null
+  }[]
+}
+
+type LoadAvg = { one: number; five: number; fifteen: number; logical_cores: number }
+
+// 平台不提供的项为 null
+type MemoryBreakdown = {
+  cached: number | null
+  buffers: number | null
+  wired: number | null
+  compressed: number | null
+  app_memory: number | null
+  standby: number | null
+  modified: number | null
+}
+
+// 仅 macOS
+type MemoryPressure = {
+  level: 'normal' | 'warn' | 'critical' | 'unknown'
+  compressed_bytes: number | null
+  swapins: number | null
+  swapouts: number | null
+  compressions: number | null
+  decompressions: number | null
 }
 
+// 未请求的分区字段为 null
 type SystemInfo = {
   os_name: string | null
   hostname: string | null
   kernel_version: string | null
   os_version: string | null
-  cpu_brand: string
+  os_edition: string | null
+  os_display_version: string | null
+  os_build: string | null
+  virtualization: { is_virtual: boolean; hypervisor: string | null; container: string | null } | null
+  cpu_brand: string | null
   cpu_physical_cores: number | null
-  total_memory: number
-  used_memory: number
-  total_swap: number
-  used_swap: number
-  uptime: number
-  network_ifaces: NetworkIface[]
-  gpus: GpuInfo[]
+  cpu_details: CpuDetails | null
+  load_average: LoadAvg | null
+  total_memory: number | null
+  used_memory: number | null
+  available_memory: number | null
+  free_memory: number | null
+  memory_used_pct: number | null
+  memory_breakdown: MemoryBreakdown | null
+  memory_pressure: MemoryPressure | null
+  total_swap: number | null
+  used_swap: number | null
+  uptime: number | null
+  boot_time: number | null
+  network_ifaces: NetworkIface[] | null
+  gpus: GpuInfo[] | null
+  warnings: AppError[]
+  collected_at: number
 }
 
 type AudioDevices = {
   inputs: string[]
   outputs: string[]
-  default_input?: string | null
-  default_output?: string | null
+  // 默认设备在列表中的下标
+  default_input_index: number | null
+  default_output_index: number | null
 }
 
 type NetTestResult = {
   external_ip?: string | null
+  external_ipv6?: string | null
+  dns_lookup_ms?: number | null
   http_latency_ms?: number | null
   download_mbps?: number | null
   upload_mbps?: number | null
   error?: string | null
 }
 
+type NetTestProgress = { phase: string; percent: number; ok: boolean | null }
+
 export default function App() {
   const [sys, setSys] = useState<SystemInfo | null>(null)
   const [audio, setAudio] = useState<AudioDevices | null>(null)
   const [cameras, setCameras] = useState<string[]>([])
+  const [displays, setDisplays] = useState<DisplayInfo[]>([])
+  const [screenshot, setScreenshot] = useState<string | null>(null)
   const [testing, setTesting] = useState(false)
   const [net, setNet] = useState<NetTestResult | null>(null)
+  const [progress, setProgress] = useState(0)
+
+  const loadAudio = async () => {
+    try {
+      const a = await invokeCmd<AudioDevices>('list_audio_devices')
+      setAudio(a)
+    } catch (e) {
+      console.error('audio devices error', errorText(e))
+    }
+  }
 
   const refresh = async () => {
+    // 各项独立获取，一项失败不影响其他项
     try {
       const s = await invokeCmd<SystemInfo>('get_system_info')
       setSys(s)
-      const a = await invokeCmd<AudioDevices>('list_audio_devices')
-      setAudio(a)
+      s.warnings.forEach((w) => console.warn('system info warning', errorText(w)))
+    } catch (e) {
+      console.error('system info error', errorText(e))
+    }
+    await loadAudio()
+    try {
       const cams = await invokeCmd<string[]>('list_cameras')
       setCameras(cams)
     } catch (e) {
-      console.error('refresh error', e)
+      console.error('cameras error', errorText(e))
+      setCameras([])
+    }
+    try {
+      setDisplays(await invokeCmd<DisplayInfo[]>('get_displays'))
+    } catch (e) {
+      console.error('displays error', errorText(e))
+      setDisplays([])
+    }
+  }
+
+  // 截取显示器画面（base64 PNG），不指定下标时为主显示器
+  const takeScreenshot = async (index?: number) => {
+    try {
+      const png = await invokeCmd<string>('capture_screenshot', { displayIndex: index })
+      setScreenshot(`data:image/png;base64,${png}`)
+    } catch (e) {
+      console.error('screenshot error', errorText(e))
+    }
+  }
+
+  // 只查询外网 IP，结果写入网络测试区域
+  const lookupIp = async () => {
+    try {
+      const ip = await invokeCmd<string>('get_public_ip')
+      setNet((prev) => ({ ...prev, external_ip: ip, error: null }))
+    } catch (e) {
+      setNet((prev) => ({ ...prev, error: errorText(e) }))
     }
   }
 
   const runTest = async () => {
     setTesting(true)
     setNet(null)
+    setProgress(0)
+    // 监听后端推送的测试进度
+    let unlisten: (() => void) | undefined
+    try {
+      const { listen } = await import('@tauri-apps/api/event')
+      unlisten = await listen<NetTestProgress>('nettest-progress', (e) => setProgress(e.payload.percent))
+    } catch (e) {
+      console.warn('progress listener error', errorText(e))
+    }
     try {
       const r = await invokeCmd<NetTestResult>('run_network_test')
       setNet(r)
-    } catch (e: any) {
-      setNet({ error: String(e) })
+    } catch (e) {
+      setNet({ error: errorText(e) })
     } finally {
+      unlisten?.()
       setTesting(false)
     }
   }
@@ -82,12 +235,40 @@ export default function App() {
     refresh()
   }, [])
 
+  // 插拔音频设备时后端推送事件，自动刷新设备列表
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    let cancelled = false
+    const watch = async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event')
+        const off = await listen('audio-devices-changed', () => loadAudio())
+        if (cancelled) {
+          off()
+          return
+        }
+        unlisten = off
+        await invokeCmd('start_audio_watch')
+      } catch (e) {
+        console.warn('audio watch error', errorText(e))
+      }
+    }
+    watch()
+    return () => {
+      cancelled = true
+      unlisten?.()
+      invokeCmd('stop_audio_watch').catch(() => {})
+    }
+  }, [])
+
   return (
     <div className="container">
       <h1>Hisen Desk</h1>
       <div className="actions">
         <button onClick={refresh}>刷新信息</button>
-        <button onClick={runTest} disabled={testing}>{testing ? '测试中…' : '网络测试'}</button>
+        <button onClick={runTest} disabled={testing}>{testing ? `测试中… ${progress}%` : '网络测试'}</button>
+        {testing && <button onClick={() => invokeCmd('cancel_network_test')}>取消测试</button>}
+        <button onClick={lookupIp} disabled={testing}>查询外网 IP</button>
       </div>
 
       <section>
@@ -98,12 +279,21 @@ export default function App() {
             <div><b>操作系统</b>: {sys.os_name ?? '-'}</div>
             <div><b>主机名</b>: {sys.hostname ?? '-'}</div>
             <div><b>内核版本</b>: {sys.kernel_version ?? '-'}</div>
-            <div><b>OS 版本</b>: {sys.os_version ?? '-'}</div>
-            <div><b>CPU</b>: {sys.cpu_brand}</div>
+            <div><b>OS 版本</b>: {sys.os_edition ? `${sys.os_edition} ${sys.os_display_version ?? ''} (${sys.os_build ?? '-'})` : sys.os_version ?? '-'}</div>
+            {sys.virtualization?.is_virtual && (
+              <div><b>虚拟环境</b>: {[sys.virtualization.hypervisor, sys.virtualization.container].filter(Boolean).join(' / ')}</div>
+            )}
+            <div><b>CPU</b>: {sys.cpu_brand ?? '-'}</div>
             <div><b>物理核心</b>: {sys.cpu_physical_cores ?? '-'}</div>
-            <div><b>内存</b>: {Math.round(sys.used_memory/1024)} / {Math.round(sys.total_memory/1024)} MB</div>
-            <div><b>Swap</b>: {Math.round(sys.used_swap/1024)} / {Math.round(sys.total_swap/1024)} MB</div>
-            <div><b>运行时间</b>: {Math.floor(sys.uptime/3600)} 小时</div>
+            <div>
+              <b>内存</b>: {Math.round((sys.used_memory ?? 0)/1048576)} / {Math.round((sys.total_memory ?? 0)/1048576)} MB
+              {sys.memory_used_pct != null && ` (${sys.memory_used_pct.toFixed(0)}%)`}
+              {sys.available_memory != null && ` · 可用 ${Math.round(sys.available_memory/1048576)} MB`}
+            </div>
+            <div><b>Swap</b>: {Math.round((sys.used_swap ?? 0)/1024)} / {Math.round((sys.total_swap ?? 0)/1024)} MB</div>
+            <div><b>运行时间</b>: {Math.floor((sys.uptime ?? 0)/3600)} 小时</div>
+            <div><b>开机时间</b>: {sys.boot_time != null ? new Date(sys.boot_time * 1000).toLocaleString() : '-'}</div>
+            <div className="muted">数据时间 {new Date(sys.collected_at * 1000).toLocaleTimeString()}</div>
           </div>
         )}
         {sys && sys.gpus && sys.gpus.length > 0 && (
@@ -114,12 +304,39 @@ export default function App() {
                 <li key={i}>
                   <b>{gpu.name}</b>
                   {gpu.vendor !== 'Unknown' && ` (${gpu.vendor})`}
-                  {gpu.vram && ` - ${gpu.vram}`}
+                  {gpu.unified_memory ? ' - 统一内存' : gpu.vram_display && ` - ${gpu.vram_display}`}
+                  {gpu.gpu_cores != null && ` · ${gpu.gpu_cores} 核`}
+                  {gpu.metal_family && ` · ${gpu.metal_family}`}
+                  {gpu.kind !== 'unknown' && ` [${gpu.kind === 'integrated' ? '集成' : '独立'}]`}
+                  {gpu.utilization != null && ` · ${gpu.utilization.toFixed(0)}%`}
+                  {gpu.temperature != null && ` · ${gpu.temperature.toFixed(0)}℃`}
+                  {gpu.is_primary && ' ★'}
+                  {gpu.driver_version && <div className="gpu-driver">驱动 {gpu.driver_version}</div>}
                 </li>
               ))}
             </ul>
           </div>
         )}
+        {displays.length > 0 && (
+          <div className="display-section">
+            <h3>显示器</h3>
+            <ul>
+              {displays.map((d) => (
+                <li key={d.id}>
+                  <b>{d.name}</b>
+                  {d.current_resolution && ` - ${d.current_resolution.width}×${d.current_resolution.height}`}
+                  {d.refresh_rate_hz != null && ` @ ${d.refresh_rate_hz.toFixed(0)}Hz`}
+                  {d.scale_factor != null && ` · ${Math.round(d.scale_factor * 100)}%`}
+                  {d.native_resolution && ` · 原生 ${d.native_resolution.width}×${d.native_resolution.height}`}
+                  {d.gpu && ` · ${d.gpu}`}
+                  {d.is_primary && ' ★'}
+                </li>
+              ))}
+            </ul>
+            <button onClick={() => takeScreenshot()}>截图</button>
+            {screenshot && <img className="screenshot" src={screenshot} alt="截图" />}
+          </div>
+        )}
       </section>
 
       <section>
@@ -130,20 +347,17 @@ export default function App() {
             <div>
               <h3>输入设备</h3>
               <ul>
-                {audio.inputs.map((d, i) => <li key={i}>{d}</li>)}
+                {audio.inputs.map((d, i) => <li key={i}>{d}{i === audio.default_input_index && '（默认）'}</li>)}
               </ul>
             </div>
             <div>
               <h3>输出设备</h3>
               <ul>
-                {audio.outputs.map((d, i) => <li key={i}>{d}</li>)}
+                {audio.outputs.map((d, i) => <li key={i}>{d}{i === audio.default_output_index && '（默认）'}</li>)}
               </ul>
             </div>
           </div>
         )}
-        {audio && (
-          <p className="muted">默认输入: {audio.default_input ?? '-'}；默认输出: {audio.default_output ?? '-'}</p>
-        )}
       </section>
 
       <section>
@@ -158,6 +372,8 @@ export default function App() {
         {net && (
           <div className="grid">
             <div><b>外网 IP</b>: {net.external_ip ?? '-'}</div>
+            <div><b>外网 IPv6</b>: {net.external_ipv6 ?? '-'}</div>
+            <div><b>DNS 解析</b>: {net.dns_lookup_ms != null ? `${net.dns_lookup_ms} ms` : '-'}</div>
             <div><b>HTTP 延迟</b>: {net.http_latency_ms != null ? `${net.http_latency_ms} ms` : '-'}</div>
             <div><b>下载速度</b>: {net.download_mbps != null ? `${net.download_mbps.toFixed(2)} Mbps` : '-'}</div>            <div><b>上传速度</b>: {net.upload_mbps != null ? `${net.upload_mbps.toFixed(2)} Mbps` : '-'}</div>            {net.error && <div className="err">错误: {net.error}</div>}
           </div>